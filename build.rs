@@ -0,0 +1,128 @@
+//! Compiles data/procedure_table.txt and data/categories.txt into the procedure table and the
+//! declaration category descriptions of the library, and data/conformance.txt into its
+//! conformance vectors
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const TABLE: &str = "data/procedure_table.txt";
+const CATEGORIES: &str = "data/categories.txt";
+const CONFORMANCE: &str = "data/conformance.txt";
+
+fn main() {
+    println!("cargo:rerun-if-changed={TABLE}");
+    println!("cargo:rerun-if-changed={CATEGORIES}");
+    println!("cargo:rerun-if-changed={CONFORMANCE}");
+
+    let contents = fs::read_to_string(TABLE).expect("could not read procedure table");
+    let mut version = None;
+    let mut mappings = String::new();
+    let mut docs = String::from("| Category | Combined | Procedure |\n|---|---|---|\n");
+
+    for (number, line) in data_lines(&contents) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields[..] {
+            ["version", v] => version = Some(v.to_string()),
+            [category, combined, procedure] => {
+                let combination = match combined {
+                    "-" => "Combination::Standalone".to_string(),
+                    "*" => "Combination::Any".to_string(),
+                    c => format!("Combination::With({c:?})"),
+                };
+                writeln!(
+                    mappings,
+                    "    ProcedureMapping {{ category: {category:?}, combination: {combination}, procedure: Procedure::{} }},",
+                    camel_case(procedure)
+                )
+                .unwrap();
+                writeln!(docs, "| {category} | {combined} | `{procedure}` |").unwrap();
+            }
+            _ => panic!("{TABLE}:{number}: expected `category combined procedure`"),
+        }
+    }
+    let version = version.unwrap_or_else(|| panic!("{TABLE}: missing version line"));
+
+    let contents = fs::read_to_string(CATEGORIES).expect("could not read category descriptions");
+    let mut categories = String::new();
+    for (number, line) in data_lines(&contents) {
+        let (code, description) = line
+            .split_once(char::is_whitespace)
+            .unwrap_or_else(|| panic!("{CATEGORIES}:{number}: expected `category description`"));
+        writeln!(
+            categories,
+            "    CategoryInfo {{ code: {code:?}, description: {:?} }},",
+            description.trim()
+        )
+        .unwrap();
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let table = format!(
+        "/// Version of the procedure table data file\n\
+         pub const PROCEDURE_TABLE_VERSION: &str = {version:?};\n\n\
+         static MAPPINGS: &[ProcedureMapping] = &[\n{mappings}];\n\n\
+         static CATEGORIES: &[CategoryInfo] = &[\n{categories}];\n"
+    );
+    fs::write(Path::new(&out_dir).join("procedure_table.rs"), table).unwrap();
+    fs::write(Path::new(&out_dir).join("procedure_table.md"), docs).unwrap();
+
+    let contents = fs::read_to_string(CONFORMANCE).expect("could not read conformance vectors");
+    let mut version = None;
+    let mut vectors = String::new();
+    for (number, line) in data_lines(&contents) {
+        let (expected, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim_start();
+        let (mrn, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        match (expected, mrn, description.trim()) {
+            ("version", v, "") if !v.is_empty() => version = Some(v.to_string()),
+            (expected, mrn, description) if !description.is_empty() => {
+                let expected = match expected {
+                    "valid" => "Valid",
+                    "length" => "Length",
+                    "invalid-character" => "InvalidCharacter",
+                    "invalid-year" => "InvalidYear",
+                    "invalid-country-code" => "InvalidCountryCode",
+                    "invalid-check-digit" => "InvalidCheckDigit",
+                    e => panic!("{CONFORMANCE}:{number}: {e} is not an expected result"),
+                };
+                writeln!(
+                    vectors,
+                    "    ConformanceVector {{ mrn: {mrn:?}, expected: Expected::{expected}, description: {description:?} }},"
+                )
+                .unwrap();
+            }
+            _ => panic!("{CONFORMANCE}:{number}: expected `expected mrn description`"),
+        }
+    }
+    let version = version.unwrap_or_else(|| panic!("{CONFORMANCE}: missing version line"));
+    let conformance = format!(
+        "/// Version of the conformance vectors data file\n\
+         pub const CONFORMANCE_VERSION: &str = {version:?};\n\n\
+         static VECTORS: &[ConformanceVector] = &[\n{vectors}];\n"
+    );
+    fs::write(Path::new(&out_dir).join("conformance.rs"), conformance).unwrap();
+}
+
+/// Numbered lines of a data file, without blank lines and comments
+fn data_lines(contents: &str) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Converts a snake case procedure name to its variant name
+fn camel_case(s: &str) -> String {
+    s.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -0,0 +1,222 @@
+# German messages
+
+cli-error = Fehler
+cli-caused-by = Ursache
+cli-category = KATEGORIE
+cli-combined = KOMBINIERT
+cli-procedure = VERFAHREN
+cli-description = BESCHREIBUNG
+cli-finding-error = Fehler
+cli-warning = Warnung
+cli-info = Hinweis
+cli-valid = gültig
+cli-repair = meinten Sie { $mrn }, mit { $replacement } statt { $found } an Position { $position }?
+cli-online-found = gültig, vom Nachverfolgungsdienst gefunden
+cli-online-not-found = gültig, vom Nachverfolgungsdienst nicht gefunden
+cli-offline-only = gültig, nicht online geprüft
+cli-stage = STUFE
+cli-time = ZEIT
+cli-per-mrn = PRO MRN
+cli-allocations = ALLOKATIONEN
+
+## Errors
+
+error-country-code-length = { $value } ist kein gültiger Ländercode, er muss aus genau zwei Zeichen bestehen (z. B. 'IT')
+error-invalid-procedure-category = { $value } ist keine gültige Verfahrenskategorie
+error-invalid-procedure-category-suggestion = { $value } ist keine gültige Verfahrenskategorie, meinten Sie { $suggestion }?
+error-invalid-procedure-category-combination = { $procedure_category }-{ $combination } ist keine gültige Kombination von Verfahrenskategorien
+error-invalid-combined-category = { $procedure_category } kann nicht mit { $combined } kombiniert werden
+error-invalid-combined-category-allowed = { $procedure_category } kann nicht mit { $combined } kombiniert werden, nur mit { $allowed }
+error-combined-without-category = Die kombinierte Verfahrenskategorie { $value } wurde ohne Verfahrenskategorie angegeben
+error-not-alphanumeric = { $value } ist kein alphanumerisches Zeichen
+error-invalid-character = { $value } an Byte { $position } ist weder eine Ziffer noch ein Großbuchstabe
+error-declaration-office-length = { $value } ist keine gültige Anmeldezollstelle, sie muss eine sechsstellige Zollstellennummer sein, optional mit vorangestelltem Ländercode
+error-source-exhausted = Die MRN-Quelle hat keine Kennungen mehr
+error-invalid-namespace = { $value } ist kein gültiger Namensraum, er muss zwischen 0 und 15 liegen
+error-grn-length = { $value } ist keine gültige GRN, sie muss 17 bis 24 Zeichen lang sein
+error-grn-prefix = { $value } ist keine gültige GRN, sie muss mit zwei Jahresziffern und einem Ländercode beginnen
+error-grn-check-character = { $value } ist keine gültige GRN, ihr Prüfzeichen muss eine Ziffer oder ein Großbuchstabe sein
+error-lrn-length = { $value } ist keine gültige LRN-Länge, sie muss das Präfix aufnehmen und darf höchstens 22 Zeichen betragen
+error-invalid-lrn-prefix = { $value } ist kein gültiges LRN-Präfix, es darf nur druckbare ASCII-Zeichen enthalten
+error-invalid-eori = { $value } ist keine gültige EORI, sie muss aus einem Ländercode und bis zu 15 alphanumerischen Zeichen bestehen
+error-invalid-owner-code = { $value } ist kein gültiger Eigentümercode eines Containers, er muss aus genau drei Buchstaben bestehen
+error-container-number-format = { $value } ist keine gültige Containernummer, sie muss aus einem Eigentümercode, U, J oder Z und sieben Ziffern bestehen
+error-invalid-ducr = { $value } ist keine gültige DUCR, sie muss aus einer Jahresziffer, einem Ländercode und 6 bis 32 Referenzzeichen bestehen
+error-invalid-mucr = { $value } ist keine gültige MUCR
+error-mrn-length = { $value } ist keine gültige MRN, sie muss genau 18 Zeichen lang sein
+error-invalid-year = { $value } ist keine gültige MRN, sie muss mit den letzten zwei Ziffern des Jahres beginnen
+error-invalid-country-code = { $value } ist keine gültige MRN, ihr Ländercode muss aus zwei Großbuchstaben bestehen
+error-invalid-check-digit = { $mrn } ist keine gültige MRN, ihre Prüfziffer muss { $expected } sein
+error-not-an-ens-mrn = { $mrn } ist keine ENS-MRN, { $procedure_char } ist kein Verfahren einer summarischen Eingangsanmeldung
+error-not-an-ens-procedure = { $procedure } ist kein Verfahren einer summarischen Eingangsanmeldung
+error-not-a-transit-mrn = { $mrn } ist keine Versand-MRN, { $procedure_char } ist kein Verfahren einer Versandanmeldung
+error-not-a-transit-procedure = { $procedure } ist kein Verfahren einer Versandanmeldung
+error-not-a-cds-mrn = { $mrn } ist keine CDS-MRN, { $procedure_char } ist kein Verfahren einer Einfuhr- oder Ausfuhranmeldung
+error-not-a-cds-procedure = { $procedure } ist kein Verfahren einer Einfuhr- oder Ausfuhranmeldung in CDS
+error-not-a-ctc-country = { $value } ist keine Vertragspartei des Übereinkommens über ein gemeinsames Versandverfahren
+error-unexpected-country = { $found } ist nicht das Land dieser MRNs, es muss { $expected } sein
+error-issue-year = { $value } ist kein gültiges Ausstellungsjahr, es müssen die letzten zwei Ziffern eines Jahres sein
+error-invalid-rules = Ungültige Verfahrensregeln: { $value }
+error-invalid-check = Ungültige Prüfung: { $value }
+error-check-failed = { $mrn } besteht die Prüfung { $check } nicht
+error-unsupported-language = { $value } ist keine unterstützte Sprache, sie muss en, de, fr oder el sein
+error-online-verification = Online-Prüfung fehlgeschlagen: { $value }
+error-unknown-country = { $value } ist kein bekanntes Land
+error-unknown-country-suggestion = { $value } ist kein bekanntes Land, meinten Sie { $suggestion }?
+error-unknown-procedure = { $value } ist kein bekanntes Verfahren, es muss ein Verfahrenszeichen wie A oder ein Name wie export_only sein
+
+## Validation warnings
+
+warning-future-year = Das Jahr { $year } liegt in der Zukunft, das aktuelle Jahr ist { $current_year }
+warning-office-country-mismatch = Der Abschnitt der Anmeldezollstelle beginnt mit { $office_prefix } statt mit dem Ländercode { $country_code }
+warning-unknown-country = Der Ländercode { $country_code } ist kein bekanntes ausstellendes Land
+warning-unknown-country-suggestion = Der Ländercode { $country_code } ist kein bekanntes ausstellendes Land, meinten Sie { $suggestion }?
+warning-office-not-numeric = Die Zollstellennummer { $office_number } enthält Buchstaben, obwohl die Zollstellennummern von { $country_code } aus Ziffern bestehen
+warning-declaration-year-mismatch = Das Jahr { $year } passt nicht zum Anmeldedatum { $declaration_date }
+
+## Procedures, by procedure character
+
+procedure-A = Nur Ausfuhr
+procedure-B = Ausfuhr und summarische Ausgangsanmeldung
+procedure-C = Nur summarische Ausgangsanmeldung
+procedure-D = Mitteilung über die Wiederausfuhr
+procedure-E = Versendung von Waren im Verkehr mit Gebieten mit besonderen Steuervorschriften
+procedure-J = Nur Versandanmeldung
+procedure-K = Versandanmeldung und summarische Ausgangsanmeldung
+procedure-L = Versandanmeldung und summarische Eingangsanmeldung
+procedure-M = Nachweis des zollrechtlichen Status von Unionswaren
+procedure-R = Nur Einfuhranmeldung
+procedure-S = Einfuhranmeldung und summarische Eingangsanmeldung
+procedure-T = Nur summarische Eingangsanmeldung
+procedure-U = Anmeldung zur vorübergehenden Verwahrung
+procedure-V = Verbringung von Waren im Verkehr mit Gebieten mit besonderen Steuervorschriften
+procedure-W = Anmeldung zur vorübergehenden Verwahrung und summarische Eingangsanmeldung
+
+## Declaration categories
+
+category-A1 = Summarische Ausgangsanmeldung
+category-A2 = Summarische Ausgangsanmeldung für Post- und Expresssendungen
+category-A3 = Mitteilung über die Wiederausfuhr
+category-B1 = Ausfuhranmeldung und Wiederausfuhranmeldung
+category-B2 = Ausfuhranmeldung zur passiven Veredelung
+category-B3 = Ausfuhranmeldung zur Zolllagerung von Unionswaren
+category-B4 = Versendung von Waren im Verkehr mit Gebieten mit besonderen Steuervorschriften
+category-C1 = Vereinfachte Ausfuhranmeldung
+category-D1 = Versandanmeldung
+category-D2 = Versandanmeldung mit reduziertem Datensatz
+category-D3 = Versandanmeldung mit elektronischem Beförderungsdokument
+category-E1 = Nachweis des zollrechtlichen Status von Unionswaren
+category-E2 = Zollgutmanifest
+category-F1a = Summarische Eingangsanmeldung, See- und Binnenschifffahrt, vollständiger Datensatz
+category-F1b = Summarische Eingangsanmeldung, See- und Binnenschifffahrt, Teildatensatz (Haus-Konnossement)
+category-F1c = Summarische Eingangsanmeldung, See- und Binnenschifffahrt, Teildatensatz (Master-Konnossement)
+category-F1d = Summarische Eingangsanmeldung, See- und Binnenschifffahrt, Teildatensatz (untergeordnetes Konnossement)
+category-F2a = Summarische Eingangsanmeldung, Luftfracht, vollständiger Datensatz
+category-F2b = Summarische Eingangsanmeldung, Luftfracht, Teildatensatz (Master-Luftfrachtbrief)
+category-F2c = Summarische Eingangsanmeldung, Luftfracht, Teildatensatz (Haus-Luftfrachtbrief)
+category-F2d = Summarische Eingangsanmeldung, Luftfracht, Mindestdatensatz vor dem Verladen
+category-F3a = Summarische Eingangsanmeldung, Expresssendungen, vollständiger Datensatz
+category-F3b = Summarische Eingangsanmeldung, Expresssendungen, Mindestdatensatz vor dem Verladen
+category-F4a = Summarische Eingangsanmeldung, Postsendungen, vollständiger Datensatz
+category-F4b = Summarische Eingangsanmeldung, Postsendungen, Teildatensatz (Hauptbehälter)
+category-F4c = Summarische Eingangsanmeldung, Postsendungen, Mindestdatensatz vor dem Verladen
+category-F5 = Summarische Eingangsanmeldung, Straße und Schiene
+category-G4 = Anmeldung zur vorübergehenden Verwahrung
+category-H1 = Überlassung zum zollrechtlich freien Verkehr
+category-H2 = Zolllagerverfahren
+category-H3 = Vorübergehende Verwendung
+category-H4 = Aktive Veredelung
+category-H5 = Verbringung von Waren im Verkehr mit Gebieten mit besonderen Steuervorschriften
+category-H6 = Überlassung zum zollrechtlich freien Verkehr im Postverkehr
+category-I1 = Vereinfachte Einfuhranmeldung
+
+## Command line help
+
+about = Kommandozeilenprogramm zum Erzeugen gültiger MRNs
+about-grn = Garantiereferenznummern (GRN) erzeugen oder prüfen
+about-lrn = Lokale Referenznummern (LRN) erzeugen, optional mit MRNs gepaart
+about-dataset = Synthetische Zollanmeldungen erzeugen, jeweils mit passender MRN, LRN, EORI, Zollstelle, Verfahren sowie Abgabe- und Annahmezeit
+about-eori = EORI-Nummern erzeugen oder prüfen
+about-container = Containernummern nach ISO 6346 erzeugen oder prüfen
+about-ucr = Britische Declaration und Master Unique Consignment References erzeugen oder prüfen
+about-validate = MRNs prüfen
+about-list-categories = Anmeldekategorien mit ihren Kombinationen, Verfahren und Beschreibungen ausgeben
+about-explain = Eine kommentierte Aufschlüsselung einer MRN ausgeben
+about-complete = Die gültigen MRNs ausgeben, die zu einer MRN mit unbekannten Zeichen passen, z. B. 24DK0047??????????
+about-check-duplicates = MRNs melden, die innerhalb einer Datei oder über mehrere Dateien hinweg mehrfach vorkommen
+about-grep = Die MRNs von Dateien ausgeben, die zu Masken wie ??DK0047* passen, wobei ? für ein beliebiges Zeichen und * für beliebig viele steht
+about-fix = Die Prüfziffern von MRN-Dateien korrigieren und die Änderungen als Diff ausgeben
+about-fixtures = Eine stabile Menge von Beispiel-MRNs aller Verfahren und mehrerer Länder ausgeben, um sie als Referenztestdaten einzuchecken
+about-mcp = MRN-Erzeugung, -Prüfung und -Zerlegung als Model-Context-Protocol-Werkzeuge über stdio anbieten
+about-schema = Das JSON-Schema der JSON-Ausgabe, der Fehler oder des Manifests ausgeben
+about-bench = Den Erzeugungsdurchsatz messen, mit der Zeit und den Allokationen jeder Stufe
+about-serve = MRN-Erzeugung, MRN-Prüfung und MRN-Leases über HTTP für Testorchestrierungen anbieten
+help-country_code = Ländercode oder Ländername der MRN, oder kommagetrennte Ländercodes, über die die MRNs verteilt werden
+help-countries_file = Datei mit einem Ländercode oder -namen pro Zeile, optional gefolgt von einem Komma und der Anzahl der für ihn zu erzeugenden MRNs, z. B. DK,100
+help-weights = TOML- oder JSON-Datei mit den Gewichten der Länder, Verfahren und Zollstellen und dem Anteil der MRNs mit falscher Prüfziffer
+help-known_countries = Nur die Ländercodes bekannter Mitgliedstaaten und Transitländer akzeptieren
+help-country_weights = Kommagetrennte Gewichte der Ländercodes, z. B. 3,1
+help-number_of_mrns = Anzahl der zu erzeugenden MRNs
+help-count_per_procedure = Diese Anzahl MRNs für jedes Verfahren von A bis W erzeugen, in Gruppen, die in der einfachen Ausgabe mit ihrem Verfahrenszeichen gekennzeichnet sind
+help-all_countries = Diese Anzahl MRNs, ohne Angabe 1, für jedes Land von --country-code, einer Länder- oder Gewichtungsdatei oder einer Voreinstellung erzeugen, ohne Länder für jedes bekannte Land
+help-procedure_category = Verfahrenskategorie
+help-combined = Kombinierte Verfahrenskategorie
+help-declaration_office = Anmeldezollstelle
+help-office_with_country = Der Anmeldezollstelle den Ländercode voranstellen, wie in den Kennungen des Zollstellenverzeichnisses (z. B. DK004700)
+help-random_office = Eine zufällige Zollstelle des Landes aus dem Zollstellenverzeichnis als Anmeldezollstelle verwenden
+help-check_office = Prüfen, ob die Anmeldezollstelle im Zollstellenverzeichnis steht
+help-preset = Die Konventionen eines bestimmten Systems anwenden
+help-profile = Die MRNs über die Länder und Verfahren eines realistischen Verkehrsprofils verteilen oder die Argumente eines Profils der Konfigurationsdatei verwenden
+help-config = Konfigurationsdatei mit den Profilen von --profile, standardmäßig mrn-generator.toml im Arbeitsverzeichnis
+help-rules = TOML- oder JSON-Datei mit Verfahrensregeln, die die eingebauten erweitern oder überschreiben
+help-year_range = Die MRNs über einen Bereich von Ausstellungsjahren verteilen, z. B. 20..=24 oder 2020..2025
+help-year_weights = Kommagetrennte Gewichte der Jahre im Jahresbereich, z. B. 1,1,2,4,8
+help-spread_over = Die MRNs über die Tage eines Datumsbereichs verteilen, beide Enden eingeschlossen, z. B. 2023-01-01..2024-12-31
+help-issued_at = Das mit --spread-over gewählte Ausstellungsdatum als Spalte issued_at ausgeben
+help-with_lrn = Jede MRN mit einer zufälligen LRN paaren, die in der Textausgabe davor und sonst als Spalte lrn ausgegeben wird
+help-id_source = Verfahren zum Füllen des freien Abschnitts der erzeugten Kennungen
+help-seed = Startwert für reproduzierbare Ausgaben der zufälligen oder permutierten Quelle
+help-sequence_start = Erster Zählerwert der sequentiellen oder permutierten Quelle
+help-namespace = Namensraum von 0 bis 15, der das erste Zeichen des freien Segments reserviert, sodass Läufe in verschiedenen Namensräumen nie dieselben Kennungen generieren
+help-seen_filter = Bloom-Filter-Datei bereits ausgegebener MRNs, um Wiederholungen über mehrere Läufe zu vermeiden
+help-registry = Registerdatei ausgegebener MRNs, während des Laufs gesperrt, damit mehrere Läufe sie gleichzeitig nutzen können
+help-audit_log = JSON-Lines-Datei, an die jede generierte MRN mit Zeitpunkt, Parametern und Seed ihrer Generierung angehängt wird
+help-webhook = URL, an die nach dem Schreiben eine Zusammenfassung der generierten MRNs mit Anzahl, Stichprobe und Prüfsummen gesendet wird
+help-output = Datei, in die die erzeugten MRNs statt in die Standardausgabe geschrieben werden
+help-format = Format der erzeugten MRNs
+help-delimiter = Trennzeichen der Felder der tsv-Ausgabe statt eines Tabulators, ein einzelnes Zeichen oder `tab`
+help-split_by = Die erzeugten MRNs in eine Datei pro Gruppe im Ausgabeverzeichnis schreiben
+help-output_dir = Verzeichnis der mit --split-by geschriebenen Dateien
+help-manifest = Eine manifest.json neben die Ausgabedateien schreiben, mit ihren Zeilenzahlen, SHA-256-Prüfsummen und den Erzeugungsparametern
+help-exec = Shell-Befehl, der für jede erzeugte MRN ausgeführt wird, wobei `{"{}"}` durch die MRN, `{"{country}"}` durch ihren Ländercode, `{"{office}"}` durch ihre Anmeldezollstelle und `{"{lrn}"}` durch ihre LRN von --with-lrn ersetzt wird
+help-exec_jobs = Höchstzahl gleichzeitig laufender --exec-Befehle
+help-queue_size = Anzahl generierter MRNs, die auf das Schreiben warten, bevor die Generierung auf die Ausgabe wartet
+help-parallel = Mit allen Kernen generieren, in Blöcken von 1024 MRNs, die jeweils aus --seed geseedet werden
+help-jobs = Anzahl der mit --parallel verwendeten Threads, standardmäßig alle Kerne
+help-ordered = Die MRNs von --parallel in der Reihenfolge ihrer Blöcke schreiben, sodass die Ausgabe für denselben Seed unabhängig von der Threadanzahl gleich ist
+help-append = An die Ausgabedatei anhängen, statt sie zu überschreiben
+help-compress = Die Ausgabedateien beim Schreiben komprimieren
+help-skip_existing = Niemals eine MRN erzeugen, die bereits in der Ausgabedatei steht
+help-fail_fast = Beim ersten fehlgeschlagenen Bezeichner anhalten, Standard beim Erzeugen
+help-continue_on_error = Fehlgeschlagene Bezeichner melden und mit dem Rest des Stapels fortfahren, am Ende mit Fehler beenden, Standard beim Prüfen
+help-dry_run = Den Erzeugungsplan ausgeben, den Lauf wie er sich aus dem Profil der Konfigurationsdatei, der Gewichtungsdatei, der Voreinstellung und der Befehlszeile ergibt, ohne zu erzeugen
+help-lang = Sprache der Meldungen, standardmäßig die Sprache der Umgebung
+
+
+## explain command
+
+explain-year = Jahr { $year }, ausgegeben zwischen { $year }-01-01 und { $year }-12-31
+explain-invalid-year = Jahr { $year }, keine zwei Ziffern
+explain-retention-expired = , älter als die Aufbewahrungsfrist von { $retention } Jahren
+explain-country-code = Ländercode { $country_code }
+explain-office = Zollstelle { $office }, { $name }
+explain-serial = laufende Nummer { $serial }
+explain-procedure = Verfahren { $char }, { $description }
+explain-no-procedure = kein Verfahren, { $char } ist kein Verfahrenskennzeichen
+explain-check-digit-valid = Prüfziffer { $digit }, gültig
+explain-check-digit-invalid = Prüfziffer { $digit }, ungültig, sie muss { $expected } sein
+
+
+## bench command
+
+bench-throughput = { $count } MRNs in { $time }, { $rate } MRNs/s, maximaler Heap { $peak } Bytes
@@ -0,0 +1,222 @@
+# Greek messages
+
+cli-error = Σφάλμα
+cli-caused-by = Αιτία
+cli-category = ΚΑΤΗΓΟΡΙΑ
+cli-combined = ΣΥΝΔΥΑΣΜΟΣ
+cli-procedure = ΔΙΑΔΙΚΑΣΙΑ
+cli-description = ΠΕΡΙΓΡΑΦΗ
+cli-finding-error = σφάλμα
+cli-warning = προειδοποίηση
+cli-info = πληροφορία
+cli-valid = έγκυρο
+cli-repair = μήπως εννοείτε { $mrn }, με { $replacement } αντί για { $found } στη θέση { $position };
+cli-online-found = έγκυρο, βρέθηκε από την υπηρεσία παρακολούθησης
+cli-online-not-found = έγκυρο, δεν βρέθηκε από την υπηρεσία παρακολούθησης
+cli-offline-only = έγκυρο, δεν ελέγχθηκε διαδικτυακά
+cli-stage = ΣΤΑΔΙΟ
+cli-time = ΧΡΟΝΟΣ
+cli-per-mrn = ΑΝΑ MRN
+cli-allocations = ΔΕΣΜΕΥΣΕΙΣ
+
+## Errors
+
+error-country-code-length = Το { $value } δεν είναι έγκυρος κωδικός χώρας, πρέπει να έχει ακριβώς δύο χαρακτήρες (π.χ. 'IT')
+error-invalid-procedure-category = Το { $value } δεν είναι έγκυρη κατηγορία διαδικασίας
+error-invalid-procedure-category-suggestion = Το { $value } δεν είναι έγκυρη κατηγορία διαδικασίας, μήπως εννοείτε { $suggestion };
+error-invalid-procedure-category-combination = Το { $procedure_category }-{ $combination } δεν είναι έγκυρος συνδυασμός κατηγοριών διαδικασίας
+error-invalid-combined-category = Η { $procedure_category } δεν μπορεί να συνδυαστεί με την { $combined }
+error-invalid-combined-category-allowed = Η { $procedure_category } δεν μπορεί να συνδυαστεί με την { $combined }, μόνο με { $allowed }
+error-combined-without-category = Η συνδυασμένη κατηγορία διαδικασίας { $value } δόθηκε χωρίς κατηγορία διαδικασίας
+error-not-alphanumeric = Το { $value } δεν είναι αλφαριθμητικός χαρακτήρας
+error-invalid-character = Το { $value } στο byte { $position } δεν είναι ψηφίο ή κεφαλαίο γράμμα
+error-declaration-office-length = Το { $value } δεν είναι έγκυρο τελωνείο διασάφησης, πρέπει να είναι αριθμός τελωνείου έξι χαρακτήρων, προαιρετικά με τον κωδικό χώρας μπροστά
+error-source-exhausted = Η πηγή MRN δεν έχει άλλα αναγνωριστικά
+error-invalid-namespace = Το { $value } δεν είναι έγκυρος χώρος ονομάτων, πρέπει να είναι από 0 έως 15
+error-grn-length = Το { $value } δεν είναι έγκυρο GRN, πρέπει να έχει 17 έως 24 χαρακτήρες
+error-grn-prefix = Το { $value } δεν είναι έγκυρο GRN, πρέπει να αρχίζει με δύο ψηφία έτους και κωδικό χώρας
+error-grn-check-character = Το { $value } δεν είναι έγκυρο GRN, ο χαρακτήρας ελέγχου του πρέπει να είναι ψηφίο ή κεφαλαίο γράμμα
+error-lrn-length = Το { $value } δεν είναι έγκυρο μήκος LRN, πρέπει να χωρά το πρόθεμα και να μην υπερβαίνει τους 22 χαρακτήρες
+error-invalid-lrn-prefix = Το { $value } δεν είναι έγκυρο πρόθεμα LRN, πρέπει να περιέχει μόνο εκτυπώσιμους χαρακτήρες ASCII
+error-invalid-eori = Το { $value } δεν είναι έγκυρο EORI, πρέπει να αποτελείται από κωδικό χώρας και έως 15 αλφαριθμητικούς χαρακτήρες
+error-invalid-owner-code = Το { $value } δεν είναι έγκυρος κωδικός ιδιοκτήτη εμπορευματοκιβωτίου, πρέπει να έχει ακριβώς τρία γράμματα
+error-container-number-format = Το { $value } δεν είναι έγκυρος αριθμός εμπορευματοκιβωτίου, πρέπει να αποτελείται από κωδικό ιδιοκτήτη, U, J ή Z και επτά ψηφία
+error-invalid-ducr = Το { $value } δεν είναι έγκυρο DUCR, πρέπει να αποτελείται από ένα ψηφίο έτους, κωδικό χώρας και 6 έως 32 χαρακτήρες αναφοράς
+error-invalid-mucr = Το { $value } δεν είναι έγκυρο MUCR
+error-mrn-length = Το { $value } δεν είναι έγκυρο MRN, πρέπει να έχει ακριβώς 18 χαρακτήρες
+error-invalid-year = Το { $value } δεν είναι έγκυρο MRN, πρέπει να αρχίζει με τα δύο τελευταία ψηφία του έτους
+error-invalid-country-code = Το { $value } δεν είναι έγκυρο MRN, ο κωδικός χώρας του πρέπει να είναι δύο κεφαλαία γράμματα
+error-invalid-check-digit = Το { $mrn } δεν είναι έγκυρο MRN, το ψηφίο ελέγχου του πρέπει να είναι { $expected }
+error-not-an-ens-mrn = Το { $mrn } δεν είναι MRN συνοπτικής διασάφησης εισόδου, το { $procedure_char } δεν είναι διαδικασία συνοπτικής διασάφησης εισόδου
+error-not-an-ens-procedure = Η { $procedure } δεν είναι διαδικασία συνοπτικής διασάφησης εισόδου
+error-not-a-transit-mrn = Το { $mrn } δεν είναι MRN διαμετακόμισης, το { $procedure_char } δεν είναι διαδικασία διασάφησης διαμετακόμισης
+error-not-a-transit-procedure = Η { $procedure } δεν είναι διαδικασία διασάφησης διαμετακόμισης
+error-not-a-cds-mrn = Το { $mrn } δεν είναι MRN του CDS, το { $procedure_char } δεν είναι διαδικασία διασάφησης εισαγωγής ή εξαγωγής
+error-not-a-cds-procedure = Η { $procedure } δεν είναι διαδικασία διασάφησης εισαγωγής ή εξαγωγής του CDS
+error-not-a-ctc-country = Το { $value } δεν είναι συμβαλλόμενο μέρος της σύμβασης για το κοινό καθεστώς διαμετακόμισης
+error-unexpected-country = Το { $found } δεν είναι η χώρα αυτών των MRN, πρέπει να είναι { $expected }
+error-issue-year = Το { $value } δεν είναι έγκυρο έτος έκδοσης, πρέπει να είναι τα δύο τελευταία ψηφία ενός έτους
+error-invalid-rules = Μη έγκυροι κανόνες διαδικασιών: { $value }
+error-invalid-check = Μη έγκυρος έλεγχος: { $value }
+error-check-failed = Το { $mrn } αποτυγχάνει στον έλεγχο { $check }
+error-unsupported-language = Το { $value } δεν είναι υποστηριζόμενη γλώσσα, πρέπει να είναι en, de, fr ή el
+error-online-verification = Η διαδικτυακή επαλήθευση απέτυχε: { $value }
+error-unknown-country = Το { $value } δεν είναι γνωστή χώρα
+error-unknown-country-suggestion = Το { $value } δεν είναι γνωστή χώρα, μήπως εννοείτε { $suggestion };
+error-unknown-procedure = Το { $value } δεν είναι γνωστή διαδικασία, πρέπει να είναι χαρακτήρας διαδικασίας όπως A ή όνομα όπως export_only
+
+## Validation warnings
+
+warning-future-year = το έτος { $year } βρίσκεται στο μέλλον, το τρέχον έτος είναι { $current_year }
+warning-office-country-mismatch = το τμήμα του τελωνείου διασάφησης αρχίζει με { $office_prefix } αντί για τον κωδικό χώρας { $country_code }
+warning-unknown-country = ο κωδικός χώρας { $country_code } δεν είναι γνωστή χώρα έκδοσης
+warning-unknown-country-suggestion = ο κωδικός χώρας { $country_code } δεν είναι γνωστή χώρα έκδοσης, μήπως εννοείτε { $suggestion };
+warning-office-not-numeric = ο αριθμός τελωνείου διασάφησης { $office_number } περιέχει γράμματα, ενώ οι αριθμοί τελωνείων της { $country_code } είναι ψηφία
+warning-declaration-year-mismatch = το έτος { $year } δεν αντιστοιχεί στην ημερομηνία διασάφησης { $declaration_date }
+
+## Procedures, by procedure character
+
+procedure-A = Μόνο εξαγωγή
+procedure-B = Εξαγωγή και συνοπτική διασάφηση εξόδου
+procedure-C = Μόνο συνοπτική διασάφηση εξόδου
+procedure-D = Κοινοποίηση επανεξαγωγής
+procedure-E = Αποστολή εμπορευμάτων στο πλαίσιο εμπορικών συναλλαγών με ειδικά φορολογικά εδάφη
+procedure-J = Μόνο διασάφηση διαμετακόμισης
+procedure-K = Διασάφηση διαμετακόμισης και συνοπτική διασάφηση εξόδου
+procedure-L = Διασάφηση διαμετακόμισης και συνοπτική διασάφηση εισόδου
+procedure-M = Απόδειξη του τελωνειακού καθεστώτος ενωσιακών εμπορευμάτων
+procedure-R = Μόνο διασάφηση εισαγωγής
+procedure-S = Διασάφηση εισαγωγής και συνοπτική διασάφηση εισόδου
+procedure-T = Μόνο συνοπτική διασάφηση εισόδου
+procedure-U = Διασάφηση προσωρινής εναπόθεσης
+procedure-V = Εισαγωγή εμπορευμάτων στο πλαίσιο εμπορικών συναλλαγών με ειδικά φορολογικά εδάφη
+procedure-W = Διασάφηση προσωρινής εναπόθεσης και συνοπτική διασάφηση εισόδου
+
+## Declaration categories
+
+category-A1 = Συνοπτική διασάφηση εξόδου
+category-A2 = Συνοπτική διασάφηση εξόδου για ταχυδρομικές αποστολές και αποστολές ταχείας παράδοσης
+category-A3 = Κοινοποίηση επανεξαγωγής
+category-B1 = Διασάφηση εξαγωγής και διασάφηση επανεξαγωγής
+category-B2 = Διασάφηση εξαγωγής για παθητική τελειοποίηση
+category-B3 = Διασάφηση εξαγωγής για τελωνειακή αποταμίευση ενωσιακών εμπορευμάτων
+category-B4 = Αποστολή εμπορευμάτων στο πλαίσιο εμπορικών συναλλαγών με ειδικά φορολογικά εδάφη
+category-C1 = Απλουστευμένη διασάφηση εξαγωγής
+category-D1 = Διασάφηση διαμετακόμισης
+category-D2 = Διασάφηση διαμετακόμισης με μειωμένο σύνολο δεδομένων
+category-D3 = Διασάφηση διαμετακόμισης με ηλεκτρονικό έγγραφο μεταφοράς
+category-E1 = Απόδειξη του τελωνειακού καθεστώτος ενωσιακών εμπορευμάτων
+category-E2 = Τελωνειακό δηλωτικό εμπορευμάτων
+category-F1a = Συνοπτική διασάφηση εισόδου, θαλάσσιες και εσωτερικές πλωτές μεταφορές, πλήρες σύνολο δεδομένων
+category-F1b = Συνοπτική διασάφηση εισόδου, θαλάσσιες και εσωτερικές πλωτές μεταφορές, μερικό σύνολο δεδομένων (φορτωτική house)
+category-F1c = Συνοπτική διασάφηση εισόδου, θαλάσσιες και εσωτερικές πλωτές μεταφορές, μερικό σύνολο δεδομένων (φορτωτική master)
+category-F1d = Συνοπτική διασάφηση εισόδου, θαλάσσιες και εσωτερικές πλωτές μεταφορές, μερικό σύνολο δεδομένων (φορτωτική κατώτερου επιπέδου)
+category-F2a = Συνοπτική διασάφηση εισόδου, αεροπορικό φορτίο, πλήρες σύνολο δεδομένων
+category-F2b = Συνοπτική διασάφηση εισόδου, αεροπορικό φορτίο, μερικό σύνολο δεδομένων (αεροπορική φορτωτική master)
+category-F2c = Συνοπτική διασάφηση εισόδου, αεροπορικό φορτίο, μερικό σύνολο δεδομένων (αεροπορική φορτωτική house)
+category-F2d = Συνοπτική διασάφηση εισόδου, αεροπορικό φορτίο, ελάχιστο σύνολο δεδομένων πριν από τη φόρτωση
+category-F3a = Συνοπτική διασάφηση εισόδου, αποστολές ταχείας παράδοσης, πλήρες σύνολο δεδομένων
+category-F3b = Συνοπτική διασάφηση εισόδου, αποστολές ταχείας παράδοσης, ελάχιστο σύνολο δεδομένων πριν από τη φόρτωση
+category-F4a = Συνοπτική διασάφηση εισόδου, ταχυδρομικές αποστολές, πλήρες σύνολο δεδομένων
+category-F4b = Συνοπτική διασάφηση εισόδου, ταχυδρομικές αποστολές, μερικό σύνολο δεδομένων (κύριος περιέκτης)
+category-F4c = Συνοπτική διασάφηση εισόδου, ταχυδρομικές αποστολές, ελάχιστο σύνολο δεδομένων πριν από τη φόρτωση
+category-F5 = Συνοπτική διασάφηση εισόδου, οδικές και σιδηροδρομικές μεταφορές
+category-G4 = Διασάφηση προσωρινής εναπόθεσης
+category-H1 = Θέση σε ελεύθερη κυκλοφορία
+category-H2 = Τελωνειακή αποταμίευση
+category-H3 = Προσωρινή εισαγωγή
+category-H4 = Τελειοποίηση προς επανεξαγωγή
+category-H5 = Εισαγωγή εμπορευμάτων στο πλαίσιο εμπορικών συναλλαγών με ειδικά φορολογικά εδάφη
+category-H6 = Θέση σε ελεύθερη κυκλοφορία στην ταχυδρομική κίνηση
+category-I1 = Απλουστευμένη διασάφηση εισαγωγής
+
+## Command line help
+
+about = Βοηθητικό πρόγραμμα γραμμής εντολών για τη δημιουργία έγκυρων MRN
+about-grn = Δημιουργία ή έλεγχος αριθμών αναφοράς εγγύησης (GRN)
+about-lrn = Δημιουργία τοπικών αριθμών αναφοράς (LRN), προαιρετικά σε ζεύγη με MRN
+about-dataset = Δημιουργία συνθετικών διασαφήσεων, καθεμία με αντίστοιχα MRN, LRN, EORI, τελωνείο, διαδικασία και ώρες κατάθεσης και αποδοχής
+about-eori = Δημιουργία ή έλεγχος αριθμών EORI
+about-container = Δημιουργία ή έλεγχος αριθμών εμπορευματοκιβωτίων ISO 6346
+about-ucr = Δημιουργία ή έλεγχος βρετανικών Declaration και Master Unique Consignment References
+about-validate = Έλεγχος MRN
+about-list-categories = Εμφάνιση των κατηγοριών διασάφησης με τους συνδυασμούς, τις διαδικασίες και τις περιγραφές τους
+about-explain = Εμφάνιση σχολιασμένης ανάλυσης ενός MRN
+about-complete = Εμφάνιση των έγκυρων MRN που ταιριάζουν σε ένα MRN με άγνωστους χαρακτήρες, π.χ. 24DK0047??????????
+about-check-duplicates = Αναφορά των MRN που εμφανίζονται περισσότερες από μία φορές σε ένα ή περισσότερα αρχεία
+about-grep = Εμφάνιση των MRN αρχείων που ταιριάζουν σε μάσκες όπως ??DK0047*, όπου ? είναι οποιοσδήποτε χαρακτήρας και * οποιοσδήποτε αριθμός χαρακτήρων
+about-fix = Διόρθωση των ψηφίων ελέγχου αρχείων MRN, με εμφάνιση των αλλαγών ως diff
+about-fixtures = Εμφάνιση ενός σταθερού συνόλου παραδειγμάτων MRN όλων των διαδικασιών και αρκετών χωρών, για καταχώριση ως δεδομένα αναφοράς δοκιμών
+about-mcp = Παροχή παραγωγής, επικύρωσης και ανάλυσης MRN ως εργαλεία Model Context Protocol μέσω stdio
+about-schema = Εμφάνιση του JSON Schema της έξοδου JSON, των σφαλμάτων ή του manifest
+about-bench = Μέτρηση της ταχύτητας παραγωγής, με τον χρόνο και τις δεσμεύσεις μνήμης κάθε σταδίου
+about-serve = Διάθεση παραγωγής, επικύρωσης και μισθώσεων MRN σε ενορχηστρωτές δοκιμών μέσω HTTP
+help-country_code = Κωδικός ή όνομα χώρας του MRN, ή κωδικοί χωρών χωρισμένοι με κόμματα στους οποίους κατανέμονται τα MRN
+help-countries_file = Αρχείο με έναν κωδικό ή όνομα χώρας ανά γραμμή, προαιρετικά ακολουθούμενο από κόμμα και το πλήθος των MRN προς δημιουργία για αυτήν, π.χ. DK,100
+help-weights = Αρχείο TOML ή JSON με τα βάρη των χωρών, των διαδικασιών και των τελωνείων και το ποσοστό των MRN με λάθος ψηφίο ελέγχου
+help-known_countries = Αποδοχή μόνο των κωδικών χωρών γνωστών κρατών μελών και χωρών διαμετακόμισης
+help-country_weights = Βάρη των κωδικών χωρών, χωρισμένα με κόμματα, π.χ. 3,1
+help-number_of_mrns = Πλήθος MRN προς δημιουργία
+help-count_per_procedure = Δημιουργία αυτού του πλήθους MRN για κάθε διαδικασία από A έως W, σε ομάδες με την ένδειξη του χαρακτήρα διαδικασίας τους στην απλή έξοδο
+help-all_countries = Δημιουργία αυτού του πλήθους MRN, 1 αν δεν δοθεί, για κάθε χώρα του --country-code, ενός αρχείου χωρών ή βαρών ή μιας προρύθμισης, ή για κάθε γνωστή χώρα αν δεν δοθεί καμία
+help-procedure_category = Κατηγορία διαδικασίας
+help-combined = Συνδυασμένη κατηγορία διαδικασίας
+help-declaration_office = Τελωνείο διασάφησης
+help-office_with_country = Πρόθεμα του κωδικού χώρας στο τελωνείο διασάφησης, όπως στα αναγνωριστικά του καταλόγου τελωνείων (π.χ. DK004700)
+help-random_office = Χρήση ενός τυχαίου τελωνείου της χώρας από τον κατάλογο τελωνείων ως τελωνείο διασάφησης
+help-check_office = Έλεγχος ότι το τελωνείο διασάφησης υπάρχει στον κατάλογο τελωνείων
+help-preset = Εφαρμογή των συμβάσεων ενός συγκεκριμένου συστήματος
+help-profile = Κατανομή των MRN στις χώρες και διαδικασίες ενός ρεαλιστικού προφίλ κίνησης ή χρήση των ορισμάτων ενός προφίλ του αρχείου ρυθμίσεων
+help-config = Αρχείο ρυθμίσεων με τα προφίλ του --profile, από προεπιλογή το mrn-generator.toml του καταλόγου εργασίας
+help-rules = Αρχείο TOML ή JSON με κανόνες διαδικασιών που επεκτείνουν ή αντικαθιστούν τους ενσωματωμένους
+help-year_range = Κατανομή των MRN σε ένα εύρος ετών έκδοσης, π.χ. 20..=24 ή 2020..2025
+help-year_weights = Βάρη των ετών του εύρους, χωρισμένα με κόμματα, π.χ. 1,1,2,4,8
+help-spread_over = Κατανομή των MRN στις ημέρες ενός εύρους ημερομηνιών, συμπεριλαμβανομένων των άκρων, π.χ. 2023-01-01..2024-12-31
+help-issued_at = Προσθήκη της ημερομηνίας έκδοσης που επιλέχθηκε με --spread-over στην έξοδο, ως στήλη issued_at
+help-with_lrn = Σύζευξη κάθε MRN με ένα τυχαίο LRN, που γράφεται πριν από αυτό στην απλή έξοδο και ως στήλη lrn αλλιώς
+help-id_source = Στρατηγική συμπλήρωσης του ελεύθερου τμήματος των αναγνωριστικών
+help-seed = Σπόρος για αναπαραγώγιμη έξοδο με την τυχαία ή τη μετατεθειμένη πηγή
+help-sequence_start = Πρώτη τιμή μετρητή της ακολουθιακής ή της μετατεθειμένης πηγής
+help-namespace = Χώρος ονομάτων από 0 έως 15, που δεσμεύει τον πρώτο χαρακτήρα του ελεύθερου τμήματος ώστε εκτελέσεις σε διαφορετικούς χώρους ονομάτων να μην παράγουν ποτέ τα ίδια αναγνωριστικά
+help-seen_filter = Αρχείο φίλτρου Bloom με ήδη εκδοθέντα MRN, ώστε να μην επαναλαμβάνονται μεταξύ εκτελέσεων
+help-registry = Αρχείο μητρώου εκδοθέντων MRN, κλειδωμένο κατά την εκτέλεση ώστε να μπορεί να μοιράζεται μεταξύ ταυτόχρονων εκτελέσεων
+help-audit_log = Αρχείο JSON lines στο οποίο προστίθεται κάθε MRN που δημιουργείται, με την ώρα, τις παραμέτρους και το seed της δημιουργίας του
+help-webhook = URL στο οποίο αποστέλλεται με POST μια σύνοψη των MRN που δημιουργήθηκαν μόλις γραφτούν, με το πλήθος, ένα δείγμα και αθροίσματα ελέγχου
+help-output = Αρχείο στο οποίο γράφονται τα MRN αντί της τυπικής εξόδου
+help-format = Μορφή των MRN που δημιουργούνται
+help-delimiter = Διαχωριστικό των πεδίων της εξόδου tsv αντί για στηλοθέτη, ένας χαρακτήρας ή `tab`
+help-split_by = Εγγραφή των MRN σε ένα αρχείο ανά ομάδα στον κατάλογο εξόδου
+help-output_dir = Κατάλογος των αρχείων που γράφονται με --split-by
+help-manifest = Εγγραφή ενός manifest.json δίπλα στα αρχεία εξόδου, με το πλήθος γραμμών, τα αθροίσματα ελέγχου SHA-256 και τις παραμέτρους παραγωγής
+help-exec = Εντολή κελύφους που εκτελείται για κάθε MRN, όπου το `{"{}"}` αντικαθίσταται από το MRN, το `{"{country}"}` από τον κωδικό χώρας, το `{"{office}"}` από το τελωνείο διασάφησης και το `{"{lrn}"}` από το LRN του --with-lrn
+help-exec_jobs = Μέγιστο πλήθος εντολών --exec που εκτελούνται ταυτόχρονα
+help-queue_size = Πλήθος παραγόμενων MRN που περιμένουν να γραφτούν πριν η παραγωγή περιμένει την έξοδο
+help-parallel = Παραγωγή με όλους τους πυρήνες, σε τμήματα των 1024 MRN που το καθένα παίρνει σπόρο από το --seed
+help-jobs = Πλήθος νημάτων που χρησιμοποιούνται με το --parallel, όλοι οι πυρήνες από προεπιλογή
+help-ordered = Εγγραφή των MRN του --parallel με τη σειρά των τμημάτων τους, ώστε η έξοδος να είναι ίδια για τον ίδιο σπόρο ανεξάρτητα από το πλήθος νημάτων
+help-append = Προσθήκη στο αρχείο εξόδου αντί για αντικατάστασή του
+help-compress = Συμπίεση των αρχείων εξόδου κατά την εγγραφή
+help-skip_existing = Να μη δημιουργείται ποτέ MRN που υπάρχει ήδη στο αρχείο εξόδου
+help-fail_fast = Διακοπή στο πρώτο αναγνωριστικό που αποτυγχάνει, η προεπιλογή κατά την παραγωγή
+help-continue_on_error = Αναφορά των αναγνωριστικών που αποτυγχάνουν και συνέχιση με την υπόλοιπη παρτίδα, με αποτυχία στο τέλος, η προεπιλογή κατά την επικύρωση
+help-dry_run = Εμφάνιση του σχεδίου δημιουργίας, της εκτέλεσης όπως προκύπτει από το προφίλ του αρχείου ρυθμίσεων, το αρχείο βαρών, την προρύθμιση και τη γραμμή εντολών, χωρίς δημιουργία
+help-lang = Γλώσσα των μηνυμάτων, από προεπιλογή η γλώσσα του περιβάλλοντος
+
+
+## explain command
+
+explain-year = έτος { $year }, εκδόθηκε μεταξύ { $year }-01-01 και { $year }-12-31
+explain-invalid-year = έτος { $year }, όχι δύο ψηφία
+explain-retention-expired = , παλαιότερο από την περίοδο διατήρησης των { $retention } ετών
+explain-country-code = κωδικός χώρας { $country_code }
+explain-office = τελωνείο { $office }, { $name }
+explain-serial = αύξων αριθμός { $serial }
+explain-procedure = διαδικασία { $char }, { $description }
+explain-no-procedure = χωρίς διαδικασία, το { $char } δεν είναι χαρακτήρας διαδικασίας
+explain-check-digit-valid = ψηφίο ελέγχου { $digit }, έγκυρο
+explain-check-digit-invalid = ψηφίο ελέγχου { $digit }, μη έγκυρο, πρέπει να είναι { $expected }
+
+
+## bench command
+
+bench-throughput = { $count } MRN σε { $time }, { $rate } MRN/s, μέγιστος σωρός { $peak } bytes
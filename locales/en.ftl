@@ -0,0 +1,42 @@
+# English messages
+#
+# English is the built-in language, error messages, descriptions and help texts fall back to the
+# ones compiled into the crate, so only the command line labels are listed here.
+
+cli-error = Error
+cli-caused-by = Caused by
+cli-category = CATEGORY
+cli-combined = COMBINED
+cli-procedure = PROCEDURE
+cli-description = DESCRIPTION
+cli-finding-error = error
+cli-warning = warning
+cli-info = info
+cli-valid = valid
+cli-repair = did you mean { $mrn }, with { $replacement } instead of { $found } at position { $position }?
+cli-online-found = valid, found by the follow-up service
+cli-online-not-found = valid, not found by the follow-up service
+cli-offline-only = valid, not checked online
+cli-stage = STAGE
+cli-time = TIME
+cli-per-mrn = PER MRN
+cli-allocations = ALLOCATIONS
+
+
+## explain command
+
+explain-year = year { $year }, issued between { $year }-01-01 and { $year }-12-31
+explain-invalid-year = year { $year }, not two digits
+explain-retention-expired = , older than the retention period of { $retention } years
+explain-country-code = country code { $country_code }
+explain-office = customs office { $office }, { $name }
+explain-serial = serial number { $serial }
+explain-procedure = procedure { $char }, { $description }
+explain-no-procedure = no procedure, { $char } is not a procedure character
+explain-check-digit-valid = check digit { $digit }, valid
+explain-check-digit-invalid = check digit { $digit }, invalid, it should be { $expected }
+
+
+## bench command
+
+bench-throughput = { $count } MRNs in { $time }, { $rate } MRNs/s, peak heap { $peak } bytes
@@ -0,0 +1,222 @@
+# French messages
+
+cli-error = Erreur
+cli-caused-by = Cause
+cli-category = CATÉGORIE
+cli-combined = COMBINÉE
+cli-procedure = PROCÉDURE
+cli-description = DESCRIPTION
+cli-finding-error = erreur
+cli-warning = avertissement
+cli-info = info
+cli-valid = valide
+cli-repair = vouliez-vous dire { $mrn }, avec { $replacement } au lieu de { $found } à la position { $position } ?
+cli-online-found = valide, trouvé par le service de suivi
+cli-online-not-found = valide, introuvable dans le service de suivi
+cli-offline-only = valide, non vérifié en ligne
+cli-stage = ÉTAPE
+cli-time = DURÉE
+cli-per-mrn = PAR MRN
+cli-allocations = ALLOCATIONS
+
+## Errors
+
+error-country-code-length = { $value } n'est pas un code pays valide, il doit comporter exactement deux caractères (par ex. 'IT')
+error-invalid-procedure-category = { $value } n'est pas une catégorie de procédure valide
+error-invalid-procedure-category-suggestion = { $value } n'est pas une catégorie de procédure valide, vouliez-vous dire { $suggestion } ?
+error-invalid-procedure-category-combination = { $procedure_category }-{ $combination } n'est pas une combinaison de catégories de procédure valide
+error-invalid-combined-category = { $procedure_category } ne peut pas être combinée avec { $combined }
+error-invalid-combined-category-allowed = { $procedure_category } ne peut pas être combinée avec { $combined }, seulement avec { $allowed }
+error-combined-without-category = La catégorie de procédure combinée { $value } a été donnée sans catégorie de procédure
+error-not-alphanumeric = { $value } n'est pas un caractère alphanumérique
+error-invalid-character = { $value } à l'octet { $position } n'est ni un chiffre ni une lettre majuscule
+error-declaration-office-length = { $value } n'est pas un bureau de déclaration valide, ce doit être un numéro de bureau de six caractères, éventuellement précédé du code pays
+error-source-exhausted = La source de MRN n'a plus d'identifiants
+error-invalid-namespace = { $value } n'est pas un espace de noms valide, il doit être compris entre 0 et 15
+error-grn-length = { $value } n'est pas un GRN valide, il doit comporter de 17 à 24 caractères
+error-grn-prefix = { $value } n'est pas un GRN valide, il doit commencer par deux chiffres d'année et un code pays
+error-grn-check-character = { $value } n'est pas un GRN valide, son caractère de contrôle doit être un chiffre ou une lettre majuscule
+error-lrn-length = { $value } n'est pas une longueur de LRN valide, elle doit contenir le préfixe et ne pas dépasser 22 caractères
+error-invalid-lrn-prefix = { $value } n'est pas un préfixe de LRN valide, il ne doit contenir que des caractères ASCII imprimables
+error-invalid-eori = { $value } n'est pas un EORI valide, il doit se composer d'un code pays suivi d'au plus 15 caractères alphanumériques
+error-invalid-owner-code = { $value } n'est pas un code propriétaire de conteneur valide, il doit comporter exactement trois lettres
+error-container-number-format = { $value } n'est pas un numéro de conteneur valide, il doit se composer d'un code propriétaire, de U, J ou Z et de sept chiffres
+error-invalid-ducr = { $value } n'est pas un DUCR valide, il doit se composer d'un chiffre d'année, d'un code pays et de 6 à 32 caractères de référence
+error-invalid-mucr = { $value } n'est pas un MUCR valide
+error-mrn-length = { $value } n'est pas un MRN valide, il doit comporter exactement 18 caractères
+error-invalid-year = { $value } n'est pas un MRN valide, il doit commencer par les deux derniers chiffres de l'année
+error-invalid-country-code = { $value } n'est pas un MRN valide, son code pays doit être composé de deux lettres majuscules
+error-invalid-check-digit = { $mrn } n'est pas un MRN valide, son chiffre de contrôle doit être { $expected }
+error-not-an-ens-mrn = { $mrn } n'est pas un MRN de déclaration sommaire d'entrée, { $procedure_char } n'est pas une procédure de déclaration sommaire d'entrée
+error-not-an-ens-procedure = { $procedure } n'est pas une procédure de déclaration sommaire d'entrée
+error-not-a-transit-mrn = { $mrn } n'est pas un MRN de transit, { $procedure_char } n'est pas une procédure de déclaration de transit
+error-not-a-transit-procedure = { $procedure } n'est pas une procédure de déclaration de transit
+error-not-a-cds-mrn = { $mrn } n'est pas un MRN CDS, { $procedure_char } n'est pas une procédure de déclaration d'importation ou d'exportation
+error-not-a-cds-procedure = { $procedure } n'est pas une procédure de déclaration d'importation ou d'exportation de CDS
+error-not-a-ctc-country = { $value } n'est pas une partie contractante de la convention relative à un régime de transit commun
+error-unexpected-country = { $found } n'est pas le pays de ces MRN, ce doit être { $expected }
+error-issue-year = { $value } n'est pas une année d'émission valide, ce doit être les deux derniers chiffres d'une année
+error-invalid-rules = Règles de procédure invalides : { $value }
+error-invalid-check = Vérification invalide : { $value }
+error-check-failed = { $mrn } échoue à la vérification { $check }
+error-unsupported-language = { $value } n'est pas une langue prise en charge, elle doit être en, de, fr ou el
+error-online-verification = Échec de la vérification en ligne : { $value }
+error-unknown-country = { $value } n'est pas un pays connu
+error-unknown-country-suggestion = { $value } n'est pas un pays connu, vouliez-vous dire { $suggestion } ?
+error-unknown-procedure = { $value } n'est pas une procédure connue, elle doit être un caractère de procédure comme A ou un nom comme export_only
+
+## Validation warnings
+
+warning-future-year = l'année { $year } est dans le futur, l'année en cours est { $current_year }
+warning-office-country-mismatch = le segment du bureau de déclaration commence par { $office_prefix } au lieu du code pays { $country_code }
+warning-unknown-country = le code pays { $country_code } n'est pas un pays émetteur connu
+warning-unknown-country-suggestion = le code pays { $country_code } n'est pas un pays émetteur connu, vouliez-vous dire { $suggestion } ?
+warning-office-not-numeric = le numéro de bureau de déclaration { $office_number } contient des lettres, alors que les numéros de bureau de { $country_code } sont des chiffres
+warning-declaration-year-mismatch = l'année { $year } ne correspond pas à la date de déclaration { $declaration_date }
+
+## Procedures, by procedure character
+
+procedure-A = Exportation uniquement
+procedure-B = Exportation et déclaration sommaire de sortie
+procedure-C = Déclaration sommaire de sortie uniquement
+procedure-D = Notification de réexportation
+procedure-E = Expédition de marchandises dans le cadre des échanges avec des territoires fiscaux spéciaux
+procedure-J = Déclaration de transit uniquement
+procedure-K = Déclaration de transit et déclaration sommaire de sortie
+procedure-L = Déclaration de transit et déclaration sommaire d'entrée
+procedure-M = Preuve du statut douanier de marchandises de l'Union
+procedure-R = Déclaration d'importation uniquement
+procedure-S = Déclaration d'importation et déclaration sommaire d'entrée
+procedure-T = Déclaration sommaire d'entrée uniquement
+procedure-U = Déclaration de dépôt temporaire
+procedure-V = Introduction de marchandises dans le cadre des échanges avec des territoires fiscaux spéciaux
+procedure-W = Déclaration de dépôt temporaire et déclaration sommaire d'entrée
+
+## Declaration categories
+
+category-A1 = Déclaration sommaire de sortie
+category-A2 = Déclaration sommaire de sortie pour les envois postaux et express
+category-A3 = Notification de réexportation
+category-B1 = Déclaration d'exportation et de réexportation
+category-B2 = Déclaration d'exportation en perfectionnement passif
+category-B3 = Déclaration d'exportation pour l'entrepôt douanier de marchandises de l'Union
+category-B4 = Expédition de marchandises dans le cadre des échanges avec des territoires fiscaux spéciaux
+category-C1 = Déclaration d'exportation simplifiée
+category-D1 = Déclaration de transit
+category-D2 = Déclaration de transit avec un jeu de données réduit
+category-D3 = Déclaration de transit au moyen d'un document de transport électronique
+category-E1 = Preuve du statut douanier de marchandises de l'Union
+category-E2 = Manifeste douanier des marchandises
+category-F1a = Déclaration sommaire d'entrée, transport maritime et fluvial, jeu de données complet
+category-F1b = Déclaration sommaire d'entrée, transport maritime et fluvial, jeu de données partiel (connaissement maison)
+category-F1c = Déclaration sommaire d'entrée, transport maritime et fluvial, jeu de données partiel (connaissement principal)
+category-F1d = Déclaration sommaire d'entrée, transport maritime et fluvial, jeu de données partiel (connaissement de niveau inférieur)
+category-F2a = Déclaration sommaire d'entrée, fret aérien, jeu de données complet
+category-F2b = Déclaration sommaire d'entrée, fret aérien, jeu de données partiel (lettre de transport aérien principale)
+category-F2c = Déclaration sommaire d'entrée, fret aérien, jeu de données partiel (lettre de transport aérien maison)
+category-F2d = Déclaration sommaire d'entrée, fret aérien, jeu de données minimal avant chargement
+category-F3a = Déclaration sommaire d'entrée, envois express, jeu de données complet
+category-F3b = Déclaration sommaire d'entrée, envois express, jeu de données minimal avant chargement
+category-F4a = Déclaration sommaire d'entrée, envois postaux, jeu de données complet
+category-F4b = Déclaration sommaire d'entrée, envois postaux, jeu de données partiel (récipient principal)
+category-F4c = Déclaration sommaire d'entrée, envois postaux, jeu de données minimal avant chargement
+category-F5 = Déclaration sommaire d'entrée, route et rail
+category-G4 = Déclaration de dépôt temporaire
+category-H1 = Mise en libre pratique
+category-H2 = Entrepôt douanier
+category-H3 = Admission temporaire
+category-H4 = Perfectionnement actif
+category-H5 = Introduction de marchandises dans le cadre des échanges avec des territoires fiscaux spéciaux
+category-H6 = Mise en libre pratique dans le trafic postal
+category-I1 = Déclaration d'importation simplifiée
+
+## Command line help
+
+about = Utilitaire en ligne de commande pour générer des MRN valides
+about-grn = Générer ou valider des numéros de référence de garantie (GRN)
+about-lrn = Générer des numéros de référence locaux (LRN), éventuellement associés à des MRN
+about-dataset = Générer des déclarations synthétiques, chacune avec MRN, LRN, EORI, bureau, procédure et heures de dépôt et d'acceptation cohérents
+about-eori = Générer ou valider des numéros EORI
+about-container = Générer ou valider des numéros de conteneur ISO 6346
+about-ucr = Générer ou valider des Declaration et Master Unique Consignment References britanniques
+about-validate = Valider des MRN
+about-list-categories = Afficher les catégories de déclaration avec leurs combinaisons, procédures et descriptions
+about-explain = Afficher une décomposition annotée d'un MRN
+about-complete = Afficher les MRN valides correspondant à un MRN aux caractères inconnus, p. ex. 24DK0047??????????
+about-check-duplicates = Signaler les MRN qui apparaissent plusieurs fois dans un fichier ou entre plusieurs fichiers
+about-grep = Afficher les MRN de fichiers correspondant à des masques comme ??DK0047*, où ? représente un caractère quelconque et * un nombre quelconque de caractères
+about-fix = Corriger les chiffres de contrôle de fichiers de MRN, en affichant les modifications sous forme de diff
+about-fixtures = Afficher un ensemble stable de MRN d'exemple de toutes les procédures et de plusieurs pays, à versionner comme données de test de référence
+about-mcp = Servir la génération, la validation et l'analyse de MRN comme outils Model Context Protocol sur stdio
+about-schema = Afficher le schéma JSON de la sortie JSON, des erreurs ou du manifeste
+about-bench = Mesurer le débit de génération, avec la durée et les allocations de chaque étape
+about-serve = Servir la génération, la validation et des baux de MRN aux orchestrateurs de tests via HTTP
+help-country_code = Code ou nom de pays du MRN, ou codes pays séparés par des virgules sur lesquels répartir les MRN
+help-countries_file = Fichier avec un code ou nom de pays par ligne, suivi éventuellement d'une virgule et du nombre de MRN à générer pour ce pays, p. ex. DK,100
+help-weights = Fichier TOML ou JSON avec les poids des pays, des procédures et des bureaux et la part des MRN avec un chiffre de contrôle erroné
+help-known_countries = N'accepter que les codes pays des États membres et pays de transit connus
+help-country_weights = Poids des codes pays, séparés par des virgules, par ex. 3,1
+help-number_of_mrns = Nombre de MRN à générer
+help-count_per_procedure = Générer ce nombre de MRN pour chaque procédure de A à W, par groupes étiquetés de leur caractère de procédure dans la sortie simple
+help-all_countries = Générer ce nombre de MRN, 1 par défaut, pour chaque pays de --country-code, d'un fichier de pays ou de poids ou d'un préréglage, ou pour chaque pays connu à défaut
+help-procedure_category = Catégorie de procédure
+help-combined = Catégorie de procédure combinée
+help-declaration_office = Bureau de douane de déclaration
+help-office_with_country = Préfixer le bureau de déclaration avec le code pays, comme dans les identifiants de la liste des bureaux de douane (par ex. DK004700)
+help-random_office = Utiliser comme bureau de déclaration un bureau aléatoire du pays tiré de la liste des bureaux de douane
+help-check_office = Vérifier que le bureau de déclaration figure dans la liste des bureaux de douane
+help-preset = Appliquer les conventions d'un système particulier
+help-profile = Répartir les MRN sur les pays et procédures d'un profil de trafic réaliste, ou utiliser les arguments d'un profil du fichier de configuration
+help-config = Fichier de configuration contenant les profils de --profile, mrn-generator.toml du répertoire de travail par défaut
+help-rules = Fichier TOML ou JSON de règles de procédure qui complètent ou remplacent les règles intégrées
+help-year_range = Répartir les MRN sur une plage d'années d'émission, par ex. 20..=24 ou 2020..2025
+help-year_weights = Poids des années de la plage, séparés par des virgules, par ex. 1,1,2,4,8
+help-spread_over = Répartir les MRN sur les jours d'une plage de dates, bornes incluses, par ex. 2023-01-01..2024-12-31
+help-issued_at = Ajouter à la sortie la date d'émission choisie avec --spread-over, dans une colonne issued_at
+help-with_lrn = Associer à chaque MRN un LRN aléatoire, écrit avant lui en sortie texte et dans une colonne lrn sinon
+help-id_source = Stratégie de remplissage du segment libre des identifiants générés
+help-seed = Graine pour une sortie reproductible avec la source aléatoire ou permutée
+help-sequence_start = Première valeur du compteur de la source séquentielle ou permutée
+help-namespace = Espace de noms de 0 à 15, qui réserve le premier caractère du segment libre pour que des exécutions dans des espaces de noms différents ne génèrent jamais les mêmes identifiants
+help-seen_filter = Fichier de filtre de Bloom des MRN déjà émis, pour éviter de les répéter d'une exécution à l'autre
+help-registry = Fichier registre des MRN émis, verrouillé pendant l'exécution afin de pouvoir être partagé entre exécutions simultanées
+help-audit_log = Fichier JSON lines auquel chaque MRN générée est ajoutée, avec l'heure, les paramètres et la graine de sa génération
+help-webhook = URL à laquelle un résumé des MRN générées est envoyé une fois écrites, avec leur nombre, un échantillon et des sommes de contrôle
+help-output = Fichier dans lequel écrire les MRN générés au lieu de la sortie standard
+help-format = Format des MRN générés
+help-delimiter = Séparateur des champs de la sortie tsv au lieu d'une tabulation, un seul caractère ou `tab`
+help-split_by = Écrire les MRN générés dans un fichier par groupe du répertoire de sortie
+help-output_dir = Répertoire des fichiers écrits avec --split-by
+help-manifest = Écrire un manifest.json à côté des fichiers de sortie, avec leurs nombres de lignes, sommes de contrôle SHA-256 et les paramètres de génération
+help-exec = Commande shell exécutée pour chaque MRN généré, `{"{}"}` étant remplacé par le MRN, `{"{country}"}` par son code pays, `{"{office}"}` par son bureau de déclaration et `{"{lrn}"}` par son LRN de --with-lrn
+help-exec_jobs = Nombre maximal de commandes --exec exécutées en même temps
+help-queue_size = Nombre de MRN générés en attente d'écriture avant que la génération n'attende la sortie
+help-parallel = Générer avec tous les cœurs, par blocs de 1024 MRN chacun amorcé à partir de --seed
+help-jobs = Nombre de threads utilisés avec --parallel, tous les cœurs par défaut
+help-ordered = Écrire les MRN de --parallel dans l'ordre de leurs blocs, pour que la sortie soit la même pour une même graine quel que soit le nombre de threads
+help-append = Ajouter au fichier de sortie au lieu de l'écraser
+help-compress = Compresser les fichiers de sortie au fil de l'écriture
+help-skip_existing = Ne jamais générer un MRN déjà présent dans le fichier de sortie
+help-fail_fast = S'arrêter au premier identifiant en échec, par défaut lors de la génération
+help-continue_on_error = Signaler les identifiants en échec et poursuivre le reste du lot, en échouant à la fin, par défaut lors de la validation
+help-dry_run = Afficher le plan de génération, l'exécution telle que résolue à partir du profil du fichier de configuration, du fichier de poids, du préréglage et de la ligne de commande, sans générer
+help-lang = Langue des messages, par défaut celle de l'environnement
+
+
+## explain command
+
+explain-year = année { $year }, émis entre le { $year }-01-01 et le { $year }-12-31
+explain-invalid-year = année { $year }, pas deux chiffres
+explain-retention-expired = , plus ancien que la durée de conservation de { $retention } ans
+explain-country-code = code pays { $country_code }
+explain-office = bureau de douane { $office }, { $name }
+explain-serial = numéro de série { $serial }
+explain-procedure = procédure { $char }, { $description }
+explain-no-procedure = pas de procédure, { $char } n'est pas un caractère de procédure
+explain-check-digit-valid = chiffre de contrôle { $digit }, valide
+explain-check-digit-invalid = chiffre de contrôle { $digit }, invalide, il doit être { $expected }
+
+
+## bench command
+
+bench-throughput = { $count } MRN en { $time }, { $rate } MRN/s, tas maximal { $peak } octets
@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+"""Converts a CSV export of the EU Customs Office List into src/col/data.rs
+
+Usage: scripts/col_to_rust.py export.csv > src/col/data.rs
+
+The export is expected to have at least the ReferenceNumber, CountryCode,
+UsualName and Roles columns (other columns are ignored), with the role codes
+separated by semicolons, e.g. `DEP;DES;TRA`.
+"""
+
+import csv
+import sys
+
+ROLES = {
+    "DEP": "Departure",
+    "DES": "Destination",
+    "TRA": "Transit",
+    "EXP": "Export",
+    "EXT": "Exit",
+    "ENT": "Entry",
+}
+
+
+def main(path):
+    with open(path, newline="", encoding="utf-8") as f:
+        offices = {
+            row["ReferenceNumber"].strip(): (
+                row["CountryCode"].strip(),
+                row["UsualName"].strip(),
+                [ROLES[code.strip()] for code in row["Roles"].split(";") if code.strip()],
+            )
+            for row in csv.DictReader(f)
+        }
+
+    print("// Excerpt of the EU Customs Office List, regenerate with scripts/col_to_rust.py")
+    print()
+    print("use super::{OfficeInfo, OfficeRole::*};")
+    print()
+    print("/// Offices sorted by reference number")
+    print("pub(super) static OFFICES: &[OfficeInfo] = &[")
+    for reference, (country_code, name, roles) in sorted(offices.items()):
+        name = name.replace("\\", "\\\\").replace('"', '\\"')
+        print("    OfficeInfo {")
+        print(f'        reference: "{reference}",')
+        print(f'        country_code: "{country_code}",')
+        print(f'        name: "{name}",')
+        print(f"        roles: &[{', '.join(roles)}],")
+        print("    },")
+    print("];")
+
+
+if __name__ == "__main__":
+    main(sys.argv[1])
@@ -0,0 +1,124 @@
+//! Generated MRNs as Arrow record batches, for use in polars or datafusion pipelines
+
+use crate::source::IdSource;
+use crate::{generate_mrn, layout, procedure_description, MrnConfig, MrnGeneratorError};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use arrow_array::builder::{StringBuilder, UInt16Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+/// Schema of the record batches, one row per MRN and its components
+pub fn mrn_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("mrn", DataType::Utf8, false),
+        Field::new("year", DataType::UInt16, false),
+        Field::new("country_code", DataType::Utf8, false),
+        Field::new("declaration_office", DataType::Utf8, true),
+        Field::new("serial_number", DataType::Utf8, false),
+        Field::new("procedure_character", DataType::Utf8, true),
+        Field::new("procedure", DataType::Utf8, true),
+        Field::new("check_digit", DataType::Utf8, false),
+    ]))
+}
+
+/// Generates a record batch of MRNs with the given parameters, taking the free segments from the
+/// given source
+///
+/// ## Example
+/// ```
+/// use mrn_generator::arrow::generate_batch;
+/// use mrn_generator::source::RandomSource;
+/// use mrn_generator::{DeclarationOffice, MrnConfig};
+///
+/// let config = MrnConfig {
+///     country_code: "DK",
+///     declaration_office: Some(DeclarationOffice::new("004700").unwrap()),
+///     ..Default::default()
+/// };
+/// let batch = generate_batch(&config, RandomSource::seeded(7), 1000).unwrap();
+/// assert_eq!(1000, batch.num_rows());
+/// assert_eq!(8, batch.num_columns());
+/// ```
+pub fn generate_batch(
+    config: &MrnConfig,
+    mut source: impl IdSource,
+    rows: usize,
+) -> Result<RecordBatch, MrnGeneratorError> {
+    let office = config.declaration_office;
+    let office_end = layout::SERIAL.start + office.map_or(0, |office| office.as_str().len());
+    let serial_end = match config.procedure {
+        Some(_) => layout::PROCEDURE.start,
+        None => layout::CHECK_DIGIT.start,
+    };
+
+    let mut mrn = StringBuilder::with_capacity(rows, rows * layout::LENGTH);
+    let mut year = UInt16Builder::with_capacity(rows);
+    let mut country_code = StringBuilder::with_capacity(rows, rows * 2);
+    let mut declaration_office = StringBuilder::new();
+    let mut serial_number = StringBuilder::with_capacity(rows, rows * 13);
+    let mut procedure_character = StringBuilder::new();
+    let mut procedure = StringBuilder::new();
+    let mut check_digit = StringBuilder::with_capacity(rows, rows);
+
+    for _ in 0..rows {
+        let value = generate_mrn(config, &mut source)?;
+        mrn.append_value(&value);
+        year.append_value(2000 + value[layout::YEAR].parse::<u16>().unwrap());
+        country_code.append_value(&value[layout::COUNTRY_CODE]);
+        declaration_office.append_option(office.map(|office| office.as_str()));
+        serial_number.append_value(&value[office_end..serial_end]);
+        procedure_character.append_option(config.procedure.map(|_| &value[layout::PROCEDURE]));
+        procedure.append_option(config.procedure.map(procedure_description));
+        check_digit.append_value(&value[layout::CHECK_DIGIT]);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(mrn.finish()),
+        Arc::new(year.finish()),
+        Arc::new(country_code.finish()),
+        Arc::new(declaration_office.finish()),
+        Arc::new(serial_number.finish()),
+        Arc::new(procedure_character.finish()),
+        Arc::new(procedure.finish()),
+        Arc::new(check_digit.finish()),
+    ];
+    Ok(RecordBatch::try_new(mrn_schema(), columns).expect("columns match the schema"))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{is_mrn_valid, Procedure};
+    use arrow_array::cast::AsArray;
+    use arrow_array::Array;
+
+    #[test]
+    fn generate_batch_test() {
+        let config = MrnConfig {
+            country_code: "it",
+            procedure: Some(Procedure::ExportOnly),
+            year: Some(24),
+            ..Default::default()
+        };
+        let batch = generate_batch(&config, crate::source::RandomSource::seeded(1), 10).unwrap();
+
+        let mrns = batch.column(0).as_string::<i32>();
+        assert!(mrns
+            .iter()
+            .all(|mrn| is_mrn_valid(mrn.unwrap()) == Ok(None)));
+        assert_eq!(
+            2024,
+            batch
+                .column(1)
+                .as_primitive::<arrow_array::types::UInt16Type>()
+                .value(0)
+        );
+        assert_eq!("IT", batch.column(2).as_string::<i32>().value(0));
+        assert_eq!(10, batch.column(3).null_count());
+        assert_eq!(12, batch.column(4).as_string::<i32>().value(0).len());
+        assert_eq!("A", batch.column(5).as_string::<i32>().value(0));
+    }
+}
@@ -0,0 +1,95 @@
+//! Audit log of the issued MRNs, so that shared test infrastructure can trace where an MRN came
+//! from
+//!
+//! Each line is a JSON object with the `timestamp` the MRN was issued at, the `mrn`, the
+//! `parameters` and `seed` it was generated with and, in server mode, the address of the
+//! `requester`.
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{json, Value};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Append-only log of the issued MRNs
+pub struct AuditLog {
+    writer: BufWriter<File>,
+}
+
+impl AuditLog {
+    /// Opens the log for appending, creating it if needed
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().append(true).create(true).open(path)?;
+        Ok(AuditLog {
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Appends an issued MRN, it is persisted on `sync` or `commit`
+    pub fn record(
+        &mut self,
+        mrn: &str,
+        parameters: &Value,
+        seed: Option<u64>,
+        requester: Option<&str>,
+    ) -> io::Result<()> {
+        let entry = json!({
+            "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            "mrn": mrn,
+            "parameters": parameters,
+            "seed": seed,
+            "requester": requester,
+        });
+        writeln!(self.writer, "{entry}")
+    }
+
+    /// Flushes the MRNs appended so far to disk
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()
+    }
+
+    /// Flushes all appended MRNs to disk and closes the log
+    pub fn commit(self) -> io::Result<()> {
+        let file = self.writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn record_test() {
+        let path = std::env::temp_dir().join(format!("mrn-audit-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        // Entries of later runs are appended
+        for (mrn, requester) in [
+            ("22ITZXBZYUTJFLJXK6", None),
+            ("26DK004700CJNMYMA6", Some("127.0.0.1")),
+        ] {
+            let mut log = AuditLog::open(&path).unwrap();
+            log.record(mrn, &json!({"count": 1}), Some(7), requester)
+                .unwrap();
+            log.commit().unwrap();
+        }
+
+        let entries: Vec<Value> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(2, entries.len());
+        assert_eq!("22ITZXBZYUTJFLJXK6", entries[0]["mrn"]);
+        assert_eq!(Value::Null, entries[0]["requester"]);
+        assert_eq!("127.0.0.1", entries[1]["requester"]);
+        assert_eq!(json!({"count": 1}), entries[1]["parameters"]);
+        assert_eq!(7, entries[1]["seed"]);
+        assert!(entries[1]["timestamp"].as_str().unwrap().ends_with('Z'));
+        fs::remove_file(path).unwrap();
+    }
+}
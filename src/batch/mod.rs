@@ -0,0 +1,200 @@
+//! Bulk validation of MRNs, for embedders validating millions of MRNs per second, e.g. stream
+//! processors
+//!
+//! [validate_batch] gives the same verdicts as [validate_mrn](crate::validate_mrn), as
+//! [ValidationResult]s that don't allocate, with the check digit computed from a lookup table of
+//! the character values.
+//!
+//! ## Example
+//! ```
+//! use mrn_generator::batch::{validate_batch, ValidationResult};
+//!
+//! let results = validate_batch(&["22ITZXBZYUTJFLJXK6", "22ITZXBZYUTJFLJXK7", "22IT"]);
+//! assert_eq!(
+//!     vec![
+//!         ValidationResult::Valid,
+//!         ValidationResult::InvalidCheckDigit { expected: '6' },
+//!         ValidationResult::Length,
+//!     ],
+//!     results
+//! );
+//! assert_eq!(2, results.iter().filter(|result| !result.is_valid()).count());
+//! ```
+
+use crate::{layout, MrnGeneratorError};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// Verdict of the validation of an MRN, the error [validate_mrn](crate::validate_mrn) would give
+/// without the MRN itself
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ValidationResult {
+    Valid,
+    /// A character that isn't a letter or digit, or a lowercase letter after the country code, at
+    /// a byte position
+    InvalidCharacter {
+        position: usize,
+        found: char,
+    },
+    Length,
+    InvalidYear,
+    InvalidCountryCode,
+    InvalidCheckDigit {
+        expected: char,
+    },
+}
+
+impl ValidationResult {
+    pub fn is_valid(self) -> bool {
+        self == ValidationResult::Valid
+    }
+
+    /// The error of the MRN the result is of, as returned by
+    /// [validate_mrn](crate::validate_mrn)
+    pub fn into_result(self, mrn: &str) -> Result<(), MrnGeneratorError> {
+        use MrnGeneratorError::*;
+
+        Err(match self {
+            ValidationResult::Valid => return Ok(()),
+            ValidationResult::InvalidCharacter { position, found } => {
+                InvalidCharacter { position, found }
+            }
+            ValidationResult::Length => MrnLength(mrn.to_string()),
+            ValidationResult::InvalidYear => InvalidYear(mrn.to_string()),
+            ValidationResult::InvalidCountryCode => InvalidCountryCode(mrn.to_string()),
+            ValidationResult::InvalidCheckDigit { expected } => InvalidCheckDigit {
+                mrn: mrn.to_string(),
+                expected,
+            },
+        })
+    }
+}
+
+/// Marks the bytes that aren't digits or uppercase letters in [CHARACTER_VALUES]
+const NO_VALUE: u8 = u8::MAX;
+
+/// Check digit value of each byte, as [check_character_value](crate::check_character_value)
+/// gives them
+const CHARACTER_VALUES: [u8; 256] = {
+    let mut values = [NO_VALUE; 256];
+    let mut byte = 0;
+    while byte < 128 {
+        let b = byte as u8;
+        values[byte] = match b {
+            b'0'..=b'9' => b - b'0',
+            b'A' => 10,
+            b'B'..=b'K' => b - 54,
+            b'L'..=b'U' => b - 53,
+            b'V'..=b'Z' => b - 52,
+            _ => NO_VALUE,
+        };
+        byte += 1;
+    }
+    values
+};
+
+/// Validates an MRN without allocating
+pub fn validate(mrn: &str) -> ValidationResult {
+    let bytes = mrn.as_bytes();
+    if let Some(position) = bytes.iter().position(|b| !b.is_ascii_alphanumeric()) {
+        let found = mrn[position..]
+            .chars()
+            .next()
+            .expect("the position is a byte");
+        return ValidationResult::InvalidCharacter { position, found };
+    }
+    if bytes.len() != layout::LENGTH {
+        return ValidationResult::Length;
+    }
+    if !bytes[layout::YEAR].iter().all(u8::is_ascii_digit) {
+        return ValidationResult::InvalidYear;
+    }
+    if !bytes[layout::COUNTRY_CODE]
+        .iter()
+        .all(u8::is_ascii_uppercase)
+    {
+        return ValidationResult::InvalidCountryCode;
+    }
+    let serial_start = layout::COUNTRY_CODE.end;
+    if let Some(position) = bytes[serial_start..]
+        .iter()
+        .position(|&b| CHARACTER_VALUES[usize::from(b)] == NO_VALUE)
+    {
+        let position = serial_start + position;
+        return ValidationResult::InvalidCharacter {
+            position,
+            found: char::from(bytes[position]),
+        };
+    }
+
+    let sum: u32 = bytes[..layout::CHECK_DIGIT.start]
+        .iter()
+        .enumerate()
+        .map(|(position, &b)| u32::from(CHARACTER_VALUES[usize::from(b)]) << position)
+        .sum();
+    let expected = (sum % 11 % 10) as u8;
+    match bytes[layout::CHECK_DIGIT.start].wrapping_sub(b'0') == expected {
+        true => ValidationResult::Valid,
+        false => ValidationResult::InvalidCheckDigit {
+            expected: char::from(b'0' + expected),
+        },
+    }
+}
+
+/// Validates MRNs, the only allocation being that of the results
+pub fn validate_batch(mrns: &[&str]) -> Vec<ValidationResult> {
+    let mut results = Vec::with_capacity(mrns.len());
+    validate_batch_into(mrns, &mut results);
+    results
+}
+
+/// Validates MRNs, appending their results to a reused buffer
+pub fn validate_batch_into(mrns: &[&str], results: &mut Vec<ValidationResult>) {
+    results.extend(mrns.iter().map(|mrn| validate(mrn)));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::validate_mrn;
+
+    #[test]
+    fn validate_batch_test() {
+        let mrns = [
+            "22ITZXBZYUTJFLJXK6",
+            "22DK1V0QQK2S6J7TU2",
+            "24DKDK004700ABCDE0",
+            "22itzxbzyutjfljxk6",
+            "22ITzXBZYUTJFLJXK6",
+            "2XITZXBZYUTJFLJXK6",
+            "22I1ZXBZYUTJFLJXK6",
+            "22ÏTZXBZYUTJFLJXK6",
+            "22D_1V0QQK2S6J7TU2",
+            "22ITZXBZYUTJFLJXKA",
+            "22ITZXBZYUTJFLJXK",
+            "",
+        ];
+        let results = validate_batch(&mrns);
+        for (mrn, result) in mrns.iter().zip(&results) {
+            assert_eq!(validate_mrn(mrn), result.into_result(mrn), "{mrn}");
+        }
+        assert_eq!(ValidationResult::Valid, results[0]);
+
+        let mut buffer = Vec::new();
+        validate_batch_into(&mrns[..2], &mut buffer);
+        validate_batch_into(&mrns[2..], &mut buffer);
+        assert_eq!(results, buffer);
+    }
+
+    #[test]
+    fn character_values_test() {
+        for c in (0..=u8::MAX).map(char::from) {
+            let value = CHARACTER_VALUES[c as usize];
+            match c.is_ascii_digit() || c.is_ascii_uppercase() {
+                true => assert_eq!(crate::check_character_value(c), Ok(value)),
+                false => assert_eq!(NO_VALUE, value),
+            }
+        }
+    }
+}
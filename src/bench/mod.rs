@@ -0,0 +1,150 @@
+//! Micro-benchmark of MRN generation, to size the tool for a load test
+
+use crate::commands::id_source;
+use crate::parser::BenchArgs;
+use crate::ArgumentErrors;
+use anyhow::Result;
+use fluent_bundle::FluentArgs;
+use mrn_generator::i18n::Localizer;
+use mrn_generator::{generate_mrn, validate_mrn, MrnConfig};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of allocations made by the process so far
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Bytes currently allocated by the process
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Most bytes allocated at once since the last [reset_peak_bytes]
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting allocations and allocated bytes so that the benchmark can
+/// report them
+struct CountingAllocator;
+
+impl CountingAllocator {
+    fn grow(size: usize) {
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        Self::grow(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        Self::grow(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Starts measuring the peak of allocated bytes from the bytes allocated now
+pub fn reset_peak_bytes() {
+    PEAK_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+/// Most bytes allocated at once since the last [reset_peak_bytes]
+pub fn peak_bytes() -> usize {
+    PEAK_BYTES.load(Ordering::Relaxed)
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Time and allocations spent in one stage of generation
+#[derive(Default)]
+struct Stage {
+    time: Duration,
+    allocations: usize,
+}
+
+impl Stage {
+    /// Runs the stage once, adding its time and allocations
+    fn measure<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let result = f();
+        self.time += start.elapsed();
+        self.allocations += ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        result
+    }
+}
+
+/// Generates, validates and writes MRNs, then prints the throughput and the time and allocations
+/// of each stage
+pub fn bench(args: BenchArgs, localizer: &Localizer) -> Result<()> {
+    let config = MrnConfig::from_input(
+        &args.country_code,
+        args.procedure_category.as_deref(),
+        None,
+        args.declaration_office.as_deref(),
+    )
+    .map_err(ArgumentErrors)?;
+    let mut source = id_source(&args.source);
+    let mut out = BufWriter::new(io::sink());
+    let [mut generate, mut validate, mut write] = [(); 3].map(|_| Stage::default());
+
+    reset_peak_bytes();
+    let start = Instant::now();
+    for _ in 0..args.count {
+        let mrn = generate.measure(|| generate_mrn(&config, &mut *source))?;
+        validate.measure(|| validate_mrn(&mrn))?;
+        write.measure(|| writeln!(out, "{mrn}"))?;
+    }
+    write.measure(|| out.flush())?;
+    let elapsed = start.elapsed();
+
+    let [stage, time, per_mrn, allocations] =
+        ["cli-stage", "cli-time", "cli-per-mrn", "cli-allocations"]
+            .map(|id| localizer.message(id, None).unwrap_or_default());
+    // Columns are as wide as their translated headers, or their widest values
+    let (w0, w1, w2) = (
+        stage.chars().count().max(8),
+        time.chars().count().max(10),
+        per_mrn.chars().count().max(10),
+    );
+    let count = args.count.max(1) as u32;
+
+    println!("{stage:<w0$}  {time:>w1$}  {per_mrn:>w2$}  {allocations}");
+    for (name, stage) in [
+        ("generate", &generate),
+        ("validate", &validate),
+        ("write", &write),
+    ] {
+        println!(
+            "{name:<w0$}  {:>w1$}  {:>w2$}  {}",
+            format!("{:.3?}", stage.time),
+            format!("{:.1?}", stage.time / count),
+            stage.allocations
+        );
+    }
+    let mut throughput = FluentArgs::new();
+    throughput.set("count", args.count.to_string());
+    throughput.set("time", format!("{elapsed:.3?}"));
+    throughput.set(
+        "rate",
+        format!("{:.0}", args.count as f64 / elapsed.as_secs_f64()),
+    );
+    throughput.set("peak", peak_bytes().to_string());
+    println!(
+        "{}",
+        localizer
+            .message("bench-throughput", Some(&throughput))
+            .unwrap_or_default()
+    );
+    Ok(())
+}
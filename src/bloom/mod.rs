@@ -0,0 +1,158 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"MRNBLOOM";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + 8;
+
+/// Number of MRNs a newly created filter is sized for
+pub const DEFAULT_CAPACITY: u64 = 1_000_000;
+
+/// False positive rate of a newly created filter filled up to its capacity
+pub const DEFAULT_FALSE_POSITIVE_RATE: f64 = 0.0001;
+
+/// Bloom filter of previously issued MRNs, persisted between runs
+///
+/// Hashing is done with FNV-1a so the on-disk format does not depend on the
+/// standard library's hasher, which is not stable across Rust releases.
+#[derive(Debug, PartialEq)]
+pub struct SeenFilter {
+    hashes: u32,
+    bits: Vec<u8>,
+}
+
+impl SeenFilter {
+    /// Creates an empty filter sized for `capacity` items at the given false positive rate
+    pub fn new(capacity: u64, false_positive_rate: f64) -> Self {
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-(capacity.max(1) as f64) * false_positive_rate.ln() / (ln2 * ln2)).ceil();
+        let num_bits = (num_bits as u64).max(8);
+        let hashes = ((num_bits as f64 / capacity.max(1) as f64) * ln2).round() as u32;
+
+        SeenFilter {
+            hashes: hashes.max(1),
+            bits: vec![0; num_bits.div_ceil(8) as usize],
+        }
+    }
+
+    /// Loads the filter stored at `path`, or creates an empty one if the file doesn't exist
+    pub fn load_or_new(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Self::from_bytes(&bytes),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Ok(Self::new(DEFAULT_CAPACITY, DEFAULT_FALSE_POSITIVE_RATE))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the filter to `path`, replacing any previous contents
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    /// Returns true if the MRN has (probably) been seen before
+    pub fn contains(&self, mrn: &str) -> bool {
+        self.bit_indices(mrn)
+            .all(|i| self.bits[(i / 8) as usize] & (1 << (i % 8)) != 0)
+    }
+
+    /// Records the MRN as seen
+    pub fn insert(&mut self, mrn: &str) {
+        for i in self.bit_indices(mrn) {
+            self.bits[(i / 8) as usize] |= 1 << (i % 8);
+        }
+    }
+
+    fn num_bits(&self) -> u64 {
+        self.bits.len() as u64 * 8
+    }
+
+    /// Bit positions of an item using double hashing
+    fn bit_indices(&self, mrn: &str) -> impl Iterator<Item = u64> {
+        let h1 = fnv1a(mrn.as_bytes());
+        let h2 = splitmix(h1) | 1;
+        let num_bits = self.num_bits();
+        (0..self.hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.bits.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.hashes.to_le_bytes());
+        bytes.extend_from_slice(&self.num_bits().to_le_bytes());
+        bytes.extend_from_slice(&self.bits);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(ErrorKind::InvalidData, msg.to_string());
+
+        if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(invalid("not a seen filter file"));
+        }
+        if bytes[MAGIC.len()] != VERSION {
+            return Err(invalid("unsupported seen filter version"));
+        }
+
+        let hashes = u32::from_le_bytes(bytes[9..13].try_into().unwrap());
+        let num_bits = u64::from_le_bytes(bytes[13..21].try_into().unwrap());
+        let bits = bytes[HEADER_LEN..].to_vec();
+
+        if hashes == 0 || num_bits == 0 || num_bits != bits.len() as u64 * 8 {
+            return Err(invalid("corrupted seen filter file"));
+        }
+
+        Ok(SeenFilter { hashes, bits })
+    }
+}
+
+/// 64-bit FNV-1a hash
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// SplitMix64 finalizer, used to derive a second independent hash
+fn splitmix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn insert_and_contains_test() {
+        let mut filter = SeenFilter::new(1000, 0.001);
+        assert!(!filter.contains("22ITZXBZYUTJFLJXK6"));
+
+        filter.insert("22ITZXBZYUTJFLJXK6");
+        assert!(filter.contains("22ITZXBZYUTJFLJXK6"));
+        assert!(!filter.contains("22DK1V0QQK2S6J7TU1"));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let mut filter = SeenFilter::new(1000, 0.001);
+        filter.insert("22ITZXBZYUTJFLJXK6");
+
+        let loaded = SeenFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(filter, loaded);
+        assert!(loaded.contains("22ITZXBZYUTJFLJXK6"));
+    }
+
+    #[test]
+    fn invalid_file_test() {
+        assert_eq!(
+            ErrorKind::InvalidData,
+            SeenFilter::from_bytes(b"not a filter").unwrap_err().kind()
+        );
+    }
+}
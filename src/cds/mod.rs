@@ -0,0 +1,124 @@
+//! UK Customs Declaration Service (CDS) conventions
+//!
+//! CDS takes the import (H and I) and export (B and C) declarations of Great Britain, export
+//! declarations optionally combined with an exit summary declaration (A), and issues their MRNs
+//! with the GB country code.
+
+use crate::{char_to_procedure_category, layout, match_optional_procedure, validate_mrn};
+use crate::{MrnGeneratorError, Procedure};
+use alloc::string::ToString;
+
+/// Country code of the MRNs issued by CDS
+pub const CDS_COUNTRY: &str = "GB";
+
+/// Returns true if the procedure is one of a declaration lodged in CDS
+pub fn is_cds_procedure(procedure: Procedure) -> bool {
+    matches!(
+        procedure,
+        Procedure::ImportDeclarationOnly
+            | Procedure::ExportOnly
+            | Procedure::ExportAndExitSummaryDeclaration
+    )
+}
+
+/// Matches the procedure of a CDS declaration, defaulting to an import declaration only
+///
+/// ## Example
+/// ```
+/// use mrn_generator::cds::match_cds_procedure;
+/// use mrn_generator::Procedure;
+///
+/// assert_eq!(Ok(Procedure::ImportDeclarationOnly), match_cds_procedure(None, None));
+/// assert_eq!(
+///     Ok(Procedure::ExportAndExitSummaryDeclaration),
+///     match_cds_procedure(Some("B1"), Some("A"))
+/// );
+/// assert!(match_cds_procedure(Some("D1"), None).is_err());
+/// ```
+pub fn match_cds_procedure(
+    proctgr: Option<&str>,
+    combined: Option<&str>,
+) -> Result<Procedure, MrnGeneratorError> {
+    check_cds_procedure(match_optional_procedure(proctgr, combined)?)
+}
+
+/// Checks that an already matched procedure is one of a CDS declaration, defaulting to
+/// [Procedure::ImportDeclarationOnly] if there is none
+pub fn check_cds_procedure(procedure: Option<Procedure>) -> Result<Procedure, MrnGeneratorError> {
+    let procedure = procedure.unwrap_or(Procedure::ImportDeclarationOnly);
+    if is_cds_procedure(procedure) {
+        Ok(procedure)
+    } else {
+        Err(MrnGeneratorError::NotACdsProcedure(procedure))
+    }
+}
+
+/// Checks that the MRN is valid and plausible as a CDS MRN, i.e. its country code is GB and its
+/// procedure character is one of an import or export declaration
+pub fn validate_cds_mrn(mrn: &str) -> Result<(), MrnGeneratorError> {
+    validate_mrn(mrn)?;
+
+    let country_code = &mrn[layout::COUNTRY_CODE];
+    if country_code != CDS_COUNTRY {
+        return Err(MrnGeneratorError::UnexpectedCountry {
+            found: country_code.to_string(),
+            expected: CDS_COUNTRY.to_string(),
+        });
+    }
+    let procedure_char = mrn[layout::PROCEDURE].chars().next().unwrap();
+    match char_to_procedure_category(procedure_char) {
+        Some(procedure) if is_cds_procedure(procedure) => Ok(()),
+        _ => Err(MrnGeneratorError::NotACdsMrn {
+            mrn: mrn.to_string(),
+            procedure_char,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn match_cds_procedure_test() {
+        assert_eq!(
+            Ok(Procedure::ImportDeclarationOnly),
+            match_cds_procedure(Some("H1"), None)
+        );
+        assert_eq!(
+            Ok(Procedure::ExportOnly),
+            match_cds_procedure(Some("C1"), None)
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::NotACdsProcedure(
+                Procedure::ImportDeclarationAndEntrySummaryDeclaration
+            )),
+            match_cds_procedure(Some("H1"), Some("F"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn validate_cds_mrn_test() {
+        let mrn = crate::generate_random_mrn("GB", Some(Procedure::ExportOnly), None).unwrap();
+        assert_eq!(Ok(()), validate_cds_mrn(&mrn));
+
+        let mrn = crate::generate_random_mrn("GB", Some(Procedure::TransitDeclarationOnly), None)
+            .unwrap();
+        assert_eq!(
+            Err(MrnGeneratorError::NotACdsMrn {
+                mrn: mrn.clone(),
+                procedure_char: 'J'
+            }),
+            validate_cds_mrn(&mrn)
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::UnexpectedCountry {
+                found: "IT".to_string(),
+                expected: "GB".to_string()
+            }),
+            validate_cds_mrn("22ITZXBZYUTJFLJXK6")
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! Site-specific checks of valid MRNs, closures or rules of a small expression language, e.g.
+//! that the office is one of those of a site
+//!
+//! A rule compares a segment of the MRN with a list of values:
+//! ```text
+//! office in 004700, 002000
+//! country not in GB, XI
+//! procedure = J
+//! serial starts with DK
+//! ```
+//! The segments are `year`, `country`, `office` (the office number the serial starts with),
+//! `office-reference` (the office reference with its country code the serial starts with),
+//! `serial` and `procedure` (the procedure character). The operators are `in`, `not in`, `=`,
+//! `!=` and `starts with`, `starts with` taking a list of prefixes.
+//!
+//! With the `rules` feature, checks files list the rules by name, in TOML:
+//! ```toml
+//! [[check]]
+//! name = "office of the site"
+//! rule = "office in 004700, 002000"
+//! ```
+//! or the equivalent JSON, `{"check": [{"name": "office of the site", "rule": "..."}]}`.
+
+use crate::{layout, validate_mrn, MrnComponents, MrnGeneratorError};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Test of a check, true if the MRN passes it
+type Test = Box<dyn Fn(&MrnComponents) -> bool + Send + Sync>;
+
+struct Check {
+    name: String,
+    test: Test,
+}
+
+/// Checks run on valid MRNs, in the order they were added
+#[derive(Default)]
+pub struct Checks {
+    checks: Vec<Check>,
+}
+
+impl core::fmt::Debug for Checks {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.checks.iter().map(|check| &check.name))
+            .finish()
+    }
+}
+
+impl Checks {
+    pub fn new() -> Self {
+        Checks::default()
+    }
+
+    /// Adds a check, a closure returning true if the MRN passes it
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::checks::Checks;
+    ///
+    /// let mut checks = Checks::new();
+    /// checks.add("recent", |mrn| mrn.year >= "20");
+    /// assert_eq!(Ok(()), checks.check("22ITZXBZYUTJFLJXK6"));
+    /// assert!(checks.check("19DK0047002M8N41B0").is_err());
+    /// ```
+    pub fn add(
+        &mut self,
+        name: &str,
+        test: impl Fn(&MrnComponents) -> bool + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.checks.push(Check {
+            name: name.to_string(),
+            test: Box::new(test),
+        });
+        self
+    }
+
+    /// Adds a check given by a rule, see the module documentation
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::checks::Checks;
+    ///
+    /// let mut checks = Checks::new();
+    /// checks.add_rule("office of the site", "office in 004700, 002000").unwrap();
+    /// assert_eq!(Ok(()), checks.check("26DK004700ZF69P728"));
+    /// assert!(checks.check("22ITZXBZYUTJFLJXK6").is_err());
+    /// assert!(checks.add_rule("typo", "ofice in 004700").is_err());
+    /// ```
+    pub fn add_rule(&mut self, name: &str, rule: &str) -> Result<&mut Self, MrnGeneratorError> {
+        let test = parse_rule(rule)
+            .map_err(|reason| MrnGeneratorError::InvalidCheck(format!("{name}: {reason}")))?;
+        self.checks.push(Check {
+            name: name.to_string(),
+            test,
+        });
+        Ok(self)
+    }
+
+    /// Checks that an MRN is valid and passes every check, or returns the error of the first
+    /// check it fails
+    pub fn check(&self, mrn: &str) -> Result<(), MrnGeneratorError> {
+        validate_mrn(mrn)?;
+        let components = crate::parse_components(mrn)?;
+        match self.checks.iter().find(|check| !(check.test)(&components)) {
+            Some(check) => Err(MrnGeneratorError::CheckFailed {
+                mrn: mrn.to_string(),
+                check: check.name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+}
+
+#[cfg(feature = "rules")]
+mod file {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct ChecksFile {
+        #[serde(default)]
+        check: Vec<CheckEntry>,
+    }
+
+    #[derive(Deserialize)]
+    struct CheckEntry {
+        name: String,
+        rule: String,
+    }
+
+    impl Checks {
+        /// Parses the checks of a TOML document
+        pub fn from_toml(s: &str) -> Result<Self, MrnGeneratorError> {
+            let file: ChecksFile = toml::from_str(s)
+                .map_err(|e| MrnGeneratorError::InvalidCheck(e.message().to_string()))?;
+            Checks::from_file(file)
+        }
+
+        /// Parses the checks of a JSON document
+        pub fn from_json(s: &str) -> Result<Self, MrnGeneratorError> {
+            let file: ChecksFile = serde_json::from_str(s)
+                .map_err(|e| MrnGeneratorError::InvalidCheck(e.to_string()))?;
+            Checks::from_file(file)
+        }
+
+        fn from_file(file: ChecksFile) -> Result<Self, MrnGeneratorError> {
+            let mut checks = Checks::new();
+            for entry in file.check {
+                checks.add_rule(&entry.name, &entry.rule)?;
+            }
+            Ok(checks)
+        }
+    }
+}
+
+/// Segment of the MRN a rule compares
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Year,
+    Country,
+    Office,
+    OfficeReference,
+    Serial,
+    Procedure,
+}
+
+impl Segment {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "year" => Segment::Year,
+            "country" => Segment::Country,
+            "office" => Segment::Office,
+            "office-reference" => Segment::OfficeReference,
+            "serial" => Segment::Serial,
+            "procedure" => Segment::Procedure,
+            _ => return None,
+        })
+    }
+
+    /// Returns true if the segment of the MRN satisfies the predicate
+    fn satisfies(self, mrn: &MrnComponents, predicate: impl Fn(&str) -> bool) -> bool {
+        let mut procedure = [0; 4];
+        let value = match self {
+            Segment::Year => mrn.year,
+            Segment::Country => mrn.country_code,
+            Segment::Office => &mrn.serial[..layout::OFFICE_NUMBER.len()],
+            Segment::OfficeReference => &mrn.serial[..layout::OFFICE_REFERENCE.len()],
+            Segment::Serial => mrn.serial,
+            Segment::Procedure => mrn.procedure_char.encode_utf8(&mut procedure),
+        };
+        predicate(value)
+    }
+}
+
+/// Parses a rule into its test, or describes why it is invalid
+fn parse_rule(rule: &str) -> Result<Test, String> {
+    let rule = rule.trim();
+    let (name, rest) = rule
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| format!("{rule} is not a rule, expected e.g. office in 004700, 002000"))?;
+    let segment = Segment::parse(name).ok_or_else(|| {
+        format!(
+            "{name} is not a segment, expected year, country, office, office-reference, serial \
+             or procedure"
+        )
+    })?;
+    let rest = rest.trim_start();
+    let (operator, values) = ["not in ", "in ", "starts with ", "!= ", "= "]
+        .iter()
+        .find_map(|&operator| Some((operator.trim_end(), rest.strip_prefix(operator)?)))
+        .ok_or_else(|| {
+            format!(
+                "{rest} doesn't start with an operator, expected in, not in, =, != or starts with"
+            )
+        })?;
+    let values: Vec<String> = values
+        .split(',')
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+        .collect();
+    if values.is_empty() {
+        return Err(format!("{rule} has no values"));
+    }
+    if matches!(operator, "=" | "!=") && values.len() > 1 {
+        return Err(format!(
+            "{operator} takes a single value, use in or not in for a list"
+        ));
+    }
+
+    Ok(match operator {
+        "in" | "=" => {
+            Box::new(move |mrn| segment.satisfies(mrn, |value| values.iter().any(|v| v == value)))
+        }
+        "not in" | "!=" => {
+            Box::new(move |mrn| !segment.satisfies(mrn, |value| values.iter().any(|v| v == value)))
+        }
+        _ => Box::new(move |mrn| {
+            segment.satisfies(mrn, |value| {
+                values
+                    .iter()
+                    .any(|prefix| value.starts_with(prefix.as_str()))
+            })
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn add_rule_test() {
+        let mut checks = Checks::new();
+        checks
+            .add_rule("site", "office-reference starts with DK0047, DK0020")
+            .unwrap()
+            .add_rule("not transit", "procedure not in J, K, L")
+            .unwrap()
+            .add_rule("denmark", "country = DK")
+            .unwrap();
+        assert_eq!(Ok(()), checks.check("24DKDK004700ABCDE0"));
+        assert_eq!(
+            Err(MrnGeneratorError::CheckFailed {
+                mrn: "22ITZXBZYUTJFLJXK6".to_string(),
+                check: "site".to_string()
+            }),
+            checks.check("22ITZXBZYUTJFLJXK6")
+        );
+        assert!(matches!(
+            checks.check("24DKDK004700ABCDE1"),
+            Err(MrnGeneratorError::InvalidCheckDigit { .. })
+        ));
+
+        let mut checks = Checks::new();
+        checks.add_rule("recent", "year in 24, 25, 26").unwrap();
+        assert_eq!(Ok(()), checks.check("24DKDK004700ABCDE0"));
+        assert!(checks.check("22ITZXBZYUTJFLJXK6").is_err());
+
+        for rule in [
+            "office",
+            "office like 004700",
+            "office in",
+            "country = DK, SE",
+        ] {
+            assert!(
+                matches!(Checks::new().add_rule("bad", rule), Err(MrnGeneratorError::InvalidCheck(reason)) if reason.starts_with("bad: ")),
+                "{rule}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rules")]
+    fn checks_file_test() {
+        let checks = Checks::from_toml(
+            r#"
+            [[check]]
+            name = "office of the site"
+            rule = "office in 004700, 002000"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(Ok(()), checks.check("26DK004700ZF69P728"));
+        assert!(checks.check("22ITZXBZYUTJFLJXK6").is_err());
+
+        let checks =
+            Checks::from_json(r#"{"check": [{"name": "italy", "rule": "country != IT"}]}"#)
+                .unwrap();
+        assert!(checks.check("22ITZXBZYUTJFLJXK6").is_err());
+        assert!(matches!(
+            Checks::from_toml("[[check]]\nname = \"no rule\""),
+            Err(MrnGeneratorError::InvalidCheck(_))
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+// Excerpt of the EU Customs Office List, regenerate with scripts/col_to_rust.py
+
+use super::{OfficeInfo, OfficeRole::*};
+
+/// Offices sorted by reference number
+pub(super) static OFFICES: &[OfficeInfo] = &[
+    OfficeInfo {
+        reference: "AT100000",
+        country_code: "AT",
+        name: "Zollamt Wien",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "AT320000",
+        country_code: "AT",
+        name: "Zollamt Linz Wels",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "BE101000",
+        country_code: "BE",
+        name: "Antwerpen",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "BE212000",
+        country_code: "BE",
+        name: "Zaventem",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "BE301000",
+        country_code: "BE",
+        name: "Liège",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "DE002600",
+        country_code: "DE",
+        name: "Hamburg Hafen",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "DE003300",
+        country_code: "DE",
+        name: "Bremerhaven",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "DE004502",
+        country_code: "DE",
+        name: "Frankfurt am Main Flughafen",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "DE008900",
+        country_code: "DE",
+        name: "München Flughafen",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "DK002000",
+        country_code: "DK",
+        name: "Aarhus",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "DK003102",
+        country_code: "DK",
+        name: "Padborg",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "DK004700",
+        country_code: "DK",
+        name: "København",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "ES000801",
+        country_code: "ES",
+        name: "Barcelona",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "ES002801",
+        country_code: "ES",
+        name: "Madrid Aeropuerto",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "ES004601",
+        country_code: "ES",
+        name: "Valencia",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "FR000690",
+        country_code: "FR",
+        name: "Le Havre Port",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "FR001260",
+        country_code: "FR",
+        name: "Roissy Fret",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "FR003830",
+        country_code: "FR",
+        name: "Marseille Port",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "GR001000",
+        country_code: "GR",
+        name: "Peiraias",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "GR002600",
+        country_code: "GR",
+        name: "Thessaloniki",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "GR003400",
+        country_code: "GR",
+        name: "Athens International Airport",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "IE000010",
+        country_code: "IE",
+        name: "Dublin Port",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "IE000020",
+        country_code: "IE",
+        name: "Dublin Airport",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "IT261101",
+        country_code: "IT",
+        name: "Genova",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "IT314101",
+        country_code: "IT",
+        name: "Milano Malpensa",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "IT321101",
+        country_code: "IT",
+        name: "Trieste",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "NL000396",
+        country_code: "NL",
+        name: "Rotterdam Haven",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "NL000511",
+        country_code: "NL",
+        name: "Venlo",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "NL000854",
+        country_code: "NL",
+        name: "Schiphol Cargo",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "PL301000",
+        country_code: "PL",
+        name: "Poznań",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "PL322010",
+        country_code: "PL",
+        name: "Gdynia",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "PT000300",
+        country_code: "PT",
+        name: "Leixões",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "PT000510",
+        country_code: "PT",
+        name: "Lisboa",
+        roles: &[Departure, Destination, Transit, Export],
+    },
+    OfficeInfo {
+        reference: "SE000101",
+        country_code: "SE",
+        name: "Göteborg",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+    OfficeInfo {
+        reference: "SE000102",
+        country_code: "SE",
+        name: "Stockholm Arlanda",
+        roles: &[Departure, Destination, Transit, Export, Exit, Entry],
+    },
+];
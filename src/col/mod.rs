@@ -0,0 +1,157 @@
+//! Embedded excerpt of the EU Customs Office List (COL)
+
+mod data;
+
+/// Role of a customs office, as listed in the customs office list
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OfficeRole {
+    Departure,
+    Destination,
+    Transit,
+    Export,
+    Exit,
+    Entry,
+}
+
+impl OfficeRole {
+    /// Role code used in the customs office list, e.g. `DEP`
+    pub fn code(&self) -> &'static str {
+        match self {
+            OfficeRole::Departure => "DEP",
+            OfficeRole::Destination => "DES",
+            OfficeRole::Transit => "TRA",
+            OfficeRole::Export => "EXP",
+            OfficeRole::Exit => "EXT",
+            OfficeRole::Entry => "ENT",
+        }
+    }
+}
+
+/// A customs office from the customs office list
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OfficeInfo {
+    /// Reference number, the country code followed by the office number, e.g. `DK004700`
+    pub reference: &'static str,
+    pub country_code: &'static str,
+    pub name: &'static str,
+    pub roles: &'static [OfficeRole],
+}
+
+impl OfficeInfo {
+    /// Office number without the country code, as embedded in MRNs
+    pub fn office_number(&self) -> &'static str {
+        &self.reference[2..]
+    }
+
+    /// Returns true if the office has the given role
+    pub fn has_role(&self, role: OfficeRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+/// Returns the offices of a country, sorted by reference number
+///
+/// ## Example
+/// ```
+/// use mrn_generator::col::offices_for_country;
+///
+/// let offices = offices_for_country("dk");
+/// assert!(offices.iter().any(|office| office.name == "København"));
+/// assert!(offices_for_country("XX").is_empty());
+/// ```
+pub fn offices_for_country(country_code: &str) -> &'static [OfficeInfo] {
+    let country_code = country_code.to_ascii_uppercase();
+    // References start with the country code, so a country's offices are contiguous
+    let start = data::OFFICES.partition_point(|o| o.country_code < country_code.as_str());
+    let len = data::OFFICES[start..].partition_point(|o| o.country_code == country_code);
+    &data::OFFICES[start..start + len]
+}
+
+/// Looks up an office by its reference number, e.g. `DK004700`
+///
+/// ## Example
+/// ```
+/// use mrn_generator::col::{office_info, OfficeRole};
+///
+/// let office = office_info("DK004700").unwrap();
+/// assert_eq!("DK", office.country_code);
+/// assert!(office.has_role(OfficeRole::Exit));
+/// ```
+pub fn office_info(reference: &str) -> Option<&'static OfficeInfo> {
+    let reference = reference.to_ascii_uppercase();
+    data::OFFICES
+        .binary_search_by(|o| o.reference.cmp(reference.as_str()))
+        .ok()
+        .map(|i| &data::OFFICES[i])
+}
+
+/// Returns true if the office is in the customs office list, given either as a reference number
+/// (`DK004700`) or as an office number together with its country code
+///
+/// ## Example
+/// ```
+/// use mrn_generator::col::office_exists;
+///
+/// assert!(office_exists("DK", "DK004700"));
+/// assert!(office_exists("DK", "004700"));
+/// assert!(!office_exists("NL", "004700"));
+/// ```
+pub fn office_exists(country_code: &str, office: &str) -> bool {
+    office_info(&crate::office_reference(
+        country_code,
+        &office.to_ascii_uppercase(),
+    ))
+    .is_some()
+}
+
+/// Returns a random office in the given country, None if the list has no offices there
+#[cfg(feature = "generate")]
+pub fn random_office(country_code: &str) -> Option<&'static OfficeInfo> {
+    use rand::seq::SliceRandom;
+
+    offices_for_country(country_code).choose(&mut rand::thread_rng())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn offices_sorted_test() {
+        assert!(data::OFFICES
+            .windows(2)
+            .all(|w| w[0].reference < w[1].reference));
+        assert!(data::OFFICES
+            .iter()
+            .all(|o| o.reference.len() == 8 && o.reference.starts_with(o.country_code)));
+    }
+
+    #[test]
+    fn office_exists_test() {
+        assert!(office_exists("DK", "004700"));
+        assert!(office_exists("dk", "dk004700"));
+        assert!(!office_exists("DK", "999999"));
+    }
+
+    #[test]
+    fn offices_for_country_test() {
+        let offices = offices_for_country("NL");
+        assert_eq!(3, offices.len());
+        assert!(offices.iter().all(|o| o.country_code == "NL"));
+        assert_eq!(
+            Some("Venlo"),
+            office_info("nl000511").map(|office| office.name)
+        );
+        assert_eq!(None, office_info("NL999999"));
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn random_office_test() {
+        let office = random_office("nl").unwrap();
+        assert_eq!("NL", office.country_code);
+        assert_eq!(6, office.office_number().len());
+        assert_eq!(None, random_office("XX"));
+    }
+}
@@ -0,0 +1,890 @@
+use crate::input::{read_mrns, Mrns};
+use crate::parser::{
+    CheckDuplicatesArgs, ContainerArgs, ContainerCategory, EoriArgs, ExplainArgs, GrepArgs,
+    GrnArgs, IdSourceArgs, IdSourceKind, LrnArgs, Preset, RowFilter, UcrArgs, UcrKind,
+    ValidateArgs,
+};
+use crate::preset;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{Datelike, Utc};
+use fluent_bundle::FluentArgs;
+use mrn_generator::checks::Checks;
+use mrn_generator::col::office_info;
+use mrn_generator::container::*;
+use mrn_generator::eori::{generate_random_eori, validate_eori};
+use mrn_generator::grn::*;
+use mrn_generator::i18n::Localizer;
+use mrn_generator::lrn::generate_lrn_with_source;
+use mrn_generator::rules::ProcedureRules;
+use mrn_generator::source::{
+    IdSource, NamespacedSource, PermutedSource, RandomSource, SequentialSource, TimestampSource,
+};
+use mrn_generator::ucr::*;
+use mrn_generator::validation::{
+    validate_semantics, Finding, Repair, SemanticOptions, Severity, ValidationReport,
+};
+use mrn_generator::{
+    all_mappings, generate_random_mrn, is_mrn_valid, layout, match_optional_procedure, matches,
+    parse_components, procecure_category_to_char, validate_mrn, Combination, MrnGeneratorError,
+};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+/// Maximum number of times an identifier is regenerated when it was already issued
+pub const MAX_ATTEMPTS: usize = 100;
+
+/// Builds the source of free segments chosen on the command line
+pub fn id_source(args: &IdSourceArgs) -> Box<dyn IdSource> {
+    let source: Box<dyn IdSource> = match (args.id_source, args.seed) {
+        (IdSourceKind::Random, None) => Box::new(RandomSource::default()),
+        (IdSourceKind::Random, Some(seed)) => Box::new(RandomSource::seeded(seed)),
+        (IdSourceKind::Sequential, _) => {
+            Box::new(SequentialSource::starting_at(args.sequence_start))
+        }
+        (IdSourceKind::Timestamp, _) => Box::new(TimestampSource::default()),
+        (IdSourceKind::Permuted, seed) => Box::new(PermutedSource::starting_at(
+            seed.unwrap_or_else(rand::random),
+            args.sequence_start,
+        )),
+    };
+    match args.namespace {
+        Some(namespace) => Box::new(
+            NamespacedSource::new(namespace, source).expect("--namespace is checked by clap"),
+        ),
+        None => source,
+    }
+}
+
+/// Loads procedure mapping rules, as JSON if the file has a `.json` extension and TOML otherwise
+pub fn load_rules(path: &Path) -> Result<ProcedureRules> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("could not read rules file {}", path.display()))?;
+    let rules = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => ProcedureRules::from_json(&contents),
+        _ => ProcedureRules::from_toml(&contents),
+    };
+    rules.with_context(|| format!("could not load rules file {}", path.display()))
+}
+
+/// Loads site-specific checks, as JSON if the file has a `.json` extension and TOML otherwise
+pub fn load_checks(path: &Path) -> Result<Checks> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("could not read checks file {}", path.display()))?;
+    let checks = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => Checks::from_json(&contents),
+        _ => Checks::from_toml(&contents),
+    };
+    checks.with_context(|| format!("could not load checks file {}", path.display()))
+}
+
+/// Generates or validates GRNs
+pub fn grn(args: GrnArgs) -> Result<()> {
+    if let Some(values) = args.validate {
+        let grn = &values[0];
+        if let Some(check_character) = is_grn_valid(grn)? {
+            bail!("{grn} is not a valid GRN, its check character should be {check_character}");
+        }
+        if let Some(access_code) = values.get(1) {
+            if !is_access_code_valid(access_code) {
+                bail!("{access_code} is not a valid access code, it should be {ACCESS_CODE_LENGTH} uppercase alphanumerics");
+            }
+        }
+        println!("{grn} is valid");
+        return Ok(());
+    }
+
+    let country_code = args.country_code.as_deref().unwrap_or_default();
+    for _ in 0..args.number_of_grns {
+        let grn = generate_random_grn(country_code, args.voucher.as_deref())?;
+        if args.access_code {
+            println!("{grn} {}", generate_access_code());
+        } else {
+            println!("{grn}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates unique LRNs, optionally each paired with an MRN
+pub fn lrn(args: LrnArgs) -> Result<()> {
+    let procedure =
+        match_optional_procedure(args.procedure_category.as_deref(), args.combined.as_deref())?;
+
+    let mut source = id_source(&args.source);
+    let mut issued = HashSet::new();
+
+    for _ in 0..args.number_of_lrns {
+        let mut attempts = 0;
+        let lrn = loop {
+            let lrn = generate_lrn_with_source(&args.prefix, args.length, &mut *source)?;
+            if issued.insert(lrn.clone()) {
+                break lrn;
+            }
+            attempts += 1;
+            if attempts == MAX_ATTEMPTS {
+                bail!("could not generate a unique LRN after {MAX_ATTEMPTS} attempts");
+            }
+        };
+
+        if args.with_mrn {
+            let country_code = args.country_code.as_deref().unwrap_or_default();
+            let mrn =
+                generate_random_mrn(country_code, procedure, args.declaration_office.as_deref())?;
+            println!("{lrn} {mrn}");
+        } else {
+            println!("{lrn}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates or validates EORI numbers
+pub fn eori(args: EoriArgs) -> Result<()> {
+    if let Some(eori) = args.validate {
+        validate_eori(&eori)?;
+        println!("{eori} is valid");
+        return Ok(());
+    }
+
+    let country_code = args.country_code.as_deref().unwrap_or_default();
+    for _ in 0..args.number_of_eoris {
+        println!("{}", generate_random_eori(country_code)?);
+    }
+
+    Ok(())
+}
+
+/// Generates or validates container numbers
+pub fn container(args: ContainerArgs) -> Result<()> {
+    if let Some(number) = args.validate {
+        if let Some(check_digit) = is_container_number_valid(&number)? {
+            bail!(
+                "{number} is not a valid container number, its check digit should be {check_digit}"
+            );
+        }
+        println!("{number} is valid");
+        return Ok(());
+    }
+
+    let category = match args.category {
+        ContainerCategory::U => EquipmentCategory::FreightContainer,
+        ContainerCategory::J => EquipmentCategory::DetachableFreightContainerRelatedEquipment,
+        ContainerCategory::Z => EquipmentCategory::TrailerOrChassis,
+    };
+
+    for _ in 0..args.number_of_containers {
+        let number = generate_random_container_number(args.owner_code.as_deref(), category)?;
+        println!("{number}");
+    }
+
+    Ok(())
+}
+
+/// Generates or validates DUCRs and MUCRs
+pub fn ucr(args: UcrArgs) -> Result<()> {
+    if let Some(ucr) = args.validate {
+        match args.kind {
+            UcrKind::Ducr => validate_ducr(&ucr)?,
+            UcrKind::Mucr => validate_mucr(&ucr)?,
+        }
+        println!("{ucr} is valid");
+        return Ok(());
+    }
+
+    for _ in 0..args.number_of_ucrs {
+        let ucr = match args.kind {
+            UcrKind::Ducr => generate_random_ducr(args.eori.as_deref())?,
+            UcrKind::Mucr => generate_random_mucr(args.eori.as_deref())?,
+        };
+        println!("{ucr}");
+    }
+
+    Ok(())
+}
+
+/// Number of MRNs read at once when validating in parallel
+const PARALLEL_CHUNK_SIZE: usize = 1 << 16;
+
+/// Number of MRNs of a chunk validated by the same task
+const PARALLEL_BATCH_SIZE: usize = 1 << 10;
+
+/// Time between the progress reports of --progress
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Validates MRNs from the arguments or standard input, failing if any of them is invalid
+pub fn validate(args: ValidateArgs, localizer: &Localizer) -> Result<()> {
+    let checks = args
+        .checks
+        .as_deref()
+        .map(load_checks)
+        .transpose()?
+        .unwrap_or_default();
+    let validator = Validator::new(&args, checks, localizer);
+    let fail_fast = args.failure.fail_fast(false);
+    if args.csv {
+        return validate_csv(&args, &validator);
+    }
+
+    // Plain lines and CSV are read one line at a time, so that dumps of any size are validated
+    // in constant memory
+    let mut mrns: Mrns = if args.mrns.is_empty() {
+        read_mrns(io::stdin().lock(), args.input_format)?
+    } else {
+        Box::new(args.mrns.into_iter().map(Ok))
+    };
+
+    let mut tally = Tally::default();
+    let mut progress = args.progress.then(Progress::new);
+    if args.parallel {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(args.jobs.unwrap_or(0))
+            .build()?;
+        let mut out = io::BufWriter::new(io::stdout().lock());
+        loop {
+            let chunk = mrns
+                .by_ref()
+                .take(PARALLEL_CHUNK_SIZE)
+                .collect::<io::Result<Vec<String>>>()?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            // Each task validates a batch into its own buffer and tally, the batches are then
+            // written in input order
+            let batches: Vec<(String, Tally)> = pool.install(|| {
+                chunk
+                    .par_chunks(PARALLEL_BATCH_SIZE)
+                    .map(|batch| {
+                        let mut text = String::new();
+                        let mut batch_tally = Tally::default();
+                        for mrn in batch {
+                            batch_tally.record(validator.check(mrn, &mut text));
+                        }
+                        (text, batch_tally)
+                    })
+                    .collect()
+            });
+            // With --fail-fast the rest of the input is dropped after the first batch with an
+            // invalid MRN, its other MRNs were validated at the same time already
+            for (text, batch_tally) in batches {
+                out.write_all(text.as_bytes())?;
+                tally.merge(batch_tally);
+                if fail_fast && tally.invalid > 0 {
+                    break;
+                }
+            }
+            if fail_fast && tally.invalid > 0 {
+                break;
+            }
+            if let Some(progress) = &mut progress {
+                progress.report(&tally);
+            }
+        }
+        out.flush()?;
+    } else {
+        let mut text = String::new();
+        for mrn in mrns {
+            text.clear();
+            tally.record(validator.check(&mrn?, &mut text));
+            print!("{text}");
+            if fail_fast && tally.invalid > 0 {
+                break;
+            }
+            if let Some(progress) = &mut progress {
+                progress.report(&tally);
+            }
+        }
+    }
+    // The final report is the summary of the whole input
+    if let Some(progress) = &progress {
+        progress.print(&tally);
+    }
+
+    if tally.invalid > 0 {
+        bail!("{} of {} MRNs are invalid", tally.invalid, tally.total);
+    }
+
+    Ok(())
+}
+
+/// Validates a column of CSV input, writing the rows back with a `valid` column or only the rows
+/// kept by the filter
+fn validate_csv(args: &ValidateArgs, validator: &Validator) -> Result<()> {
+    let input: Box<dyn io::Read> = match &args.mrns[..] {
+        [] => Box::new(io::stdin().lock()),
+        [path] => Box::new(
+            fs::File::open(path).with_context(|| format!("could not open CSV file {path}"))?,
+        ),
+        _ => bail!("only one CSV file can be validated at a time"),
+    };
+    let mut reader = csv::Reader::from_reader(input);
+    let mut writer = csv::Writer::from_writer(io::stdout().lock());
+
+    let mut headers = reader.headers()?.clone();
+    let column = headers
+        .iter()
+        .position(|header| header == args.column)
+        .with_context(|| format!("the CSV input has no {} column", args.column))?;
+    if args.filter.is_none() {
+        headers.push_field("valid");
+    }
+    writer.write_record(&headers)?;
+
+    let fail_fast = args.failure.fail_fast(false);
+    for record in reader.records() {
+        let mut record = record?;
+        let mrn = record.get(column).unwrap_or_default().trim().to_string();
+        let valid = validator.is_valid(&mrn);
+        match args.filter {
+            None => {
+                record.push_field(if valid { "true" } else { "false" });
+                writer.write_record(&record)?;
+            }
+            Some(RowFilter::Valid) if valid => writer.write_record(&record)?,
+            Some(RowFilter::Invalid) if !valid => writer.write_record(&record)?,
+            Some(_) => {}
+        }
+        if fail_fast && !valid {
+            writer.flush()?;
+            bail!("{mrn} is invalid, stopping at the first invalid MRN");
+        }
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Reports the MRNs appearing more than once within or across files, with how often and where
+pub fn check_duplicates(args: CheckDuplicatesArgs) -> Result<()> {
+    // Places of each MRN as file index and line number, and the MRNs in order of appearance
+    let mut places: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    let mut order = Vec::new();
+    for (file, path) in args.files.iter().enumerate() {
+        let reader = BufReader::new(
+            fs::File::open(path).with_context(|| format!("could not open {}", path.display()))?,
+        );
+        for (line, mrn) in reader.lines().enumerate() {
+            let mrn = mrn.with_context(|| format!("could not read {}", path.display()))?;
+            let mrn = mrn.trim();
+            if mrn.is_empty() {
+                continue;
+            }
+            match places.get_mut(mrn) {
+                Some(places) => places.push((file, line + 1)),
+                None => {
+                    order.push(mrn.to_string());
+                    places.insert(mrn.to_string(), vec![(file, line + 1)]);
+                }
+            }
+        }
+    }
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let mut duplicates = 0;
+    for mrn in &order {
+        let places = &places[mrn];
+        if places.len() < 2 {
+            continue;
+        }
+        duplicates += 1;
+        let places: Vec<String> = places
+            .iter()
+            .map(|(file, line)| format!("{}:{line}", args.files[*file].display()))
+            .collect();
+        writeln!(out, "{mrn}: {} times, {}", places.len(), places.join(", "))?;
+    }
+    out.flush()?;
+
+    if duplicates > 0 {
+        bail!(
+            "{duplicates} of {} distinct MRNs appear more than once",
+            order.len()
+        );
+    }
+    Ok(())
+}
+
+/// Prints the MRNs of files, or of standard input, matching any of the masks
+pub fn grep(args: GrepArgs) -> Result<()> {
+    let inputs: Vec<(String, Box<dyn BufRead>)> = match args.files.is_empty() {
+        true => vec![("standard input".to_string(), Box::new(io::stdin().lock()))],
+        false => args
+            .files
+            .iter()
+            .map(|path| {
+                let file = fs::File::open(path)
+                    .with_context(|| format!("could not open {}", path.display()))?;
+                Ok((
+                    path.display().to_string(),
+                    Box::new(BufReader::new(file)) as Box<dyn BufRead>,
+                ))
+            })
+            .collect::<Result<_>>()?,
+    };
+    // Like grep, MRNs are prefixed with their file when there are several files
+    let prefix = args.files.len() > 1;
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let mut count = 0;
+    for (name, reader) in inputs {
+        for mrn in reader.lines() {
+            let mrn = mrn.with_context(|| format!("could not read {name}"))?;
+            let mrn = mrn.trim();
+            if mrn.is_empty()
+                || args.masks.iter().any(|mask| matches(mrn, mask)) == args.invert_match
+            {
+                continue;
+            }
+            count += 1;
+            match (args.count, prefix) {
+                (true, _) => {}
+                (false, true) => writeln!(out, "{name}:{mrn}")?,
+                (false, false) => writeln!(out, "{mrn}")?,
+            }
+        }
+    }
+    if args.count {
+        writeln!(out, "{count}")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Verifies MRNs offline, then online with the follow-up service if requested
+#[cfg(feature = "online")]
+pub fn verify(args: crate::parser::VerifyArgs, localizer: &Localizer) -> Result<()> {
+    use mrn_generator::online::{FollowUpClient, MrnStatus};
+
+    let client = args
+        .endpoint
+        .as_deref()
+        .filter(|_| args.online)
+        .map(FollowUpClient::new)
+        .transpose()?;
+    let label = |id: &str, default: &str| {
+        localizer
+            .message(id, None)
+            .unwrap_or_else(|| default.to_string())
+    };
+
+    let mut failed = 0;
+    for mrn in &args.mrns {
+        let mrn = mrn.trim();
+        let result = match &client {
+            Some(client) => client.verify(mrn).map(|status| match status {
+                MrnStatus::Found => {
+                    label("cli-online-found", "valid, found by the follow-up service")
+                }
+                MrnStatus::NotFound => label(
+                    "cli-online-not-found",
+                    "valid, not found by the follow-up service",
+                ),
+            }),
+            None => {
+                validate_mrn(mrn).map(|()| label("cli-offline-only", "valid, not checked online"))
+            }
+        };
+        match result {
+            Ok(text) => println!("{mrn}: {text}"),
+            Err(e) => {
+                println!("{mrn}: {}", localizer.error(&e));
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} MRNs could not be verified", args.mrns.len());
+    }
+    Ok(())
+}
+
+/// Checks MRNs with the options of the validate command
+struct Validator<'a> {
+    options: SemanticOptions,
+    preset: Option<Preset>,
+    /// Site-specific checks of the MRNs, run after the preset
+    checks: Checks,
+    reject_future: bool,
+    /// Lowest severity of the semantic findings that makes an MRN invalid
+    fail_on: Severity,
+    /// Only write the findings that make an MRN invalid
+    ignore_warnings: bool,
+    /// Only write the result lines of invalid MRNs
+    quiet: bool,
+    localizer: &'a Localizer,
+    error_label: String,
+    warning_label: String,
+    info_label: String,
+    valid_label: String,
+}
+
+impl<'a> Validator<'a> {
+    fn new(args: &ValidateArgs, checks: Checks, localizer: &'a Localizer) -> Self {
+        Validator {
+            options: SemanticOptions {
+                future_year_tolerance: args.future_tolerance,
+                check_office_country: args.check_office_country,
+                suggest_repair: args.suggest_repair,
+                century_pivot: args.century_pivot,
+                declaration_date: args.declaration_date,
+                ..SemanticOptions::now()
+            },
+            preset: args.preset,
+            checks,
+            reject_future: args.reject_future,
+            fail_on: match args.warnings_as_errors {
+                true => Severity::Warning,
+                false => Severity::Error,
+            },
+            ignore_warnings: args.ignore_warnings,
+            quiet: args.quiet,
+            localizer,
+            error_label: localizer
+                .message("cli-finding-error", None)
+                .unwrap_or_else(|| "error".to_string()),
+            warning_label: localizer
+                .message("cli-warning", None)
+                .unwrap_or_else(|| "warning".to_string()),
+            info_label: localizer
+                .message("cli-info", None)
+                .unwrap_or_else(|| "info".to_string()),
+            valid_label: localizer
+                .message("cli-valid", None)
+                .unwrap_or_else(|| "valid".to_string()),
+        }
+    }
+
+    /// Validates an MRN with the preset and checks
+    fn report(&self, mrn: &str) -> ValidationReport {
+        let mut report = validate_semantics(mrn, &self.options);
+        if let Some(preset) = self.preset {
+            if let Err(e) = preset::validate_mrn(preset, mrn) {
+                if report.errors.is_empty() {
+                    report.errors.push(e);
+                }
+            }
+        }
+        if report.errors.is_empty() {
+            if let Err(e) = self.checks.check(mrn) {
+                report.errors.push(e);
+            }
+        }
+        report
+    }
+
+    /// The semantic finding that makes an MRN invalid, if there is one
+    fn failure<'r>(&self, report: &'r ValidationReport) -> Option<&'r Finding> {
+        report.findings.iter().find(|finding| {
+            finding.severity() >= self.fail_on
+                || (self.reject_future && matches!(finding, Finding::FutureYear { .. }))
+        })
+    }
+
+    /// Returns true if the MRN is valid
+    fn is_valid(&self, mrn: &str) -> bool {
+        let report = self.report(mrn);
+        report.errors.is_empty() && self.failure(&report).is_none()
+    }
+
+    /// Describes the correction of an invalid MRN in the language of the localizer
+    fn repair_hint(&self, repair: &Repair) -> String {
+        let mut args = FluentArgs::new();
+        args.set("mrn", repair.mrn.clone());
+        args.set("position", repair.position + 1);
+        args.set("found", repair.found.to_string());
+        args.set("replacement", repair.replacement.to_string());
+        self.localizer
+            .message("cli-repair", Some(&args))
+            .unwrap_or_else(|| {
+                format!(
+                    "did you mean {}, with {} instead of {} at position {}?",
+                    repair.mrn,
+                    repair.replacement,
+                    repair.found,
+                    repair.position + 1
+                )
+            })
+    }
+
+    /// Validates an MRN, writing its result lines to `out`, returns None for blank lines and
+    /// whether the MRN is valid otherwise
+    fn check(&self, mrn: &str, out: &mut String) -> Option<bool> {
+        let mrn = mrn.trim();
+        if mrn.is_empty() {
+            return None;
+        }
+
+        let report = self.report(mrn);
+        let failure = self.failure(&report);
+        if self.quiet && report.errors.is_empty() && failure.is_none() {
+            return Some(true);
+        }
+
+        let findings = report
+            .findings
+            .iter()
+            .filter(|&finding| !failure.is_some_and(|failure| ptr::eq(finding, failure)));
+        for finding in findings.filter(|_| !self.ignore_warnings) {
+            let label = match finding.severity() {
+                Severity::Info => &self.info_label,
+                Severity::Warning => &self.warning_label,
+                Severity::Error => &self.error_label,
+            };
+            let finding = self.localizer.finding(finding);
+            writeln!(out, "{mrn}: {label}: {finding}").unwrap();
+        }
+        if let Some(e) = report.errors.first() {
+            writeln!(out, "{mrn}: {}", self.localizer.error(e)).unwrap();
+            if let Some(repair) = &report.repair {
+                writeln!(out, "{mrn}: {}", self.repair_hint(repair)).unwrap();
+            }
+            Some(false)
+        } else if let Some(finding) = failure {
+            writeln!(out, "{mrn}: invalid, {}", self.localizer.finding(finding)).unwrap();
+            Some(false)
+        } else {
+            writeln!(out, "{mrn}: {}", self.valid_label).unwrap();
+            Some(true)
+        }
+    }
+}
+
+/// Counts of validated MRNs
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    total: usize,
+    invalid: usize,
+}
+
+impl Tally {
+    fn record(&mut self, valid: Option<bool>) {
+        if let Some(valid) = valid {
+            self.total += 1;
+            self.invalid += usize::from(!valid);
+        }
+    }
+
+    fn merge(&mut self, other: Tally) {
+        self.total += other.total;
+        self.invalid += other.invalid;
+    }
+}
+
+/// Periodic report of the MRNs validated so far on standard error, keeping standard output for
+/// the results
+struct Progress {
+    start: Instant,
+    last: Instant,
+}
+
+impl Progress {
+    fn new() -> Self {
+        let now = Instant::now();
+        Progress {
+            start: now,
+            last: now,
+        }
+    }
+
+    /// Reports the tally if the last report is older than the interval
+    fn report(&mut self, tally: &Tally) {
+        if self.last.elapsed() >= PROGRESS_INTERVAL {
+            self.last = Instant::now();
+            self.print(tally);
+        }
+    }
+
+    fn print(&self, tally: &Tally) {
+        let seconds = self.start.elapsed().as_secs_f64();
+        eprintln!(
+            "{} MRNs validated, {} invalid, in {seconds:.0}s ({:.0} MRNs/s)",
+            tally.total,
+            tally.invalid,
+            tally.total as f64 / seconds.max(f64::EPSILON)
+        );
+    }
+}
+
+/// Prints the procedure table with the descriptions of the declaration categories
+pub fn list_categories(localizer: &Localizer) -> Result<()> {
+    let [category, combined, procedure, description] = [
+        "cli-category",
+        "cli-combined",
+        "cli-procedure",
+        "cli-description",
+    ]
+    .map(|id| localizer.message(id, None).unwrap_or_default());
+    // Columns are as wide as their translated headers
+    let (w0, w1, w2) = (
+        category.chars().count(),
+        combined.chars().count(),
+        procedure.chars().count(),
+    );
+
+    println!("{category:<w0$}  {combined:<w1$}  {procedure:<w2$}  {description}");
+    for mapping in all_mappings() {
+        let combined = match mapping.combination {
+            Combination::Standalone => "-",
+            Combination::With(c) => c,
+            Combination::Any => "*",
+        };
+        println!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            mapping.category,
+            combined,
+            procecure_category_to_char(mapping.procedure),
+            localizer
+                .category_description(mapping.category)
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Prints the MRN with each of its segments underlined and labeled
+pub fn explain(args: ExplainArgs, localizer: &Localizer) -> Result<()> {
+    let mrn = args.mrn.trim();
+    let components = parse_components(mrn).map_err(|e| anyhow!(localizer.error(&e)))?;
+    let label = |id: &str, args: &[(&'static str, String)]| {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        localizer
+            .message(id, Some(&fluent_args))
+            .unwrap_or_default()
+    };
+
+    let current_year = Utc::now().year();
+    let issue_year = match args.century_pivot {
+        Some(pivot) => components.issue_year_from(pivot.into()),
+        None => components.issue_year(current_year),
+    };
+    let year = match issue_year {
+        Some(year) => {
+            let mut text = label("explain-year", &[("year", year.to_string())]);
+            // Only flagged once the retention period is over for the whole issue year
+            if let Some(retention) = args.retention_years {
+                if year + i32::from(retention) < current_year {
+                    text += &label(
+                        "explain-retention-expired",
+                        &[("retention", retention.to_string())],
+                    );
+                }
+            }
+            text
+        }
+        None => label(
+            "explain-invalid-year",
+            &[("year", components.year.to_string())],
+        ),
+    };
+    let mut segments = vec![
+        (layout::YEAR.start, layout::YEAR.len(), year),
+        (
+            layout::COUNTRY_CODE.start,
+            layout::COUNTRY_CODE.len(),
+            label(
+                "explain-country-code",
+                &[("country_code", components.country_code.to_string())],
+            ),
+        ),
+    ];
+
+    // The serial usually starts with the declaration office, with or without the country code
+    let serial = components.serial;
+    let office = [
+        serial.get(..layout::OFFICE_REFERENCE.len()),
+        serial.get(..layout::OFFICE_NUMBER.len()),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|office| {
+        let reference = if office.len() == layout::OFFICE_REFERENCE.len() {
+            office.to_string()
+        } else {
+            format!("{}{office}", components.country_code)
+        };
+        office_info(&reference)
+            .filter(|info| info.country_code == components.country_code)
+            .map(|info| (office, info))
+    });
+    let serial_start = match office {
+        Some((office, info)) => {
+            segments.push((
+                layout::SERIAL.start,
+                office.len(),
+                label(
+                    "explain-office",
+                    &[
+                        ("office", office.to_string()),
+                        ("name", info.name.to_string()),
+                    ],
+                ),
+            ));
+            layout::SERIAL.start + office.len()
+        }
+        None => layout::SERIAL.start,
+    };
+    segments.push((
+        serial_start,
+        layout::SERIAL.end - serial_start,
+        label(
+            "explain-serial",
+            &[("serial", mrn[serial_start..layout::SERIAL.end].to_string())],
+        ),
+    ));
+
+    let procedure_char = components.procedure_char.to_string();
+    segments.push((
+        layout::PROCEDURE.start,
+        layout::PROCEDURE.len(),
+        match components.procedure {
+            Some(procedure) => label(
+                "explain-procedure",
+                &[
+                    ("char", procedure_char),
+                    ("description", localizer.procedure_description(procedure)),
+                ],
+            ),
+            None => label("explain-no-procedure", &[("char", procedure_char)]),
+        },
+    ));
+
+    let digit = components.check_digit.to_string();
+    segments.push((
+        layout::CHECK_DIGIT.start,
+        layout::CHECK_DIGIT.len(),
+        match is_mrn_valid(mrn)? {
+            None => label("explain-check-digit-valid", &[("digit", digit)]),
+            Some(expected) => label(
+                "explain-check-digit-invalid",
+                &[("digit", digit), ("expected", expected.to_string())],
+            ),
+        },
+    ));
+
+    println!("{mrn}");
+    for (start, len, text) in segments {
+        println!(
+            "{}{}{}  {text}",
+            " ".repeat(start),
+            "^".repeat(len),
+            " ".repeat(mrn.len() - start - len)
+        );
+    }
+
+    match validate_mrn(mrn) {
+        Ok(()) | Err(MrnGeneratorError::InvalidCheckDigit { .. }) => Ok(()),
+        Err(e) => bail!(localizer.error(&e)),
+    }
+}
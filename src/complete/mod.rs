@@ -0,0 +1,276 @@
+//! Completion of MRNs with unknown characters, e.g. MRNs read from partially redacted documents
+
+use crate::parser::CompleteArgs;
+use anyhow::{bail, Result};
+use mrn_generator::source::ALPHABET;
+use mrn_generator::{check_character_value, is_mrn_valid, layout};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashSet;
+use std::io::{self, BufWriter, Write};
+
+/// Character standing for an unknown character of a pattern
+const WILDCARD: char = '?';
+
+/// An MRN with unknown characters
+struct Pattern {
+    /// Characters each position before the check digit may have, a single one if it is known
+    positions: Vec<Vec<char>>,
+    /// Check digit, None if it is unknown
+    check_digit: Option<char>,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Self> {
+        let pattern = pattern.to_ascii_uppercase();
+        if pattern.chars().count() != layout::LENGTH {
+            bail!(
+                "{pattern} is {} characters long, an MRN pattern should be {} with {WILDCARD} for \
+                 each unknown character",
+                pattern.chars().count(),
+                layout::LENGTH
+            );
+        }
+
+        let mut positions = Vec::with_capacity(layout::LENGTH - 1);
+        let mut check_digit = None;
+        for (position, c) in pattern.chars().enumerate() {
+            let (kind, allowed): (&str, Vec<char>) = if layout::YEAR.contains(&position) {
+                ("a year digit", ('0'..='9').collect())
+            } else if layout::COUNTRY_CODE.contains(&position) {
+                ("a country code letter", ('A'..='Z').collect())
+            } else if layout::CHECK_DIGIT.contains(&position) {
+                ("a check digit", ('0'..='9').collect())
+            } else {
+                (
+                    "a letter or digit",
+                    ALPHABET.iter().map(|&c| c as char).collect(),
+                )
+            };
+            let allowed = match c {
+                WILDCARD => allowed,
+                c if allowed.contains(&c) => vec![c],
+                c => bail!(
+                    "{c} at position {} of {pattern} should be {kind}",
+                    position + 1
+                ),
+            };
+            match layout::CHECK_DIGIT.contains(&position) {
+                true => check_digit = (c != WILDCARD).then_some(c),
+                false => positions.push(allowed),
+            }
+        }
+        Ok(Pattern {
+            positions,
+            check_digit,
+        })
+    }
+
+    /// Number of valid MRNs matching the pattern
+    fn count(&self) -> u128 {
+        // MRNs by the remainder of the weighted sum of their characters so far, modulo 11
+        let mut remainders = [0u128; 11];
+        remainders[0] = 1;
+        for (position, allowed) in self.positions.iter().enumerate() {
+            let mut next = [0u128; 11];
+            for &c in allowed {
+                let value = (check_character_value(c).expect("allowed characters have values")
+                    as usize)
+                    << position;
+                for (remainder, &count) in remainders.iter().enumerate() {
+                    next[(remainder + value) % 11] += count;
+                }
+            }
+            remainders = next;
+        }
+        remainders
+            .iter()
+            .enumerate()
+            .filter(|(remainder, _)| match self.check_digit {
+                Some(digit) => char::from_digit(*remainder as u32 % 10, 10) == Some(digit),
+                None => true,
+            })
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Completes the characters before the check digit, returning the MRN if its check digit
+    /// matches the pattern
+    fn complete(&self, chars: impl Iterator<Item = char>) -> Option<String> {
+        let mut mrn: String = chars.collect();
+        mrn.push('0');
+        let check_digit = is_mrn_valid(&mrn)
+            .expect("allowed characters have values")
+            .unwrap_or('0');
+        mrn.pop();
+        mrn.push(check_digit);
+        match self.check_digit {
+            Some(digit) if digit != check_digit => None,
+            _ => Some(mrn),
+        }
+    }
+
+    /// Valid MRNs matching the pattern, in order
+    fn completions(&self) -> impl Iterator<Item = String> + '_ {
+        // Index of the character of each position, advanced like an odometer
+        let mut indexes = Some(vec![0; self.positions.len()]);
+        std::iter::from_fn(move || {
+            let current = indexes.clone()?;
+            indexes = self.advance(current.clone());
+            Some(current)
+        })
+        .filter_map(|indexes| {
+            self.complete(
+                indexes
+                    .iter()
+                    .zip(&self.positions)
+                    .map(|(&index, allowed)| allowed[index]),
+            )
+        })
+    }
+
+    /// Indexes following these, None after the last ones
+    fn advance(&self, mut indexes: Vec<usize>) -> Option<Vec<usize>> {
+        for (index, allowed) in indexes.iter_mut().zip(&self.positions).rev() {
+            *index += 1;
+            if *index < allowed.len() {
+                return Some(indexes);
+            }
+            *index = 0;
+        }
+        None
+    }
+
+    /// A random valid MRN matching the pattern, which some MRN should match
+    fn sample(&self, rng: &mut StdRng) -> String {
+        loop {
+            let chars = self
+                .positions
+                .iter()
+                .map(|allowed| *allowed.choose(rng).expect("positions allow a character"));
+            if let Some(mrn) = self.complete(chars) {
+                return mrn;
+            }
+        }
+    }
+}
+
+/// Prints valid MRNs matching a pattern to standard output
+pub fn complete(args: CompleteArgs) -> Result<()> {
+    write_completions(&args, io::stdout().lock())
+}
+
+fn write_completions(args: &CompleteArgs, out: impl Write) -> Result<()> {
+    let pattern = Pattern::parse(&args.pattern)?;
+    let count = pattern.count();
+    let mut out = BufWriter::new(out);
+    if args.count {
+        writeln!(out, "{count}")?;
+        return Ok(out.flush()?);
+    }
+    if count == 0 {
+        bail!("no valid MRN matches {}", args.pattern);
+    }
+
+    let number_of_mrns = (args.number_of_mrns as u128).min(count) as usize;
+    if count > number_of_mrns as u128 {
+        eprintln!(
+            "printing {number_of_mrns} of the {count} MRNs matching {}",
+            args.pattern
+        );
+    }
+    // Sampling draws MRNs until enough distinct ones came up, which slows down as they run out,
+    // so the matches are shuffled instead when most of them are printed
+    let mrns: Vec<String> = match args.sample {
+        true if count > 2 * number_of_mrns as u128 => {
+            let mut rng = StdRng::seed_from_u64(args.seed.unwrap_or_else(rand::random));
+            let mut mrns = HashSet::with_capacity(number_of_mrns);
+            let mut sampled = Vec::with_capacity(number_of_mrns);
+            while sampled.len() < number_of_mrns {
+                let mrn = pattern.sample(&mut rng);
+                if mrns.insert(mrn.clone()) {
+                    sampled.push(mrn);
+                }
+            }
+            sampled
+        }
+        true => {
+            let mut rng = StdRng::seed_from_u64(args.seed.unwrap_or_else(rand::random));
+            let mut mrns: Vec<String> = pattern.completions().collect();
+            mrns.shuffle(&mut rng);
+            mrns.truncate(number_of_mrns);
+            mrns
+        }
+        false => pattern.completions().take(number_of_mrns).collect(),
+    };
+    for mrn in mrns {
+        writeln!(out, "{mrn}")?;
+    }
+    Ok(out.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parser::{Args, Command};
+    use clap::Parser;
+    use mrn_generator::validate_mrn;
+
+    fn completions(argv: &[&str]) -> Result<Vec<String>> {
+        let argv = ["mrn-generator", "complete"].iter().chain(argv);
+        let args = match Args::parse_from(argv).command {
+            Some(Command::Complete(args)) => args,
+            _ => unreachable!(),
+        };
+        let mut out = Vec::new();
+        write_completions(&args, &mut out)?;
+        Ok(String::from_utf8(out)?
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    #[test]
+    fn complete_test() {
+        // Only the check digit is unknown
+        assert_eq!(
+            vec!["26DK0047002M8N41B6"],
+            completions(&["26DK0047002M8N41B?"]).unwrap()
+        );
+        assert_eq!(
+            vec!["1"],
+            completions(&["26DK0047002M8N41B?", "--count"]).unwrap()
+        );
+
+        // Two unknown characters, one of them the check digit, leave 36 MRNs
+        let all = completions(&["26dk0047002m8n41?6", "-n", "50"]).unwrap();
+        assert!(all.iter().all(|mrn| validate_mrn(mrn).is_ok()));
+        assert!(all.contains(&"26DK0047002M8N41B6".to_string()));
+        let count: usize = completions(&["26DK0047002M8N41?6", "--count"]).unwrap()[0]
+            .parse()
+            .unwrap();
+        assert_eq!(all.len(), count);
+        assert_eq!(
+            36,
+            completions(&["26DK0047002M8N41??", "--count"]).unwrap()[0]
+                .parse::<usize>()
+                .unwrap()
+        );
+
+        let sampled =
+            completions(&["26DK0047??????????", "--sample", "-n", "5", "--seed", "7"]).unwrap();
+        assert_eq!(5, sampled.len());
+        assert!(sampled
+            .iter()
+            .all(|mrn| mrn.starts_with("26DK0047") && validate_mrn(mrn).is_ok()));
+        assert_eq!(
+            sampled,
+            completions(&["26DK0047??????????", "--sample", "-n", "5", "--seed", "7"]).unwrap()
+        );
+
+        assert!(completions(&["26DK0047"]).is_err());
+        assert!(completions(&["2ADK0047002M8N41B6"]).is_err());
+    }
+}
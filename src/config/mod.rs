@@ -0,0 +1,147 @@
+//! Config file of the command line utility, with named profiles of arguments, e.g.
+//!
+//! ```toml
+//! [profile.dk-export]
+//! country-code = "DK"
+//! declaration-office = "004700"
+//! procedure-category = "B1"
+//! format = "json"
+//! output = "dk-export.jsonl"
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use toml::{Table, Value};
+
+/// Config file read from the working directory when no other one is given
+pub const CONFIG_FILE: &str = "mrn-generator.toml";
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Arguments of each profile, by their long names without dashes
+    #[serde(default)]
+    pub profile: BTreeMap<String, Table>,
+}
+
+impl Config {
+    /// Loads a config file, or the config file of the working directory if there is one
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(CONFIG_FILE).exists() => Path::new(CONFIG_FILE),
+            None => return Ok(Config::default()),
+        };
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("could not read config file {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("could not load config file {}", path.display()))
+    }
+
+    /// Arguments of a profile as they would be written on the command line, leaving out those
+    /// that are already given
+    pub fn profile_args(
+        &self,
+        name: &str,
+        command: &clap::Command,
+        given: &[String],
+    ) -> Result<Option<Vec<String>>> {
+        let Some(profile) = self.profile.get(name) else {
+            return Ok(None);
+        };
+
+        let mut args = Vec::new();
+        for (key, value) in profile {
+            let Some(arg) = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(key))
+            else {
+                bail!("profile {name} has an unknown argument {key}");
+            };
+            if is_given(arg, given) {
+                continue;
+            }
+            let value = match value {
+                Value::Boolean(true) => None,
+                Value::Boolean(false) => continue,
+                Value::Array(values) => Some(
+                    values
+                        .iter()
+                        .map(|value| scalar(name, key, value))
+                        .collect::<Result<Vec<_>>>()?
+                        .join(","),
+                ),
+                value => Some(scalar(name, key, value)?),
+            };
+            args.push(format!("--{key}"));
+            args.extend(value);
+        }
+        Ok(Some(args))
+    }
+}
+
+/// Whether an argument is among the command line arguments, by its long or short name
+fn is_given(arg: &clap::Arg, given: &[String]) -> bool {
+    given.iter().any(|given| match given.strip_prefix("--") {
+        Some(long) => arg
+            .get_long()
+            .is_some_and(|name| long == name || long.starts_with(&format!("{name}="))),
+        None => arg.get_short().is_some_and(|short| {
+            given.strip_prefix('-').and_then(|s| s.chars().next()) == Some(short)
+        }),
+    })
+}
+
+/// Command line form of a single value of a profile
+fn scalar(name: &str, key: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(i) => Ok(i.to_string()),
+        Value::Float(f) => Ok(f.to_string()),
+        _ => bail!("argument {key} of profile {name} should be a string, number or list of them"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parser::Args;
+    use clap::CommandFactory;
+
+    #[test]
+    fn profile_args_test() {
+        let config: Config = toml::from_str(
+            r#"
+            [profile.dk-export]
+            country-code = ["DK", "SE"]
+            number-of-mrns = 10
+            procedure-category = "B1"
+            random-office = true
+            manifest = false
+            "#,
+        )
+        .unwrap();
+        let command = Args::command();
+
+        assert_eq!(
+            Some(vec![
+                "--country-code".to_string(),
+                "DK,SE".to_string(),
+                "--procedure-category".to_string(),
+                "B1".to_string(),
+                "--random-office".to_string(),
+            ]),
+            config
+                .profile_args("dk-export", &command, &["-n5".to_string()])
+                .unwrap()
+        );
+        assert_eq!(None, config.profile_args("dk", &command, &[]).unwrap());
+
+        let config: Config = toml::from_str("[profile.dk]\ncountry = \"DK\"").unwrap();
+        assert!(config.profile_args("dk", &command, &[]).is_err());
+    }
+}
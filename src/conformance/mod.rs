@@ -0,0 +1,137 @@
+//! Conformance vectors, curated valid and invalid MRNs for other validators to be tested against
+//!
+//! The valid vectors cover every procedure character and several countries, with and without the
+//! office reference real MRNs start with. The invalid ones cover each kind of error, mostly as
+//! near misses of valid MRNs, e.g. swapped characters or a letter O instead of a zero. The vectors
+//! are compiled from `data/conformance.txt`, versioned as [CONFORMANCE_VERSION].
+//!
+//! ## Example
+//! ```
+//! use mrn_generator::conformance::vectors;
+//!
+//! // A validator of another crate or system, checking only the length
+//! fn is_valid(mrn: &str) -> bool {
+//!     mrn.len() == 18
+//! }
+//!
+//! let failures: Vec<_> = vectors()
+//!     .iter()
+//!     .filter(|vector| is_valid(vector.mrn) != vector.expected.is_valid())
+//!     .map(|vector| (vector.mrn, vector.description))
+//!     .collect();
+//! assert!(failures.contains(&("24DKU1B0GKUUN40NA4", "Wrong check digit")));
+//! ```
+
+use crate::MrnGeneratorError;
+
+/// Expected result of the validation of a conformance vector
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Expected {
+    Valid,
+    /// The MRN isn't 18 characters long
+    Length,
+    /// The MRN has a character other than a letter or digit
+    InvalidCharacter,
+    /// The MRN doesn't start with two digits
+    InvalidYear,
+    /// The country code isn't two capital letters
+    InvalidCountryCode,
+    /// The check digit is wrong
+    InvalidCheckDigit,
+}
+
+impl Expected {
+    /// Code of the result, as in the data file and the errors of `mrn-generator serve`, e.g.
+    /// `invalid-check-digit`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Expected::Valid => "valid",
+            Expected::Length => "length",
+            Expected::InvalidCharacter => "invalid-character",
+            Expected::InvalidYear => "invalid-year",
+            Expected::InvalidCountryCode => "invalid-country-code",
+            Expected::InvalidCheckDigit => "invalid-check-digit",
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        *self == Expected::Valid
+    }
+
+    /// Checks whether a result of [validate_mrn](crate::validate_mrn) is the expected one
+    pub fn matches(&self, result: &Result<(), MrnGeneratorError>) -> bool {
+        use MrnGeneratorError::*;
+
+        matches!(
+            (self, result),
+            (Expected::Valid, Ok(()))
+                | (Expected::Length, Err(MrnLength(_)))
+                | (Expected::InvalidCharacter, Err(InvalidCharacter { .. }))
+                | (Expected::InvalidYear, Err(InvalidYear(_)))
+                | (Expected::InvalidCountryCode, Err(InvalidCountryCode(_)))
+                | (Expected::InvalidCheckDigit, Err(InvalidCheckDigit { .. }))
+        )
+    }
+}
+
+/// An MRN with its expected validation result
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ConformanceVector {
+    pub mrn: &'static str,
+    pub expected: Expected,
+    /// What the vector is about, e.g. `Denmark, export only` or `Two characters swapped`
+    pub description: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/conformance.rs"));
+
+/// Returns every conformance vector, the valid ones first
+pub fn vectors() -> &'static [ConformanceVector] {
+    VECTORS
+}
+
+/// Returns the conformance vectors of valid MRNs
+pub fn valid_vectors() -> impl Iterator<Item = &'static ConformanceVector> {
+    VECTORS.iter().filter(|vector| vector.expected.is_valid())
+}
+
+/// Returns the conformance vectors of invalid MRNs
+pub fn invalid_vectors() -> impl Iterator<Item = &'static ConformanceVector> {
+    VECTORS.iter().filter(|vector| !vector.expected.is_valid())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{all_procedures, layout, procecure_category_to_char, validate_mrn};
+
+    #[test]
+    fn conformance_test() {
+        for vector in vectors() {
+            let result = validate_mrn(vector.mrn);
+            assert!(
+                vector.expected.matches(&result),
+                "{} ({}) should be {}, not {result:?}",
+                vector.mrn,
+                vector.description,
+                vector.expected.code()
+            );
+        }
+
+        // Every procedure character and kind of error is covered
+        for &procedure in all_procedures() {
+            let c = procecure_category_to_char(procedure);
+            assert!(valid_vectors().any(|vector| vector.mrn[layout::PROCEDURE].starts_with(c)));
+        }
+        for expected in [
+            Expected::Length,
+            Expected::InvalidCharacter,
+            Expected::InvalidYear,
+            Expected::InvalidCountryCode,
+            Expected::InvalidCheckDigit,
+        ] {
+            assert!(invalid_vectors().any(|vector| vector.expected == expected));
+        }
+    }
+}
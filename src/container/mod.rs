@@ -0,0 +1,132 @@
+//! ISO 6346 freight container identification numbers, e.g. `CSQU3054383`
+
+#[cfg(feature = "generate")]
+use crate::{capitalize, replace_last_char};
+use crate::{is_mrn_valid, MrnGeneratorError};
+#[cfg(feature = "generate")]
+use alloc::format;
+#[cfg(feature = "generate")]
+use alloc::string::String;
+use alloc::string::ToString;
+
+/// Length of a container number including its check digit
+pub const CONTAINER_NUMBER_LENGTH: usize = 11;
+
+/// Equipment category identifiers
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EquipmentCategory {
+    FreightContainer,
+    DetachableFreightContainerRelatedEquipment,
+    TrailerOrChassis,
+}
+
+/// Maps equipment category to a corresponding character
+pub fn equipment_category_to_char(category: EquipmentCategory) -> char {
+    match category {
+        EquipmentCategory::FreightContainer => 'U',
+        EquipmentCategory::DetachableFreightContainerRelatedEquipment => 'J',
+        EquipmentCategory::TrailerOrChassis => 'Z',
+    }
+}
+
+/// Returns a valid container number, using a random owner code if none is given
+///
+/// ## Example
+/// ```
+/// use mrn_generator::container::{
+///     generate_random_container_number, is_container_number_valid, EquipmentCategory,
+/// };
+///
+/// let number =
+///     generate_random_container_number(Some("CSQ"), EquipmentCategory::FreightContainer).unwrap();
+/// assert!(number.starts_with("CSQU"));
+/// assert_eq!(None, is_container_number_valid(&number).unwrap());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_random_container_number(
+    owner_code: Option<&str>,
+    category: EquipmentCategory,
+) -> Result<String, MrnGeneratorError> {
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    let owner_code = match owner_code {
+        Some(owner_code) => {
+            if owner_code.len() != 3 || !owner_code.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(MrnGeneratorError::InvalidOwnerCode(owner_code.to_string()));
+            }
+            capitalize(owner_code)
+        }
+        None => (0..3).map(|_| rng.gen_range('A'..='Z')).collect(),
+    };
+
+    let number = format!(
+        "{owner_code}{}{:06}0",
+        equipment_category_to_char(category),
+        rng.gen_range(0..1_000_000)
+    );
+
+    match is_container_number_valid(&number)? {
+        Some(check_digit) => Ok(replace_last_char(&number, check_digit)),
+        None => Ok(number),
+    }
+}
+
+/// Returns None if the container number is valid, and correct check digit if it's invalid
+///
+/// The number should be an owner code of three capital letters, an equipment category
+/// identifier (U, J or Z), a six digit serial number and a check digit.
+pub fn is_container_number_valid(number: &str) -> Result<Option<char>, MrnGeneratorError> {
+    let bytes = number.as_bytes();
+
+    let valid_format = bytes.len() == CONTAINER_NUMBER_LENGTH
+        && bytes[..3].iter().all(u8::is_ascii_uppercase)
+        && matches!(bytes[3], b'U' | b'J' | b'Z')
+        && bytes[4..].iter().all(u8::is_ascii_digit);
+
+    if !valid_format {
+        return Err(MrnGeneratorError::ContainerNumberFormat(number.to_string()));
+    }
+
+    is_mrn_valid(number)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_random_container_number_test() {
+        let number =
+            generate_random_container_number(Some("msk"), EquipmentCategory::TrailerOrChassis)
+                .unwrap();
+        assert_eq!(CONTAINER_NUMBER_LENGTH, number.len());
+        assert!(number.starts_with("MSKZ"));
+        assert_eq!(None, is_container_number_valid(&number).unwrap());
+
+        let number =
+            generate_random_container_number(None, EquipmentCategory::FreightContainer).unwrap();
+        assert_eq!(None, is_container_number_valid(&number).unwrap());
+
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidOwnerCode("MS".to_string())),
+            generate_random_container_number(Some("MS"), EquipmentCategory::FreightContainer)
+        );
+    }
+
+    #[test]
+    fn is_container_number_valid_test() {
+        assert_eq!(None, is_container_number_valid("CSQU3054383").unwrap());
+        assert_eq!(Some('3'), is_container_number_valid("CSQU3054384").unwrap());
+        for invalid in ["CSQU305438", "CSQX3054383", "CSQUA054383", "csqu3054383"] {
+            assert_eq!(
+                Err(MrnGeneratorError::ContainerNumberFormat(
+                    invalid.to_string()
+                )),
+                is_container_number_valid(invalid)
+            );
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Country names and aliases, resolved to the country codes used in MRNs
+
+use crate::{closest, MrnGeneratorError};
+use alloc::string::{String, ToString};
+
+/// A country that issues MRNs, with its English name and other common names
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Country {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+}
+
+/// Member states and common transit countries
+const COUNTRIES: &[Country] = &[
+    country("AD", "Andorra", &[]),
+    country("AL", "Albania", &[]),
+    country("AT", "Austria", &["Osterreich"]),
+    country("BE", "Belgium", &["Belgique", "Belgie"]),
+    country("BG", "Bulgaria", &[]),
+    country("CH", "Switzerland", &["Schweiz", "Suisse"]),
+    country("CY", "Cyprus", &[]),
+    country("CZ", "Czechia", &["Czech Republic"]),
+    country("DE", "Germany", &["Deutschland"]),
+    country("DK", "Denmark", &["Danmark"]),
+    country("EE", "Estonia", &[]),
+    country("ES", "Spain", &["Espana"]),
+    country("FI", "Finland", &["Suomi"]),
+    country("FR", "France", &[]),
+    country("GB", "United Kingdom", &["UK", "Great Britain", "Britain"]),
+    country("GE", "Georgia", &[]),
+    country("GR", "Greece", &["Hellas", "Ellada"]),
+    country("HR", "Croatia", &["Hrvatska"]),
+    country("HU", "Hungary", &["Magyarorszag"]),
+    country("IE", "Ireland", &["Eire"]),
+    country("IS", "Iceland", &[]),
+    country("IT", "Italy", &["Italia"]),
+    country("LT", "Lithuania", &[]),
+    country("LU", "Luxembourg", &[]),
+    country("LV", "Latvia", &[]),
+    country("MD", "Moldova", &[]),
+    country("ME", "Montenegro", &[]),
+    country("MK", "North Macedonia", &["Macedonia"]),
+    country("MT", "Malta", &[]),
+    country(
+        "NL",
+        "Netherlands",
+        &["The Netherlands", "Holland", "Nederland"],
+    ),
+    country("NO", "Norway", &["Norge"]),
+    country("PL", "Poland", &["Polska"]),
+    country("PT", "Portugal", &[]),
+    country("RO", "Romania", &[]),
+    country("RS", "Serbia", &[]),
+    country("SE", "Sweden", &["Sverige"]),
+    country("SI", "Slovenia", &[]),
+    country("SK", "Slovakia", &["Slovak Republic"]),
+    country("SM", "San Marino", &[]),
+    country("TR", "Turkiye", &["Turkey"]),
+    country("UA", "Ukraine", &[]),
+    country("XI", "Northern Ireland", &[]),
+];
+
+/// Codes used for a country outside of MRNs, e.g. in VAT numbers, and the country code of MRNs
+const CODE_ALIASES: &[(&str, &str)] = &[("EL", "GR"), ("UK", "GB")];
+
+const fn country(
+    code: &'static str,
+    name: &'static str,
+    aliases: &'static [&'static str],
+) -> Country {
+    Country {
+        code,
+        name,
+        aliases,
+    }
+}
+
+/// Returns the known countries, sorted by country code
+pub fn all_countries() -> &'static [Country] {
+    COUNTRIES
+}
+
+/// Returns the English name of a country code
+///
+/// ## Example
+/// ```
+/// use mrn_generator::country::country_name;
+///
+/// assert_eq!(Some("Greece"), country_name("GR"));
+/// ```
+pub fn country_name(code: &str) -> Option<&'static str> {
+    COUNTRIES.iter().find(|c| c.code == code).map(|c| c.name)
+}
+
+/// Resolves a country code, name or alias to a capitalized country code
+///
+/// Names, aliases and codes used outside of MRNs are matched ignoring case, other two letters are
+/// taken as a country code as they are, and an unknown name gets the closest known one as a
+/// suggestion.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::country::resolve_country;
+/// use mrn_generator::MrnGeneratorError;
+///
+/// assert_eq!(Ok("DK".to_string()), resolve_country("Denmark"));
+/// assert_eq!(Ok("GR".to_string()), resolve_country("hellas"));
+/// assert_eq!(Ok("IT".to_string()), resolve_country("it"));
+/// assert_eq!(
+///     Err(MrnGeneratorError::UnknownCountry {
+///         value: "Denmrak".to_string(),
+///         suggestion: Some("Denmark".to_string())
+///     }),
+///     resolve_country("Denmrak")
+/// );
+/// ```
+pub fn resolve_country(value: &str) -> Result<String, MrnGeneratorError> {
+    let value = value.trim();
+    let names = || {
+        COUNTRIES.iter().flat_map(|c| {
+            core::iter::once(c.name)
+                .chain(c.aliases.iter().copied())
+                .map(move |name| (name, c.code))
+        })
+    };
+    let found = names()
+        .chain(CODE_ALIASES.iter().copied())
+        .find(|(name, _)| name.eq_ignore_ascii_case(value));
+    match found {
+        Some((_, code)) => Ok(code.to_string()),
+        None if value.len() == 2 && value.chars().all(|c| c.is_ascii_alphabetic()) => {
+            Ok(value.to_ascii_uppercase())
+        }
+        None => Err(MrnGeneratorError::UnknownCountry {
+            value: value.to_string(),
+            suggestion: closest(value, names().map(|(name, _)| name)).map(str::to_string),
+        }),
+    }
+}
+
+/// Checks that a country code is one of the known countries, suggesting the closest known code
+/// if it isn't
+///
+/// ## Example
+/// ```
+/// use mrn_generator::country::check_known_country;
+///
+/// assert!(check_known_country("DK").is_ok());
+/// assert!(check_known_country("US").is_err());
+/// ```
+pub fn check_known_country(code: &str) -> Result<(), MrnGeneratorError> {
+    match country_name(code) {
+        Some(_) => Ok(()),
+        None => Err(MrnGeneratorError::UnknownCountry {
+            value: code.to_string(),
+            suggestion: suggest_country_code(code).map(str::to_string),
+        }),
+    }
+}
+
+/// Known country code that an unknown one was likely meant to be: the code itself without
+/// blanks or in capitals, the MRN code of a country known by another code or the closest code
+///
+/// ## Example
+/// ```
+/// use mrn_generator::country::suggest_country_code;
+///
+/// assert_eq!(Some("DK"), suggest_country_code("D K"));
+/// assert_eq!(Some("GR"), suggest_country_code("EL"));
+/// assert_eq!(None, suggest_country_code("QQ"));
+/// ```
+pub fn suggest_country_code(code: &str) -> Option<&'static str> {
+    let compact: String = code
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    COUNTRIES
+        .iter()
+        .map(|c| c.code)
+        .find(|known| *known == compact)
+        .or_else(|| {
+            CODE_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == compact)
+                .map(|(_, known)| *known)
+        })
+        .or_else(|| closest(&compact, COUNTRIES.iter().map(|c| c.code)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn resolve_country_test() {
+        assert_eq!(Ok("NL".to_string()), resolve_country(" holland "));
+        assert_eq!(Ok("XI".to_string()), resolve_country("Northern Ireland"));
+        assert_eq!(Ok("GR".to_string()), resolve_country("Greece"));
+        assert_eq!(Ok("GB".to_string()), resolve_country("UK"));
+        assert_eq!(Ok("GR".to_string()), resolve_country("el"));
+        assert_eq!(Ok("ZZ".to_string()), resolve_country("zz"));
+        assert_eq!(
+            Err(MrnGeneratorError::UnknownCountry {
+                value: "Grece".to_string(),
+                suggestion: Some("Greece".to_string())
+            }),
+            resolve_country("Grece")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::UnknownCountry {
+                value: "Atlantis".to_string(),
+                suggestion: None
+            }),
+            resolve_country("Atlantis")
+        );
+        assert!(COUNTRIES.windows(2).all(|w| w[0].code < w[1].code));
+    }
+
+    #[test]
+    fn check_known_country_test() {
+        assert!(COUNTRIES
+            .iter()
+            .all(|c| check_known_country(c.code).is_ok()));
+        assert_eq!(
+            Err(MrnGeneratorError::UnknownCountry {
+                value: "QQ".to_string(),
+                suggestion: None
+            }),
+            check_known_country("QQ")
+        );
+    }
+
+    #[test]
+    fn suggest_country_code_test() {
+        assert_eq!(Some("DK"), suggest_country_code("dk"));
+        assert_eq!(Some("GB"), suggest_country_code("UK"));
+        assert_eq!(Some("XI"), suggest_country_code("X1"));
+        assert!(CODE_ALIASES
+            .iter()
+            .all(|(alias, code)| country_name(alias).is_none() && country_name(code).is_some()));
+    }
+}
@@ -0,0 +1,303 @@
+//! Synthetic declarations for customs system tests, with every reference and time of a declaration
+//! agreeing with the others
+
+use crate::commands::MAX_ATTEMPTS;
+use crate::distribution::DateSpread;
+use crate::parser::{DatasetArgs, DatasetFormat};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike, NaiveTime, SecondsFormat, TimeDelta, Utc};
+use mrn_generator::col::offices_for_country;
+use mrn_generator::eori::generate_eori_with_rng;
+use mrn_generator::lrn::{generate_lrn_with_source, LRN_MAX_LENGTH};
+use mrn_generator::source::RandomSource;
+use mrn_generator::{
+    all_mappings, generate_mrn, match_procedure, procedure_description, Combination,
+    DeclarationOffice, MrnConfig, Procedure,
+};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::{self, BufWriter, Write};
+
+/// Longest time between lodging a declaration and accepting it, in seconds
+const MAX_ACCEPTANCE_DELAY: i64 = 4 * 60 * 60;
+
+/// A declaration, named like the columns of the CSV output and the keys of the JSON output
+#[derive(Serialize, Debug)]
+struct Declaration {
+    mrn: String,
+    lrn: String,
+    /// EORI number of the declarant
+    eori: String,
+    country_code: String,
+    /// Reference of the customs office of declaration, empty if the customs office list has no
+    /// offices in the country
+    declaration_office: String,
+    declaration_category: String,
+    /// Category the declaration category is combined with, empty if it isn't combined
+    combined_category: String,
+    procedure: &'static str,
+    lodged_at: String,
+    accepted_at: String,
+}
+
+/// Picks the country, office, procedure and times of each declaration and generates its
+/// references
+struct Declarations<'a> {
+    args: &'a DatasetArgs,
+    rng: StdRng,
+    accepted_on: DateSpread,
+    mrns: RandomSource<StdRng>,
+    lrns: RandomSource<StdRng>,
+    issued: HashSet<String>,
+    now: DateTime<Utc>,
+}
+
+impl<'a> Declarations<'a> {
+    fn new(args: &'a DatasetArgs) -> Result<Self> {
+        let seed = args.seed.unwrap_or_else(rand::random);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let (start, end) = match args.accepted_over {
+            Some(range) => (range.start, range.end),
+            None => (
+                today.with_ordinal(1).expect("every year has a first day"),
+                today,
+            ),
+        };
+        // Offset the seed so that none of the choices are made in lockstep
+        Ok(Declarations {
+            args,
+            rng: StdRng::seed_from_u64(seed),
+            accepted_on: DateSpread::new(start, end, Some(seed.wrapping_add(1)))?,
+            mrns: RandomSource::seeded(seed.wrapping_add(2)),
+            lrns: RandomSource::seeded(seed.wrapping_add(3)),
+            issued: HashSet::new(),
+            now,
+        })
+    }
+
+    /// Generates the next declaration
+    fn next(&mut self) -> Result<Declaration> {
+        let args = self.args;
+        let country_code = args
+            .country_code
+            .choose(&mut self.rng)
+            .expect("--country-code is required");
+        let office = offices_for_country(country_code).choose(&mut self.rng);
+        let (declaration_category, combined_category, procedure) = self.procedure()?;
+
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(self.rng.gen_range(0..86_400), 0)
+            .expect("the time is within a day");
+        // Only the last day of the range can be today, whose acceptances can't be later than now
+        let accepted_at = self
+            .accepted_on
+            .pick()
+            .and_time(time)
+            .and_utc()
+            .min(self.now);
+        let lodged_at =
+            accepted_at - TimeDelta::seconds(self.rng.gen_range(0..=MAX_ACCEPTANCE_DELAY));
+
+        let config = MrnConfig {
+            country_code,
+            procedure: Some(procedure),
+            declaration_office: office
+                .map(|office| DeclarationOffice::new(office.office_number()))
+                .transpose()?,
+            year: Some((accepted_at.year() % 100) as u8),
+        };
+        let mrns = &mut self.mrns;
+        let mrn = unique(&mut self.issued, || Ok(generate_mrn(&config, &mut *mrns)?))?;
+        let lrns = &mut self.lrns;
+        let lrn = unique(&mut self.issued, || {
+            Ok(generate_lrn_with_source(
+                &args.lrn_prefix,
+                LRN_MAX_LENGTH,
+                &mut *lrns,
+            )?)
+        })?;
+
+        Ok(Declaration {
+            mrn,
+            lrn,
+            eori: generate_eori_with_rng(country_code, &mut self.rng)?,
+            country_code: country_code.clone(),
+            declaration_office: office
+                .map(|office| office.reference.to_string())
+                .unwrap_or_default(),
+            declaration_category,
+            combined_category,
+            procedure: procedure_description(procedure),
+            lodged_at: lodged_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            accepted_at: accepted_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        })
+    }
+
+    /// The declaration category, the category it is combined with and the procedure of the next
+    /// declaration
+    fn procedure(&mut self) -> Result<(String, String, Procedure)> {
+        let args = self.args;
+        if let Some(category) = args.procedure_category.as_deref() {
+            let procedure = match_procedure(category, args.combined.as_deref())?;
+            let combined = args.combined.clone().unwrap_or_default();
+            return Ok((category.to_string(), combined, procedure));
+        }
+        let mapping = all_mappings()
+            .choose(&mut self.rng)
+            .expect("the procedure table is not empty");
+        let combined = match mapping.combination {
+            Combination::With(combined) => combined,
+            Combination::Standalone | Combination::Any => "",
+        };
+        Ok((
+            mapping.category.to_string(),
+            combined.to_string(),
+            mapping.procedure,
+        ))
+    }
+}
+
+/// Generates identifiers until one of them wasn't issued yet, and issues it
+fn unique(
+    issued: &mut HashSet<String>,
+    mut generate: impl FnMut() -> Result<String>,
+) -> Result<String> {
+    for _ in 0..MAX_ATTEMPTS {
+        let id = generate()?;
+        if issued.insert(id.clone()) {
+            return Ok(id);
+        }
+    }
+    bail!("could not generate a unique identifier after {MAX_ATTEMPTS} attempts")
+}
+
+/// Generates synthetic declarations to standard output
+pub fn dataset(args: DatasetArgs) -> Result<()> {
+    write_dataset(&args, io::stdout().lock())
+}
+
+fn write_dataset(args: &DatasetArgs, out: impl Write) -> Result<()> {
+    if args.delimiter.is_some() && args.format == DatasetFormat::Json {
+        bail!("--delimiter only works with csv or tsv output");
+    }
+    let mut declarations = Declarations::new(args)?;
+    match args.format {
+        DatasetFormat::Csv | DatasetFormat::Tsv => {
+            let default_delimiter = match args.format {
+                DatasetFormat::Tsv => b'\t',
+                _ => b',',
+            };
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(args.delimiter.unwrap_or(default_delimiter))
+                .from_writer(out);
+            for _ in 0..args.number_of_declarations {
+                writer.serialize(declarations.next()?)?;
+            }
+            writer.flush()?;
+        }
+        DatasetFormat::Json => {
+            let mut out = BufWriter::new(out);
+            for _ in 0..args.number_of_declarations {
+                serde_json::to_writer(&mut out, &declarations.next()?)?;
+                writeln!(out)?;
+            }
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parser::{Args, Command};
+    use clap::Parser;
+    use mrn_generator::eori::validate_eori;
+    use mrn_generator::{char_to_procedure_category, layout, validate_mrn};
+
+    fn dataset_args(argv: &[&str]) -> DatasetArgs {
+        let argv = ["mrn-generator", "dataset"].iter().chain(argv);
+        match Args::parse_from(argv).command {
+            Some(Command::Dataset(args)) => args,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn dataset_test() {
+        let args = dataset_args(&[
+            "-c",
+            "DK,IT",
+            "-n",
+            "50",
+            "--lrn-prefix",
+            "DECL-",
+            "--accepted-over",
+            "2023-12-31..2024-01-01",
+            "--seed",
+            "7",
+        ]);
+        let mut csv = Vec::new();
+        write_dataset(&args, &mut csv).unwrap();
+        let mut again = Vec::new();
+        write_dataset(&args, &mut again).unwrap();
+        assert_eq!(csv, again);
+
+        let mut reader = csv::Reader::from_reader(&*csv);
+        let mut rows = 0;
+        for row in reader.deserialize::<std::collections::HashMap<String, String>>() {
+            let row = row.unwrap();
+            let mrn = &row["mrn"];
+            assert_eq!(Ok(()), validate_mrn(mrn));
+            assert_eq!(row["country_code"], mrn[layout::COUNTRY_CODE]);
+            assert!(row["eori"].starts_with(&row["country_code"]));
+            assert_eq!(Ok(()), validate_eori(&row["eori"]));
+            assert!(row["lrn"].starts_with("DECL-"));
+            assert!(mrn[layout::SERIAL].starts_with(&row["declaration_office"][2..]));
+            let procedure =
+                char_to_procedure_category(mrn[layout::PROCEDURE].chars().next().unwrap());
+            assert_eq!(row["procedure"], procedure_description(procedure.unwrap()));
+            assert_eq!(&mrn[layout::YEAR], &row["accepted_at"][2..4]);
+            assert!(row["lodged_at"] <= row["accepted_at"]);
+            rows += 1;
+        }
+        assert_eq!(50, rows);
+
+        let args = dataset_args(&["-c", "DK", "-p", "D1", "-C", "F", "--format", "json"]);
+        let mut json = Vec::new();
+        write_dataset(&args, &mut json).unwrap();
+        let declaration: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!("D1", declaration["declaration_category"]);
+        assert_eq!("F", declaration["combined_category"]);
+        assert_eq!(
+            'L',
+            declaration["mrn"]
+                .as_str()
+                .unwrap()
+                .chars()
+                .nth(16)
+                .unwrap()
+        );
+
+        for (argv, delimiter) in [
+            (&["--format", "tsv"][..], b'\t'),
+            (&["--delimiter", ";"][..], b';'),
+        ] {
+            let args = dataset_args(&[&["-c", "DK", "-n", "3"], argv].concat());
+            let mut out = Vec::new();
+            write_dataset(&args, &mut out).unwrap();
+            let mut reader = csv::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .from_reader(&*out);
+            for row in reader.deserialize::<std::collections::HashMap<String, String>>() {
+                assert_eq!(Ok(()), validate_mrn(&row.unwrap()["mrn"]));
+            }
+        }
+        let args = dataset_args(&["-c", "DK", "--format", "json", "--delimiter", ";"]);
+        assert!(write_dataset(&args, Vec::new()).is_err());
+    }
+}
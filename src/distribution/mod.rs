@@ -0,0 +1,111 @@
+use anyhow::{bail, Result};
+use chrono::{Days, NaiveDate};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Random choice between values, uniform or proportional to weights
+pub struct WeightedChoice<T> {
+    values: Vec<T>,
+    index: WeightedIndex<u32>,
+    rng: StdRng,
+}
+
+impl<T> WeightedChoice<T> {
+    /// Creates a choice between the values, seeded for reproducible output if a seed is given
+    pub fn new(values: Vec<T>, weights: Option<&[u32]>, seed: Option<u64>) -> Result<Self> {
+        let weights = match weights {
+            Some(weights) if weights.len() != values.len() => {
+                bail!(
+                    "expected {} weights but got {}",
+                    values.len(),
+                    weights.len()
+                )
+            }
+            Some(weights) => weights.to_vec(),
+            None => vec![1; values.len()],
+        };
+
+        let index = WeightedIndex::new(weights)?;
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Ok(WeightedChoice { values, index, rng })
+    }
+
+    /// Picks one of the values
+    pub fn pick(&mut self) -> &T {
+        &self.values[self.index.sample(&mut self.rng)]
+    }
+}
+
+/// Random choice of a day in a date range, so that each year gets MRNs in proportion to the
+/// number of its days in the range
+pub struct DateSpread {
+    start: NaiveDate,
+    days: u64,
+    rng: StdRng,
+}
+
+impl DateSpread {
+    /// Creates a choice between the days from start to end, both included
+    pub fn new(start: NaiveDate, end: NaiveDate, seed: Option<u64>) -> Result<Self> {
+        let Ok(days) = u64::try_from((end - start).num_days()) else {
+            bail!("the date range {start}..{end} ends before it starts");
+        };
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Ok(DateSpread {
+            start,
+            days: days + 1,
+            rng,
+        })
+    }
+
+    /// Picks one of the days
+    pub fn pick(&mut self) -> NaiveDate {
+        self.start + Days::new(self.rng.gen_range(0..self.days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn weighted_choice_test() {
+        let mut choice = WeightedChoice::new(vec![20, 21, 22], Some(&[0, 1, 0]), None).unwrap();
+        assert!((0..100).all(|_| *choice.pick() == 21));
+
+        let mut uniform = WeightedChoice::new(vec![20, 21], None, Some(1)).unwrap();
+        let mut same = WeightedChoice::new(vec![20, 21], None, Some(1)).unwrap();
+        assert!((0..100).all(|_| uniform.pick() == same.pick()));
+
+        assert!(WeightedChoice::new(vec![20, 21], Some(&[1]), None).is_err());
+        assert!(WeightedChoice::new(vec![20, 21], Some(&[0, 0]), None).is_err());
+    }
+
+    #[test]
+    fn date_spread_test() {
+        let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+        let mut spread = DateSpread::new(date("2023-12-31"), date("2024-01-01"), Some(3)).unwrap();
+        let picks: Vec<NaiveDate> = (0..100).map(|_| spread.pick()).collect();
+        assert!(picks.contains(&date("2023-12-31")));
+        assert!(picks.contains(&date("2024-01-01")));
+        assert!(picks.iter().all(|d| *d <= date("2024-01-01")));
+
+        // 2024 has 366 of the 731 days
+        let mut spread = DateSpread::new(date("2023-01-01"), date("2024-12-31"), None).unwrap();
+        let in_2024 = (0..10000)
+            .filter(|_| spread.pick() >= date("2024-01-01"))
+            .count();
+        assert!((4500..5500).contains(&in_2024));
+
+        assert!(DateSpread::new(date("2024-01-02"), date("2024-01-01"), None).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! ICS2 entry summary declaration (ENS) conventions
+//!
+//! ENS filings use the F declaration categories, optionally combined with a transit, import or
+//! temporary storage declaration, and their MRN is issued by the customs office of first entry.
+
+use crate::{char_to_procedure_category, layout, match_optional_procedure, validate_mrn};
+use crate::{MrnGeneratorError, Procedure};
+use alloc::string::ToString;
+
+/// Returns true if the procedure includes an entry summary declaration
+pub fn is_ens_procedure(procedure: Procedure) -> bool {
+    matches!(
+        procedure,
+        Procedure::EntrySummaryDeclarationOnly
+            | Procedure::TransitDeclarationAndEntrySummaryDeclaration
+            | Procedure::ImportDeclarationAndEntrySummaryDeclaration
+            | Procedure::TemporaryStorageDeclarationAndEntrySummaryDeclaration
+    )
+}
+
+/// Matches the procedure of an ENS filing, defaulting to an entry summary declaration only
+///
+/// ## Example
+/// ```
+/// use mrn_generator::ens::match_ens_procedure;
+/// use mrn_generator::Procedure;
+///
+/// assert_eq!(Ok(Procedure::EntrySummaryDeclarationOnly), match_ens_procedure(None, None));
+/// assert_eq!(
+///     Ok(Procedure::ImportDeclarationAndEntrySummaryDeclaration),
+///     match_ens_procedure(Some("H1"), Some("F"))
+/// );
+/// assert!(match_ens_procedure(Some("B1"), None).is_err());
+/// ```
+pub fn match_ens_procedure(
+    proctgr: Option<&str>,
+    combined: Option<&str>,
+) -> Result<Procedure, MrnGeneratorError> {
+    check_ens_procedure(match_optional_procedure(proctgr, combined)?)
+}
+
+/// Checks that an already matched procedure is an entry summary declaration procedure, defaulting
+/// to [Procedure::EntrySummaryDeclarationOnly] if there is none
+pub fn check_ens_procedure(procedure: Option<Procedure>) -> Result<Procedure, MrnGeneratorError> {
+    let procedure = procedure.unwrap_or(Procedure::EntrySummaryDeclarationOnly);
+    if is_ens_procedure(procedure) {
+        Ok(procedure)
+    } else {
+        Err(MrnGeneratorError::NotAnEnsProcedure(procedure))
+    }
+}
+
+/// Checks that the MRN is valid and plausible as an ENS MRN, i.e. its procedure character is one
+/// of an entry summary declaration
+pub fn validate_ens_mrn(mrn: &str) -> Result<(), MrnGeneratorError> {
+    validate_mrn(mrn)?;
+
+    let procedure_char = mrn[layout::PROCEDURE].chars().next().unwrap();
+    match char_to_procedure_category(procedure_char) {
+        Some(procedure) if is_ens_procedure(procedure) => Ok(()),
+        _ => Err(MrnGeneratorError::NotAnEnsMrn {
+            mrn: mrn.to_string(),
+            procedure_char,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn match_ens_procedure_test() {
+        assert_eq!(
+            Ok(Procedure::EntrySummaryDeclarationOnly),
+            match_ens_procedure(Some("F1a"), None)
+        );
+        assert_eq!(
+            Ok(Procedure::TransitDeclarationAndEntrySummaryDeclaration),
+            match_ens_procedure(Some("D1"), Some("F"))
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::NotAnEnsProcedure(
+                Procedure::TransitDeclarationOnly
+            )),
+            match_ens_procedure(Some("D1"), None)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn validate_ens_mrn_test() {
+        let mrn =
+            crate::generate_random_mrn("DK", Some(Procedure::EntrySummaryDeclarationOnly), None)
+                .unwrap();
+        assert_eq!(Ok(()), validate_ens_mrn(&mrn));
+
+        let mrn = crate::generate_random_mrn("DK", Some(Procedure::ExportOnly), None).unwrap();
+        assert_eq!(
+            Err(MrnGeneratorError::NotAnEnsMrn {
+                mrn: mrn.clone(),
+                procedure_char: 'A'
+            }),
+            validate_ens_mrn(&mrn)
+        );
+    }
+}
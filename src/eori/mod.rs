@@ -0,0 +1,127 @@
+//! Economic Operators Registration and Identification (EORI) numbers, identifying traders
+
+use crate::MrnGeneratorError;
+#[cfg(feature = "generate")]
+use crate::{capitalize, source::IdSource, source::SequentialSource};
+#[cfg(feature = "generate")]
+use alloc::format;
+#[cfg(feature = "generate")]
+use alloc::string::String;
+use alloc::string::ToString;
+
+/// Maximum length of the national identifier following the country code
+pub const NATIONAL_IDENTIFIER_MAX_LENGTH: usize = 15;
+
+/// Length of the numeric national identifier used when generating EORIs for a country
+///
+/// Most member states derive the identifier from a national company or VAT number, the lengths
+/// below follow those conventions and everything else gets 12 digits.
+pub fn national_identifier_length(country_code: &str) -> usize {
+    match country_code {
+        "DK" => 8,
+        "NL" => 9,
+        "BE" => 10,
+        "IT" => 11,
+        "GB" | "XI" => 12,
+        "FR" => 14,
+        "DE" => 15,
+        _ => 12,
+    }
+}
+
+/// Returns a syntactically valid EORI number for the given country
+///
+/// ## Example
+/// ```
+/// use mrn_generator::eori::{generate_random_eori, validate_eori};
+///
+/// let eori = generate_random_eori("DK").unwrap();
+/// assert_eq!(10, eori.len());
+/// assert_eq!(Ok(()), validate_eori(&eori));
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_random_eori(country_code: &str) -> Result<String, MrnGeneratorError> {
+    generate_eori_with_rng(country_code, &mut rand::thread_rng())
+}
+
+/// Returns a syntactically valid EORI number for the given country, with its national identifier
+/// drawn from `rng`
+///
+/// ## Example
+/// ```
+/// use mrn_generator::eori::generate_eori_with_rng;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let eori = generate_eori_with_rng("DK", &mut StdRng::seed_from_u64(7)).unwrap();
+/// assert_eq!(eori, generate_eori_with_rng("DK", &mut StdRng::seed_from_u64(7)).unwrap());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_eori_with_rng(
+    country_code: &str,
+    rng: &mut impl rand::Rng,
+) -> Result<String, MrnGeneratorError> {
+    if country_code.len() != 2 {
+        return Err(MrnGeneratorError::CountryCodeLength(
+            country_code.to_string(),
+        ));
+    }
+
+    let country_code = capitalize(country_code);
+    let length = national_identifier_length(&country_code);
+    let number = rng.gen_range(0..10u64.pow(length.min(19) as u32));
+    let identifier = SequentialSource::starting_at(number).next_segment(length)?;
+
+    Ok(format!("{country_code}{identifier}"))
+}
+
+/// Checks that the EORI is a two letter country code followed by 1 to 15 uppercase alphanumerics
+pub fn validate_eori(eori: &str) -> Result<(), MrnGeneratorError> {
+    let valid = eori.len() > 2
+        && eori.len() <= 2 + NATIONAL_IDENTIFIER_MAX_LENGTH
+        && eori.chars().take(2).all(|c| c.is_ascii_uppercase())
+        && eori
+            .chars()
+            .skip(2)
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(MrnGeneratorError::InvalidEori(eori.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_random_eori_test() {
+        let eori = generate_random_eori("gb").unwrap();
+        assert!(eori.starts_with("GB"));
+        assert_eq!(14, eori.len());
+        assert_eq!(Ok(()), validate_eori(&eori));
+
+        assert_eq!(17, generate_random_eori("DE").unwrap().len());
+    }
+
+    #[test]
+    fn validate_eori_test() {
+        assert_eq!(Ok(()), validate_eori("DK12345678"));
+        assert_eq!(Ok(()), validate_eori("ESB12345678"));
+        for invalid in [
+            "DK",
+            "D112345678",
+            "dk12345678",
+            "DK1234567890123456",
+            "DK-1234",
+        ] {
+            assert_eq!(
+                Err(MrnGeneratorError::InvalidEori(invalid.to_string())),
+                validate_eori(invalid)
+            );
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{bail, Context, Result};
+use mrn_generator::render;
+use mrn_generator::sink::MrnRecord;
+use std::collections::VecDeque;
+use std::process::{Child, Command};
+
+/// Runs a shell command for each generated MRN, a limited number of them at a time
+///
+/// The placeholders `{}`, `{country}`, `{office}` and `{lrn}` of the command are replaced with the
+/// MRN, its country code, its declaration office and the LRN paired with it. Unless it fails fast, a failed command is reported
+/// and the hook only fails once all the commands finished.
+pub struct ExecHook {
+    template: String,
+    jobs: usize,
+    fail_fast: bool,
+    running: VecDeque<(String, Child)>,
+    failed: usize,
+    total: usize,
+}
+
+impl ExecHook {
+    pub fn new(template: &str, jobs: usize, fail_fast: bool) -> Result<Self> {
+        if jobs == 0 {
+            bail!("--exec-jobs must be at least 1");
+        }
+        Ok(ExecHook {
+            template: template.to_string(),
+            jobs,
+            fail_fast,
+            running: VecDeque::new(),
+            failed: 0,
+            total: 0,
+        })
+    }
+
+    /// Starts the command for an MRN, after waiting for the oldest one if too many are running
+    pub fn run(&mut self, record: &MrnRecord) -> Result<()> {
+        if self.running.len() == self.jobs {
+            self.wait_oldest()?;
+        }
+        let command = render::template(&self.template, record);
+        let child = shell(&command)
+            .spawn()
+            .with_context(|| format!("could not run {command}"))?;
+        self.running.push_back((record.mrn.to_string(), child));
+        self.total += 1;
+        Ok(())
+    }
+
+    /// Waits for all the commands still running
+    pub fn finish(mut self) -> Result<()> {
+        while !self.running.is_empty() {
+            self.wait_oldest()?;
+        }
+        if self.failed > 0 {
+            bail!("{} of {} --exec commands failed", self.failed, self.total);
+        }
+        Ok(())
+    }
+
+    fn wait_oldest(&mut self) -> Result<()> {
+        let Some((mrn, mut child)) = self.running.pop_front() else {
+            return Ok(());
+        };
+        let status = child.wait()?;
+        if !status.success() {
+            if self.fail_fast {
+                bail!("the command for {mrn} failed with {status}");
+            }
+            eprintln!("the command for {mrn} failed with {status}");
+            self.failed += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_failure_test() {
+        let record = MrnRecord {
+            mrn: "22ITZXBZYUTJFLJXK6",
+            procedure: None,
+            declaration_office: None,
+            issued_at: None,
+            lrn: None,
+        };
+        let mut hook = ExecHook::new("test {} = 22ITZXBZYUTJFLJXK6", 2, true).unwrap();
+        hook.run(&record).unwrap();
+        hook.finish().unwrap();
+
+        let mut hook = ExecHook::new("test {country} = DK", 2, true).unwrap();
+        hook.run(&record).unwrap();
+        assert!(hook.finish().is_err());
+
+        // The failures are counted and the remaining commands still run
+        let mut hook = ExecHook::new("test {country} = DK", 1, false).unwrap();
+        hook.run(&record).unwrap();
+        hook.run(&record).unwrap();
+        assert_eq!(1, hook.failed);
+        assert_eq!(
+            "2 of 2 --exec commands failed",
+            hook.finish().unwrap_err().to_string()
+        );
+    }
+}
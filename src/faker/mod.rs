@@ -0,0 +1,95 @@
+//! Random MRNs, countries and procedures for the synthetic data models of the
+//! [fake](https://docs.rs/fake) crate
+//!
+//! [Mrn], [Country] and [Procedure] implement `Dummy<Faker>`, so they can be faked on their own or
+//! as fields of structs deriving `Dummy`. Faked MRNs are issued this year by one of the known
+//! countries, for a random procedure.
+//!
+//! ## Example
+//! ```
+//! use fake::{Fake, Faker};
+//! use mrn_generator::country::Country;
+//! use mrn_generator::{Mrn, Procedure};
+//!
+//! let mrn: Mrn = Faker.fake();
+//! assert!(mrn.components().procedure.is_some());
+//! let country: Country = Faker.fake();
+//! assert_eq!(2, country.code.len());
+//! let _procedure: Procedure = Faker.fake();
+//! ```
+
+use crate::country::{all_countries, Country};
+use crate::source::RandomSource;
+use crate::{
+    char_to_procedure_category, generate_mrn, Mrn, MrnConfig, Procedure, PROCEDURE_CHARACTERS,
+};
+use fake::{Dummy, Faker};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+impl Dummy<Faker> for Procedure {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        let c = *PROCEDURE_CHARACTERS
+            .choose(rng)
+            .expect("there are procedure characters");
+        char_to_procedure_category(c as char).expect("every procedure character has a procedure")
+    }
+}
+
+impl Dummy<Faker> for Country {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        *all_countries()
+            .choose(rng)
+            .expect("there are known countries")
+    }
+}
+
+impl Dummy<Faker> for Mrn {
+    fn dummy_with_rng<R: Rng + ?Sized>(faker: &Faker, rng: &mut R) -> Self {
+        let country = Country::dummy_with_rng(faker, rng);
+        let config = MrnConfig {
+            country_code: country.code,
+            procedure: Some(Procedure::dummy_with_rng(faker, rng)),
+            ..Default::default()
+        };
+        let mrn = generate_mrn(&config, RandomSource::from_rng(rng))
+            .expect("known countries generate valid MRNs");
+        Mrn::try_from(mrn).expect("generated MRNs are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::procecure_category_to_char;
+    use fake::Fake;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn dummy_test() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..100 {
+            let mrn: Mrn = Faker.fake_with_rng(&mut rng);
+            let components = mrn.components();
+            assert!(all_countries()
+                .iter()
+                .any(|country| country.code == components.country_code));
+            assert!(components.procedure.is_some());
+        }
+
+        let procedures: alloc::vec::Vec<Procedure> =
+            (0..200).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+        assert!(PROCEDURE_CHARACTERS.iter().all(|c| procedures
+            .iter()
+            .any(|procedure| procecure_category_to_char(*procedure) == *c as char)));
+
+        let mut same = StdRng::seed_from_u64(7);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(
+            Faker.fake_with_rng::<Mrn, _>(&mut rng),
+            Faker.fake_with_rng::<Mrn, _>(&mut same)
+        );
+    }
+}
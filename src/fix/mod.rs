@@ -0,0 +1,143 @@
+//! Fixing of the check digits of files of MRNs, e.g. legacy fixture files
+
+use crate::parser::FixArgs;
+use anyhow::{bail, Context, Result};
+use mrn_generator::{validate_mrn, MrnGeneratorError};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Contents of a file of MRNs with their check digits fixed
+#[derive(Debug, Default, PartialEq)]
+struct Fixed {
+    contents: String,
+    /// Line number, MRN and fixed MRN of each changed line
+    changes: Vec<(usize, String, String)>,
+    /// Line number and error of each MRN the check digit doesn't fix
+    unfixable: Vec<(usize, MrnGeneratorError)>,
+    mrns: usize,
+}
+
+/// Fixes the check digits of the MRNs of a file, one per line, keeping everything else as is
+fn fix_contents(contents: &str) -> Fixed {
+    let mut fixed = Fixed::default();
+    for (line, text) in contents.split_inclusive('\n').enumerate() {
+        let mrn = text.trim();
+        if mrn.is_empty() {
+            fixed.contents.push_str(text);
+            continue;
+        }
+        fixed.mrns += 1;
+        match validate_mrn(mrn) {
+            Err(MrnGeneratorError::InvalidCheckDigit { expected, .. }) => {
+                let start = text.find(mrn).expect("the MRN is part of its line");
+                let check_digit = start + mrn.len() - 1;
+                let fixed_mrn = format!("{}{expected}", &mrn[..mrn.len() - 1]);
+                fixed.contents.push_str(&text[..check_digit]);
+                fixed.contents.push(expected);
+                fixed.contents.push_str(&text[check_digit + 1..]);
+                fixed.changes.push((line + 1, mrn.to_string(), fixed_mrn));
+            }
+            result => {
+                if let Err(e) = result {
+                    fixed.unfixable.push((line + 1, e));
+                }
+                fixed.contents.push_str(text);
+            }
+        }
+    }
+    fixed
+}
+
+/// Diff of the changed lines of a file, with a hunk header per line
+fn diff(path: &Path, changes: &[(usize, String, String)]) -> String {
+    let mut diff = format!("--- {0}\n+++ {0}\n", path.display());
+    for (line, mrn, fixed) in changes {
+        writeln!(diff, "@@ -{line} +{line} @@\n-{mrn}\n+{fixed}").unwrap();
+    }
+    diff
+}
+
+/// Fixes the check digits of files of MRNs, printing the diff of the changes and rewriting the
+/// files with --in-place
+pub fn fix(args: FixArgs) -> Result<()> {
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let (mut mrns, mut changes, mut unfixable) = (0, 0, 0);
+    for path in &args.files {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        let fixed = fix_contents(&contents);
+        mrns += fixed.mrns;
+        unfixable += fixed.unfixable.len();
+        for (line, e) in &fixed.unfixable {
+            eprintln!("{}:{line}: {e}", path.display());
+        }
+        if fixed.changes.is_empty() {
+            continue;
+        }
+        changes += fixed.changes.len();
+        write!(out, "{}", diff(path, &fixed.changes))?;
+
+        if args.in_place {
+            if let Some(suffix) = &args.backup {
+                let mut backup = path.clone().into_os_string();
+                backup.push(suffix);
+                fs::copy(path, PathBuf::from(&backup)).with_context(|| {
+                    format!("could not back up {} to {backup:?}", path.display())
+                })?;
+            }
+            fs::write(path, &fixed.contents)
+                .with_context(|| format!("could not write {}", path.display()))?;
+        }
+    }
+    out.flush()?;
+
+    let verb = match args.in_place {
+        true => "fixed",
+        false => "would fix",
+    };
+    eprintln!("{verb} {changes} of {mrns} MRNs");
+    if unfixable > 0 {
+        bail!("{unfixable} of {mrns} MRNs have errors other than their check digit");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn fix_contents_test() {
+        let fixed = fix_contents("26DK0047002M8N41B6\n  22ITZXBZYUTJFLJXK7 \r\n\n26DK\n");
+        assert_eq!(
+            "26DK0047002M8N41B6\n  22ITZXBZYUTJFLJXK6 \r\n\n26DK\n",
+            fixed.contents
+        );
+        assert_eq!(
+            vec![(
+                2,
+                "22ITZXBZYUTJFLJXK7".to_string(),
+                "22ITZXBZYUTJFLJXK6".to_string()
+            )],
+            fixed.changes
+        );
+        assert_eq!(
+            vec![(4, MrnGeneratorError::MrnLength("26DK".to_string()))],
+            fixed.unfixable
+        );
+        assert_eq!(3, fixed.mrns);
+        assert_eq!(
+            "--- a.txt\n+++ a.txt\n@@ -2 +2 @@\n-22ITZXBZYUTJFLJXK7\n+22ITZXBZYUTJFLJXK6\n",
+            diff(Path::new("a.txt"), &fixed.changes)
+        );
+
+        // Files without a final newline keep it that way
+        assert_eq!(
+            "22ITZXBZYUTJFLJXK6",
+            fix_contents("22ITZXBZYUTJFLJXK0").contents
+        );
+    }
+}
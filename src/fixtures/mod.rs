@@ -0,0 +1,111 @@
+//! Canonical example MRNs, the same for the same arguments in every version, to be checked into
+//! other repositories as golden test data
+
+use crate::parser::FixturesArgs;
+use anyhow::Result;
+use mrn_generator::{
+    all_procedures, generate_nth, procecure_category_to_char, procedure_name, Mrn, MrnConfig,
+    Procedure,
+};
+use std::io::{self, BufWriter, Write};
+
+/// A fixture MRN with the country and procedure it was generated for
+struct Fixture<'a> {
+    mrn: Mrn,
+    country: &'a str,
+    procedure: Procedure,
+}
+
+/// One MRN of every procedure for each country, in order of the countries and procedure
+/// characters, the index of each MRN in the seeded dataset being its position
+fn fixtures<'a>(args: &'a FixturesArgs) -> Result<Vec<Fixture<'a>>> {
+    let combinations = args.country_code.iter().flat_map(|country| {
+        all_procedures()
+            .iter()
+            .map(move |&procedure| (country.as_str(), procedure))
+    });
+    combinations
+        .zip(0..)
+        .map(|((country, procedure), index)| {
+            let config = MrnConfig {
+                country_code: country,
+                procedure: Some(procedure),
+                declaration_office: None,
+                year: Some(args.year),
+            };
+            Ok(Fixture {
+                mrn: generate_nth(args.seed, index, &config)?,
+                country,
+                procedure,
+            })
+        })
+        .collect()
+}
+
+/// Prints the fixture MRNs to standard output
+pub fn print_fixtures(args: FixturesArgs) -> Result<()> {
+    write_fixtures(&args, io::stdout().lock())
+}
+
+fn write_fixtures(args: &FixturesArgs, out: impl Write) -> Result<()> {
+    let mut out = BufWriter::new(out);
+    if args.csv {
+        writeln!(out, "mrn,country,procedure,procedure_character")?;
+    }
+    for fixture in fixtures(args)? {
+        match args.csv {
+            true => writeln!(
+                out,
+                "{},{},{},{}",
+                fixture.mrn,
+                fixture.country,
+                procedure_name(fixture.procedure),
+                procecure_category_to_char(fixture.procedure)
+            )?,
+            false => writeln!(out, "{}", fixture.mrn)?,
+        }
+    }
+    Ok(out.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parser::{Args, Command};
+    use clap::Parser;
+
+    fn fixture_lines(argv: &[&str]) -> Vec<String> {
+        let argv = ["mrn-generator", "fixtures"].iter().chain(argv);
+        let args = match Args::parse_from(argv).command {
+            Some(Command::Fixtures(args)) => args,
+            _ => unreachable!(),
+        };
+        let mut out = Vec::new();
+        write_fixtures(&args, &mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn fixtures_test() {
+        let lines = fixture_lines(&[]);
+        assert_eq!(5 * all_procedures().len(), lines.len());
+        // Golden values, which must never change for the same arguments
+        assert_eq!("24DKBP8MABADFD3XA1", lines[0]);
+        assert_eq!(
+            lines,
+            fixture_lines(&["-c", "DK,DE,FR,IT,NL", "--seed", "0"])
+        );
+
+        let csv = fixture_lines(&["-c", "Greece", "--year", "26", "--csv"]);
+        assert_eq!("mrn,country,procedure,procedure_character", csv[0]);
+        assert_eq!(1 + all_procedures().len(), csv.len());
+        assert!(csv[1].starts_with("26GR"));
+        assert!(csv[1].ends_with(",GR,export_only,A"));
+        assert_ne!(lines, fixture_lines(&["--seed", "1"]));
+    }
+}
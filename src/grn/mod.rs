@@ -0,0 +1,167 @@
+//! Guarantee Reference Numbers (GRN), identifying guarantees lodged for transit
+
+#[cfg(feature = "generate")]
+use crate::{capitalize, current_year, replace_last_char, source::IdSource, source::RandomSource};
+use crate::{check_character_value, is_mrn_valid, MrnGeneratorError};
+#[cfg(feature = "generate")]
+use alloc::format;
+#[cfg(feature = "generate")]
+use alloc::string::String;
+use alloc::string::ToString;
+
+/// Length of a GRN up to and including its check character
+pub const GRN_LENGTH: usize = 17;
+
+/// Maximum length of the optional individual guarantee voucher identifier following the check character
+pub const VOUCHER_MAX_LENGTH: usize = 7;
+
+/// Length of the access code accompanying a GRN
+pub const ACCESS_CODE_LENGTH: usize = 4;
+
+/// Returns a valid GRN given the country code of the guarantee office
+///
+/// The voucher identifier is only used for individual guarantees by voucher.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::grn::{generate_random_grn, is_grn_valid};
+///
+/// let grn = generate_random_grn("DK", None).unwrap();
+/// assert_eq!(None, is_grn_valid(&grn).unwrap());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_random_grn(
+    country_code: &str,
+    voucher: Option<&str>,
+) -> Result<String, MrnGeneratorError> {
+    use MrnGeneratorError::*;
+
+    if country_code.len() != 2 {
+        return Err(CountryCodeLength(country_code.to_string()));
+    }
+
+    let identifier = RandomSource::default().next_segment(12)?;
+    let grn = format!(
+        "{}{}{}0",
+        current_year(),
+        capitalize(country_code),
+        identifier
+    );
+    let grn = match is_grn_valid(&grn)? {
+        Some(check_character) => replace_last_char(&grn, check_character),
+        None => grn,
+    };
+
+    match voucher {
+        Some(voucher) => {
+            let grn = format!("{grn}{}", capitalize(voucher));
+            is_grn_valid(&grn)?;
+            Ok(grn)
+        }
+        None => Ok(grn),
+    }
+}
+
+/// Returns None if GRN is valid, and correct check character if it's invalid
+///
+/// The check character is computed the same way as for MRNs, over the year, country code and
+/// unique identifier. Any voucher identifier after it is only checked for its characters.
+pub fn is_grn_valid(grn: &str) -> Result<Option<char>, MrnGeneratorError> {
+    use MrnGeneratorError::*;
+
+    if !(GRN_LENGTH..=GRN_LENGTH + VOUCHER_MAX_LENGTH).contains(&grn.len()) || !grn.is_ascii() {
+        return Err(GrnLength(grn.to_string()));
+    }
+
+    let (year, rest) = grn.split_at(2);
+    let country_code = &rest[..2];
+    if !year.chars().all(|c| c.is_ascii_digit())
+        || !country_code.chars().all(|c| c.is_ascii_uppercase())
+    {
+        return Err(GrnPrefix(grn.to_string()));
+    }
+
+    let check_character = grn.as_bytes()[GRN_LENGTH - 1];
+    if !(check_character.is_ascii_digit() || check_character.is_ascii_uppercase()) {
+        return Err(GrnCheckCharacter(grn.to_string()));
+    }
+
+    let voucher = &grn[GRN_LENGTH..];
+    for c in voucher.chars() {
+        check_character_value(c)?;
+    }
+
+    is_mrn_valid(&grn[..GRN_LENGTH])
+}
+
+/// Returns a random access code for a GRN
+#[cfg(feature = "generate")]
+pub fn generate_access_code() -> String {
+    RandomSource::default()
+        .next_segment(ACCESS_CODE_LENGTH)
+        .unwrap()
+}
+
+/// Returns true if the access code has the right length and only uppercase alphanumerics
+pub fn is_access_code_valid(access_code: &str) -> bool {
+    access_code.len() == ACCESS_CODE_LENGTH
+        && access_code
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_random_grn_test() {
+        let grn = generate_random_grn("dk", None).unwrap();
+
+        assert_eq!(GRN_LENGTH, grn.len());
+        assert_eq!(current_year(), &grn[..2]);
+        assert_eq!("DK", &grn[2..4]);
+        assert_eq!(None, is_grn_valid(&grn).unwrap());
+
+        let grn = generate_random_grn("DK", Some("V12")).unwrap();
+        assert_eq!(GRN_LENGTH + 3, grn.len());
+        assert!(grn.ends_with("V12"));
+        assert_eq!(None, is_grn_valid(&grn).unwrap());
+    }
+
+    #[test]
+    fn is_grn_valid_test() {
+        assert_eq!(None, is_grn_valid("22ITZXBZYUTJFLJX4").unwrap());
+        assert_eq!(None, is_grn_valid("24DK0000000000012V12").unwrap());
+        assert_eq!(Some('4'), is_grn_valid("22ITZXBZYUTJFLJX6").unwrap());
+        assert_eq!(
+            Err(MrnGeneratorError::GrnLength("22IT".to_string())),
+            is_grn_valid("22IT")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::GrnPrefix(
+                "2XITZXBZYUTJFLJX4".to_string()
+            )),
+            is_grn_valid("2XITZXBZYUTJFLJX4")
+        );
+        for invalid in [
+            "22ITZXBZYUTJFLJX-",
+            "22ITZXBZYUTJFLJX ",
+            "22ITZXBZYUTJFLJXa",
+        ] {
+            assert_eq!(
+                Err(MrnGeneratorError::GrnCheckCharacter(invalid.to_string())),
+                is_grn_valid(invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn is_access_code_valid_test() {
+        assert!(is_access_code_valid("AB12"));
+        assert!(!is_access_code_valid("AB1"));
+        assert!(!is_access_code_valid("ab12"));
+    }
+}
@@ -0,0 +1,479 @@
+//! Localized error messages, descriptions and command line texts
+//!
+//! English is built into the crate, the other languages are read from the Fluent files in
+//! `locales/` and fall back to English for any message they don't translate.
+
+use crate::validation::Finding;
+use crate::{
+    category_description, procecure_category_to_char, procedure_description, MrnGeneratorError,
+    Procedure,
+};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::env;
+use std::str::FromStr;
+
+/// Languages of the messages
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Lang {
+    #[default]
+    En,
+    De,
+    Fr,
+    El,
+}
+
+impl Lang {
+    /// ISO 639-1 code of the language
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::De => "de",
+            Lang::Fr => "fr",
+            Lang::El => "el",
+        }
+    }
+
+    /// Language of a POSIX locale (`de_DE.UTF-8`) or BCP 47 language tag (`fr-BE`)
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::i18n::Lang;
+    ///
+    /// assert_eq!(Some(Lang::De), Lang::from_locale("de_DE.UTF-8"));
+    /// assert_eq!(Some(Lang::El), Lang::from_locale("el-GR"));
+    /// assert_eq!(None, Lang::from_locale("C"));
+    /// ```
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .and_then(|lang| lang.parse().ok())
+    }
+
+    /// Language of the environment, taken from `LC_ALL`, `LC_MESSAGES` or `LANG`
+    pub fn from_env() -> Option<Lang> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_locale(&locale))
+    }
+
+    fn resource(&self) -> &'static str {
+        match self {
+            Lang::En => include_str!("../../locales/en.ftl"),
+            Lang::De => include_str!("../../locales/de.ftl"),
+            Lang::Fr => include_str!("../../locales/fr.ftl"),
+            Lang::El => include_str!("../../locales/el.ftl"),
+        }
+    }
+}
+
+impl FromStr for Lang {
+    type Err = MrnGeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "en" => Ok(Lang::En),
+            "de" => Ok(Lang::De),
+            "fr" => Ok(Lang::Fr),
+            "el" => Ok(Lang::El),
+            _ => Err(MrnGeneratorError::UnsupportedLanguage(s.to_string())),
+        }
+    }
+}
+
+/// Formats messages in one language, can be shared between threads
+pub struct Localizer {
+    lang: Lang,
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Localizer {
+    pub fn new(lang: Lang) -> Self {
+        let resource = FluentResource::try_new(lang.resource().to_string())
+            .unwrap_or_else(|(_, errors)| panic!("invalid {} locale: {errors:?}", lang.code()));
+        let mut bundle = FluentBundle::new_concurrent(vec![lang.code().parse().unwrap()]);
+        // Unicode isolation marks around arguments would show up in terminals
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .unwrap_or_else(|errors| panic!("invalid {} locale: {errors:?}", lang.code()));
+        Localizer { lang, bundle }
+    }
+
+    pub fn lang(&self) -> Lang {
+        self.lang
+    }
+
+    /// Formats a message, None if the language doesn't have it
+    pub fn message(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let pattern = self.bundle.get_message(id)?.value()?;
+        let mut errors = vec![];
+        Some(
+            self.bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned(),
+        )
+    }
+
+    /// Describes an error
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::i18n::{Lang, Localizer};
+    /// use mrn_generator::MrnGeneratorError;
+    ///
+    /// let localizer = Localizer::new(Lang::De);
+    /// assert_eq!(
+    ///     "X9 ist keine gültige Verfahrenskategorie",
+    ///     localizer.error(&MrnGeneratorError::InvalidProcedureCategory {
+    ///         value: "X9".to_string(),
+    ///         suggestion: None
+    ///     })
+    /// );
+    /// ```
+    pub fn error(&self, error: &MrnGeneratorError) -> String {
+        let (id, args) = self.error_message(error);
+        self.message(id, Some(&args))
+            .unwrap_or_else(|| error.to_string())
+    }
+
+    /// Describes a semantic finding of a validation
+    pub fn finding(&self, finding: &Finding) -> String {
+        let mut args = FluentArgs::new();
+        let id = match finding {
+            Finding::FutureYear { year, current_year } => {
+                args.set("year", format!("{year:02}"));
+                args.set("current_year", format!("{current_year:02}"));
+                "warning-future-year"
+            }
+            Finding::OfficeCountryMismatch {
+                country_code,
+                office_prefix,
+            } => {
+                args.set("country_code", country_code.clone());
+                args.set("office_prefix", office_prefix.clone());
+                "warning-office-country-mismatch"
+            }
+            Finding::UnknownCountry {
+                country_code,
+                suggestion,
+            } => {
+                args.set("country_code", country_code.clone());
+                match suggestion {
+                    Some(suggestion) => {
+                        args.set("suggestion", suggestion.clone());
+                        "warning-unknown-country-suggestion"
+                    }
+                    None => "warning-unknown-country",
+                }
+            }
+            Finding::OfficeNotNumeric {
+                country_code,
+                office_number,
+            } => {
+                args.set("country_code", country_code.clone());
+                args.set("office_number", office_number.clone());
+                "warning-office-not-numeric"
+            }
+            Finding::DeclarationYearMismatch {
+                year,
+                declaration_date,
+            } => {
+                args.set("year", format!("{year:02}"));
+                args.set("declaration_date", declaration_date.to_string());
+                "warning-declaration-year-mismatch"
+            }
+        };
+        self.message(id, Some(&args))
+            .unwrap_or_else(|| finding.to_string())
+    }
+
+    /// Describes a procedure in words
+    pub fn procedure_description(&self, procedure: Procedure) -> String {
+        let id = format!("procedure-{}", procecure_category_to_char(procedure));
+        self.message(&id, None)
+            .unwrap_or_else(|| procedure_description(procedure).to_string())
+    }
+
+    /// Describes a declaration category, None if it is unknown
+    pub fn category_description(&self, category: &str) -> Option<String> {
+        let description = category_description(category)?;
+        Some(
+            self.message(&format!("category-{category}"), None)
+                .unwrap_or_else(|| description.to_string()),
+        )
+    }
+
+    /// Message id and arguments of an error
+    fn error_message(&self, error: &MrnGeneratorError) -> (&'static str, FluentArgs<'static>) {
+        use MrnGeneratorError::*;
+
+        let mut args = FluentArgs::new();
+        let id = match error {
+            CountryCodeLength(value) => {
+                args.set("value", value.clone());
+                "error-country-code-length"
+            }
+            InvalidProcedureCategory { value, suggestion } => {
+                args.set("value", value.clone());
+                match suggestion {
+                    Some(suggestion) => {
+                        args.set("suggestion", suggestion.clone());
+                        "error-invalid-procedure-category-suggestion"
+                    }
+                    None => "error-invalid-procedure-category",
+                }
+            }
+            InvalidProcedureCategoryCombination {
+                procedure_category,
+                combination,
+            } => {
+                args.set("procedure_category", procedure_category.clone());
+                args.set("combination", combination.clone());
+                "error-invalid-procedure-category-combination"
+            }
+            InvalidCombinedCategory {
+                procedure_category,
+                combined,
+                allowed,
+            } => {
+                args.set("procedure_category", procedure_category.clone());
+                args.set("combined", combined.clone());
+                match allowed.as_slice() {
+                    [] => "error-invalid-combined-category",
+                    allowed => {
+                        args.set("allowed", allowed.join(", "));
+                        "error-invalid-combined-category-allowed"
+                    }
+                }
+            }
+            CombinedWithoutCategory(value) => {
+                args.set("value", value.clone());
+                "error-combined-without-category"
+            }
+            NotAlphanumeric(c) => {
+                args.set("value", c.to_string());
+                "error-not-alphanumeric"
+            }
+            InvalidCharacter { position, found } => {
+                args.set("value", found.to_string());
+                args.set("position", *position);
+                "error-invalid-character"
+            }
+            DeclarationOfficeLength(value) => {
+                args.set("value", value.clone());
+                "error-declaration-office-length"
+            }
+            SourceExhausted => "error-source-exhausted",
+            InvalidNamespace(value) => {
+                args.set("value", value.to_string());
+                "error-invalid-namespace"
+            }
+            GrnLength(value) => {
+                args.set("value", value.clone());
+                "error-grn-length"
+            }
+            GrnPrefix(value) => {
+                args.set("value", value.clone());
+                "error-grn-prefix"
+            }
+            GrnCheckCharacter(value) => {
+                args.set("value", value.clone());
+                "error-grn-check-character"
+            }
+            LrnLength(length) => {
+                args.set("value", length.to_string());
+                "error-lrn-length"
+            }
+            InvalidLrnPrefix(value) => {
+                args.set("value", value.clone());
+                "error-invalid-lrn-prefix"
+            }
+            InvalidEori(value) => {
+                args.set("value", value.clone());
+                "error-invalid-eori"
+            }
+            InvalidOwnerCode(value) => {
+                args.set("value", value.clone());
+                "error-invalid-owner-code"
+            }
+            ContainerNumberFormat(value) => {
+                args.set("value", value.clone());
+                "error-container-number-format"
+            }
+            InvalidDucr(value) => {
+                args.set("value", value.clone());
+                "error-invalid-ducr"
+            }
+            InvalidMucr(value) => {
+                args.set("value", value.clone());
+                "error-invalid-mucr"
+            }
+            MrnLength(value) => {
+                args.set("value", value.clone());
+                "error-mrn-length"
+            }
+            InvalidYear(value) => {
+                args.set("value", value.clone());
+                "error-invalid-year"
+            }
+            InvalidCountryCode(value) => {
+                args.set("value", value.clone());
+                "error-invalid-country-code"
+            }
+            InvalidCheckDigit { mrn, expected } => {
+                args.set("mrn", mrn.clone());
+                args.set("expected", expected.to_string());
+                "error-invalid-check-digit"
+            }
+            NotAnEnsMrn {
+                mrn,
+                procedure_char,
+            } => {
+                args.set("mrn", mrn.clone());
+                args.set("procedure_char", procedure_char.to_string());
+                "error-not-an-ens-mrn"
+            }
+            NotAnEnsProcedure(procedure) => {
+                args.set("procedure", self.procedure_description(*procedure));
+                "error-not-an-ens-procedure"
+            }
+            NotATransitMrn {
+                mrn,
+                procedure_char,
+            } => {
+                args.set("mrn", mrn.clone());
+                args.set("procedure_char", procedure_char.to_string());
+                "error-not-a-transit-mrn"
+            }
+            NotATransitProcedure(procedure) => {
+                args.set("procedure", self.procedure_description(*procedure));
+                "error-not-a-transit-procedure"
+            }
+            NotACdsMrn {
+                mrn,
+                procedure_char,
+            } => {
+                args.set("mrn", mrn.clone());
+                args.set("procedure_char", procedure_char.to_string());
+                "error-not-a-cds-mrn"
+            }
+            NotACdsProcedure(procedure) => {
+                args.set("procedure", self.procedure_description(*procedure));
+                "error-not-a-cds-procedure"
+            }
+            NotACtcCountry(value) => {
+                args.set("value", value.clone());
+                "error-not-a-ctc-country"
+            }
+            UnexpectedCountry { found, expected } => {
+                args.set("found", found.clone());
+                args.set("expected", expected.clone());
+                "error-unexpected-country"
+            }
+            IssueYear(year) => {
+                args.set("value", year.to_string());
+                "error-issue-year"
+            }
+            InvalidRules(value) => {
+                args.set("value", value.clone());
+                "error-invalid-rules"
+            }
+            InvalidCheck(value) => {
+                args.set("value", value.clone());
+                "error-invalid-check"
+            }
+            CheckFailed { mrn, check } => {
+                args.set("mrn", mrn.clone());
+                args.set("check", check.clone());
+                "error-check-failed"
+            }
+            UnsupportedLanguage(value) => {
+                args.set("value", value.clone());
+                "error-unsupported-language"
+            }
+            OnlineVerification(value) => {
+                args.set("value", value.clone());
+                "error-online-verification"
+            }
+            UnknownCountry { value, suggestion } => {
+                args.set("value", value.clone());
+                match suggestion {
+                    Some(suggestion) => {
+                        args.set("suggestion", suggestion.clone());
+                        "error-unknown-country-suggestion"
+                    }
+                    None => "error-unknown-country",
+                }
+            }
+            UnknownProcedure(value) => {
+                args.set("value", value.clone());
+                "error-unknown-procedure"
+            }
+        };
+        (id, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::all_categories;
+
+    const LANGS: [Lang; 4] = [Lang::En, Lang::De, Lang::Fr, Lang::El];
+
+    #[test]
+    fn locales_complete_test() {
+        for lang in &LANGS[1..] {
+            let localizer = Localizer::new(*lang);
+            assert!(all_categories().iter().all(|c| localizer
+                .message(&format!("category-{}", c.code), None)
+                .is_some()));
+            assert!(crate::all_mappings().iter().all(|m| localizer
+                .message(
+                    &format!("procedure-{}", procecure_category_to_char(m.procedure)),
+                    None
+                )
+                .is_some()));
+            for id in [
+                "cli-error",
+                "about",
+                "help-country_code",
+                "error-mrn-length",
+            ] {
+                assert!(localizer.message(id, None).is_some(), "{id} in {lang:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn fallback_test() {
+        let error = MrnGeneratorError::InvalidCheckDigit {
+            mrn: "22ITZXBZYUTJFLJXK5".to_string(),
+            expected: '6',
+        };
+        assert_eq!(error.to_string(), Localizer::new(Lang::En).error(&error));
+        assert_eq!(
+            "22ITZXBZYUTJFLJXK5 n'est pas un MRN valide, son chiffre de contrôle doit être 6",
+            Localizer::new(Lang::Fr).error(&error)
+        );
+        assert_eq!(
+            Some("Release for free circulation".to_string()),
+            Localizer::new(Lang::En).category_description("H1")
+        );
+        assert_eq!(None, Localizer::new(Lang::El).category_description("Z9"));
+    }
+
+    #[test]
+    fn lang_from_str_test() {
+        assert_eq!(Ok(Lang::Fr), "FR".parse());
+        assert_eq!(
+            Err(MrnGeneratorError::UnsupportedLanguage("gr".to_string())),
+            "gr".parse::<Lang>()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! MRNs of the input of `validate`, one per line, in a CSV column or in a JSON array, the format
+//! being detected from the start of the input unless given
+
+use crate::lines::BoundedLines;
+use crate::parser::InputFormat;
+use anyhow::{bail, Context, Result};
+use csv::StringRecord;
+use mrn_generator::{layout, validate_mrn};
+use serde_json::Value;
+use std::io::{self, BufRead};
+
+/// Length lines are cut to, far over that of MRNs, so that inputs without line breaks are read in
+/// constant memory as well
+const MAX_LINE_LENGTH: usize = 1 << 10;
+
+/// Number of CSV rows the MRN column is chosen from
+const SAMPLE_ROWS: usize = 100;
+
+/// MRNs read from an input, in order
+pub type Mrns = Box<dyn Iterator<Item = io::Result<String>>>;
+
+/// Reads the MRNs of an input. Plain lines and CSV are streamed, a JSON array is read at once.
+pub fn read_mrns<R: BufRead + 'static>(mut reader: R, format: InputFormat) -> Result<Mrns> {
+    let format = match format {
+        InputFormat::Auto => detect(&mut reader)?,
+        format => format,
+    };
+    match format {
+        InputFormat::Plain | InputFormat::Auto => {
+            Ok(Box::new(BoundedLines::new(reader, MAX_LINE_LENGTH)))
+        }
+        InputFormat::Csv => csv_mrns(reader),
+        InputFormat::Json => json_mrns(reader),
+    }
+}
+
+/// Detects the format from the start of the input: a JSON array starts with `[`, and the first
+/// line of CSV has delimiters, which MRNs never have
+fn detect(reader: &mut impl BufRead) -> io::Result<InputFormat> {
+    let start = reader.fill_buf()?;
+    let start = start.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(start);
+    let first_line = start.split(|&b| b == b'\n').next().unwrap_or_default();
+    Ok(match start.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'[') => InputFormat::Json,
+        _ if delimiter(first_line).is_some() => InputFormat::Csv,
+        _ => InputFormat::Plain,
+    })
+}
+
+/// Most frequent CSV delimiter of a line, if it has any
+fn delimiter(line: &[u8]) -> Option<u8> {
+    [b',', b';', b'\t']
+        .into_iter()
+        .map(|delimiter| (line.iter().filter(|&&b| b == delimiter).count(), delimiter))
+        .filter(|(count, _)| *count > 0)
+        .max_by_key(|(count, _)| *count)
+        .map(|(_, delimiter)| delimiter)
+}
+
+/// How much a value looks like an MRN: 2 if it is a valid MRN, 1 if it only has the length and
+/// characters of one
+fn mrn_likeness(value: &str) -> usize {
+    let value = value.trim();
+    if validate_mrn(value).is_ok() {
+        2
+    } else if value.len() == layout::LENGTH && value.chars().all(|c| c.is_ascii_alphanumeric()) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Index of the column of rows whose values look the most like MRNs, the first one of equally
+/// likely columns, None if no value looks like an MRN
+fn mrn_column<'a, I>(rows: impl Iterator<Item = I>) -> Option<usize>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut scores: Vec<usize> = Vec::new();
+    for row in rows {
+        for (column, value) in row.into_iter().enumerate() {
+            if scores.len() <= column {
+                scores.resize(column + 1, 0);
+            }
+            scores[column] += mrn_likeness(value);
+        }
+    }
+    let best = scores.iter().copied().max().filter(|&score| score > 0)?;
+    scores.iter().position(|&score| score == best)
+}
+
+/// MRNs of the most MRN-like column of CSV, skipping its first row if none of its values look
+/// like an MRN, as it is then a header
+fn csv_mrns<R: BufRead + 'static>(mut reader: R) -> Result<Mrns> {
+    let first_line = reader.fill_buf()?;
+    let delimiter = delimiter(first_line.split(|&b| b == b'\n').next().unwrap_or_default());
+    let mut records = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter.unwrap_or(b','))
+        .from_reader(reader)
+        .into_records();
+    let mut sample = records
+        .by_ref()
+        .take(SAMPLE_ROWS)
+        .collect::<Result<Vec<StringRecord>, _>>()
+        .context("could not read the CSV input")?;
+
+    let header = sample
+        .first()
+        .is_some_and(|row| row.iter().all(|value| mrn_likeness(value) == 0));
+    if header {
+        sample.remove(0);
+    }
+    let Some(column) = mrn_column(sample.iter()) else {
+        bail!("no column of the CSV input looks like MRNs");
+    };
+
+    let mrn = move |row: &StringRecord| row.get(column).unwrap_or_default().to_string();
+    let rest = records.map(move |row| {
+        row.map(|row| mrn(&row))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    });
+    Ok(Box::new(
+        sample.into_iter().map(move |row| Ok(mrn(&row))).chain(rest),
+    ))
+}
+
+/// MRNs of a JSON array of MRNs, or of objects with them in their most MRN-like field
+fn json_mrns(reader: impl BufRead) -> Result<Mrns> {
+    let values: Vec<Value> =
+        serde_json::from_reader(reader).context("could not read the JSON input")?;
+    if values.iter().all(Value::is_string) {
+        return Ok(Box::new(values.into_iter().map(|value| match value {
+            Value::String(mrn) => Ok(mrn),
+            _ => unreachable!(),
+        })));
+    }
+    if !values.iter().all(Value::is_object) {
+        bail!("the JSON input should be an array of MRNs or of objects with an MRN field");
+    }
+
+    // Fields of the objects, in order of first appearance
+    let mut fields: Vec<&str> = Vec::new();
+    for object in values.iter().filter_map(Value::as_object) {
+        for field in object.keys() {
+            if !fields.contains(&field.as_str()) {
+                fields.push(field);
+            }
+        }
+    }
+    fn text(value: Option<&Value>) -> &str {
+        value.and_then(Value::as_str).unwrap_or_default()
+    }
+    let column = mrn_column(values.iter().map(|object| {
+        fields
+            .iter()
+            .map(|field| text(object.get(field)))
+            .collect::<Vec<&str>>()
+    }));
+    let Some(field) = column.map(|column| fields[column].to_string()) else {
+        bail!("no field of the JSON input looks like MRNs");
+    };
+    let mrns: Vec<String> = values
+        .iter()
+        .map(|object| text(object.get(&field)).to_string())
+        .collect();
+    Ok(Box::new(mrns.into_iter().map(Ok)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn mrns(input: &'static str, format: InputFormat) -> Result<Vec<String>> {
+        Ok(read_mrns(input.as_bytes(), format)?.collect::<io::Result<_>>()?)
+    }
+
+    #[test]
+    fn read_mrns_test() {
+        use InputFormat::*;
+
+        let expected = vec!["22ITZXBZYUTJFLJXK6", "26DK0047002M8N41B7"];
+        assert_eq!(
+            expected,
+            mrns("22ITZXBZYUTJFLJXK6\n26DK0047002M8N41B7\n", Auto).unwrap()
+        );
+        // The column with valid MRNs wins over one with the same length
+        assert_eq!(
+            expected,
+            mrns(
+                "LRN,MRN,Office\nABCDEFGHIJKLMNOPQR,22ITZXBZYUTJFLJXK6,IT\n\
+                 ABCDEFGHIJKLMNOPQS,26DK0047002M8N41B7,DK\n",
+                Auto
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            expected,
+            mrns("1;22ITZXBZYUTJFLJXK6\n2;26DK0047002M8N41B7", Csv).unwrap()
+        );
+        assert_eq!(
+            expected,
+            mrns(r#" ["22ITZXBZYUTJFLJXK6", "26DK0047002M8N41B7"]"#, Auto).unwrap()
+        );
+        assert_eq!(
+            expected,
+            mrns(
+                r#"[{"id": 1, "mrn": "22ITZXBZYUTJFLJXK6"}, {"mrn": "26DK0047002M8N41B7"}]"#,
+                Auto
+            )
+            .unwrap()
+        );
+
+        // An override reads the input as it is
+        assert_eq!(vec!["a,b"], mrns("a,b\n", Plain).unwrap());
+        assert!(mrns("a,b\nc,d\n", Auto).is_err());
+        assert!(mrns("[1, 2]", Auto).is_err());
+    }
+}
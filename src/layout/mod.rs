@@ -0,0 +1,39 @@
+//! Positions of the segments of an MRN, as byte ranges of the 18 character string
+//!
+//! ## Example
+//! ```
+//! use mrn_generator::layout;
+//!
+//! let mrn = "24DK004700ABCDEFA3";
+//! assert_eq!(layout::LENGTH, mrn.len());
+//! assert_eq!("DK", &mrn[layout::COUNTRY_CODE]);
+//! assert_eq!("004700", &mrn[layout::OFFICE_NUMBER]);
+//! assert_eq!("A", &mrn[layout::PROCEDURE]);
+//! ```
+
+use core::ops::Range;
+
+/// Length of an MRN
+pub const LENGTH: usize = 18;
+
+/// Last two digits of the issue year
+pub const YEAR: Range<usize> = 0..2;
+
+/// Country code of the declaring member state
+pub const COUNTRY_CODE: Range<usize> = 2..4;
+
+/// Unique part of the MRN, which may start with the declaration office
+pub const SERIAL: Range<usize> = 4..16;
+
+/// Declaration office, when the serial starts with an office number
+pub const OFFICE_NUMBER: Range<usize> = 4..10;
+
+/// Declaration office, when the serial starts with an office reference including the country
+/// code, e.g. `DK004700`
+pub const OFFICE_REFERENCE: Range<usize> = 4..12;
+
+/// Procedure character, which belongs to the serial for MRNs without a procedure
+pub const PROCEDURE: Range<usize> = 16..17;
+
+/// Check digit
+pub const CHECK_DIGIT: Range<usize> = 17..18;
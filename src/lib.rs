@@ -1,67 +1,426 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "generate")]
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "generate")]
 use chrono::{Datelike, Utc};
-use rand::{distributions::Alphanumeric, prelude::Distribution};
+use core::fmt;
+#[cfg(feature = "generate")]
+use source::RandomSource;
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq)]
+pub use mask::matches;
+pub use mrn::Mrn;
+pub use office::DeclarationOffice;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod batch;
+pub mod cds;
+pub mod checks;
+#[cfg(feature = "col")]
+pub mod col;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod container;
+pub mod country;
+pub mod ens;
+pub mod eori;
+#[cfg(feature = "faker")]
+pub mod faker;
+pub mod grn;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+pub mod layout;
+pub mod lrn;
+pub mod mask;
+pub mod mrn;
+pub mod office;
+#[cfg(feature = "online")]
+pub mod online;
+#[cfg(feature = "serde")]
+pub mod procedure_code;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "rules")]
+pub mod rules;
+#[cfg(feature = "std")]
+pub mod sink;
+pub mod source;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transit;
+pub mod ucr;
+pub mod validation;
+
+#[derive(Error, Debug, PartialEq, Clone)]
 pub enum MrnGeneratorError {
     #[error("{0} is not a valid country code, it should be exactly two characters (e.g. 'IT')")]
     CountryCodeLength(String),
-    #[error("{0} is not a valid procedure category")]
-    InvalidProcedureCategory(String),
+    #[error("{value} is not a valid procedure category{}", DidYouMean(suggestion))]
+    InvalidProcedureCategory {
+        value: String,
+        suggestion: Option<String>,
+    },
     #[error("{procedure_category}-{combination} is not a valid procedure category combination")]
     InvalidProcedureCategoryCombination {
         procedure_category: String,
         combination: String,
     },
+    #[error(
+        "{procedure_category} cannot be combined with {combined}{}",
+        CombinableWith(allowed)
+    )]
+    InvalidCombinedCategory {
+        procedure_category: String,
+        combined: String,
+        allowed: Vec<String>,
+    },
+    #[error("combined procedure category {0} was given without a procedure category")]
+    CombinedWithoutCategory(String),
     #[error("{0} is not an alphanumeric")]
     NotAlphanumeric(char),
+    #[error("{found} at byte {position} is not a digit or an uppercase letter")]
+    InvalidCharacter { position: usize, found: char },
+    #[error("{0} is not a valid declaration office, it should be a six character office number, optionally preceded by the country code")]
+    DeclarationOfficeLength(String),
+    #[error("the MRN source has no identifiers left")]
+    SourceExhausted,
+    #[error("{0} is not a valid namespace, it should be from 0 to 15")]
+    InvalidNamespace(u8),
+    #[error("{0} is not a valid GRN, it should be 17 to 24 characters long")]
+    GrnLength(String),
+    #[error("{0} is not a valid GRN, it should start with two year digits and a country code")]
+    GrnPrefix(String),
+    #[error(
+        "{0} is not a valid GRN, its check character should be a digit or an uppercase letter"
+    )]
+    GrnCheckCharacter(String),
+    #[error(
+        "{0} is not a valid LRN length, it should fit the prefix and be at most 22 characters"
+    )]
+    LrnLength(usize),
+    #[error("{0} is not a valid LRN prefix, it should only contain printable ASCII characters")]
+    InvalidLrnPrefix(String),
+    #[error(
+        "{0} is not a valid EORI, it should be a country code followed by up to 15 alphanumerics"
+    )]
+    InvalidEori(String),
+    #[error("{0} is not a valid container owner code, it should be exactly three letters")]
+    InvalidOwnerCode(String),
+    #[error("{0} is not a valid container number, it should be an owner code, U, J or Z and seven digits")]
+    ContainerNumberFormat(String),
+    #[error("{0} is not a valid DUCR, it should be a year digit, a country code and 6 to 32 reference characters")]
+    InvalidDucr(String),
+    #[error("{0} is not a valid MUCR")]
+    InvalidMucr(String),
+    #[error("{0} is not a valid MRN, it should be exactly 18 characters")]
+    MrnLength(String),
+    #[error("{0} is not a valid MRN, it should start with the last two digits of the year")]
+    InvalidYear(String),
+    #[error("{0} is not a valid MRN, its country code should be two capital letters")]
+    InvalidCountryCode(String),
+    #[error("{mrn} is not a valid MRN, its check digit should be {expected}")]
+    InvalidCheckDigit { mrn: String, expected: char },
+    #[error(
+        "{mrn} is not an ENS MRN, {procedure_char} is not an entry summary declaration procedure"
+    )]
+    NotAnEnsMrn { mrn: String, procedure_char: char },
+    #[error("{0:?} is not an entry summary declaration procedure")]
+    NotAnEnsProcedure(Procedure),
+    #[error("{mrn} is not a transit MRN, {procedure_char} is not a transit declaration procedure")]
+    NotATransitMrn { mrn: String, procedure_char: char },
+    #[error("{0:?} is not a transit declaration procedure")]
+    NotATransitProcedure(Procedure),
+    #[error(
+        "{mrn} is not a CDS MRN, {procedure_char} is not an import or export declaration procedure"
+    )]
+    NotACdsMrn { mrn: String, procedure_char: char },
+    #[error("{0:?} is not an import or export declaration procedure of CDS")]
+    NotACdsProcedure(Procedure),
+    #[error("{0} is not a contracting party of the Common Transit Convention")]
+    NotACtcCountry(String),
+    #[error("{found} is not the country of these MRNs, it should be {expected}")]
+    UnexpectedCountry { found: String, expected: String },
+    #[error("{0} is not a valid issue year, it should be the last two digits of a year")]
+    IssueYear(u8),
+    #[error("invalid procedure rules: {0}")]
+    InvalidRules(String),
+    #[error("invalid check: {0}")]
+    InvalidCheck(String),
+    #[error("{mrn} fails the check {check}")]
+    CheckFailed { mrn: String, check: String },
+    #[error("{0} is not a supported language, it should be one of en, de, fr or el")]
+    UnsupportedLanguage(String),
+    #[error("online verification failed: {0}")]
+    OnlineVerification(String),
+    #[error("{value} is not a known country{}", DidYouMean(suggestion))]
+    UnknownCountry {
+        value: String,
+        suggestion: Option<String>,
+    },
+    #[error("{0} is not a known procedure, it should be a procedure character such as A or a name such as export_only")]
+    UnknownProcedure(String),
+}
+
+/// Suffix of error messages listing the categories a procedure category can be combined with,
+/// empty if it can't be combined with any
+struct CombinableWith<'a>(&'a [String]);
+
+impl fmt::Display for CombinableWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            [] => Ok(()),
+            allowed => write!(f, ", it can only be combined with {}", allowed.join(", ")),
+        }
+    }
+}
+
+/// Suffix of error messages suggesting a close match, empty without a suggestion
+pub(crate) struct DidYouMean<'a>(&'a Option<String>);
+
+impl fmt::Display for DidYouMean<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(suggestion) => write!(f, ", did you mean {suggestion}?"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the candidate closest to the value by edit distance, ignoring case, if it is close
+/// enough to be a typo of it
+pub(crate) fn closest<'a>(
+    value: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (value.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(value, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, ignoring ASCII case
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().map(|c| c.to_ascii_lowercase()).collect();
+    // Distances from the prefix of `a` seen so far to every prefix of `b`
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().map(|c| c.to_ascii_lowercase()).enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Parameters of a generated MRN
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct MrnConfig<'a> {
+    pub country_code: &'a str,
+    pub procedure: Option<Procedure>,
+    pub declaration_office: Option<DeclarationOffice<'a>>,
+    /// Last two digits of the issue year, the current year if None
+    pub year: Option<u8>,
+}
+
+impl<'a> MrnConfig<'a> {
+    /// Builds the parameters of MRNs from user input, reporting every invalid input at once
+    /// rather than only the first one
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::{MrnConfig, Procedure};
+    ///
+    /// let config = MrnConfig::from_input("DK", Some("B1"), None, Some("004700")).unwrap();
+    /// assert_eq!(Some(Procedure::ExportOnly), config.procedure);
+    ///
+    /// let errors = MrnConfig::from_input("DNK", Some("H7"), None, Some("0047")).unwrap_err();
+    /// assert_eq!(3, errors.len());
+    /// ```
+    pub fn from_input(
+        country_code: &'a str,
+        procedure_category: Option<&str>,
+        combined: Option<&str>,
+        declaration_office: Option<&'a str>,
+    ) -> Result<Self, Vec<MrnGeneratorError>> {
+        let mut errors = Vec::new();
+        if country_code.len() != 2 {
+            errors.push(MrnGeneratorError::CountryCodeLength(
+                country_code.to_string(),
+            ));
+        }
+        let procedure =
+            match_optional_procedure(procedure_category, combined).unwrap_or_else(|e| {
+                errors.push(e);
+                None
+            });
+        let declaration_office = declaration_office
+            .map(DeclarationOffice::new)
+            .transpose()
+            .unwrap_or_else(|e| {
+                errors.push(e);
+                None
+            });
+
+        match errors.is_empty() {
+            true => Ok(MrnConfig {
+                country_code,
+                procedure,
+                declaration_office,
+                year: None,
+            }),
+            false => Err(errors),
+        }
+    }
+
+    /// Length of the free segment, everything between the office and the check digit minus the
+    /// procedure character
+    pub fn segment_len(&self) -> usize {
+        layout::CHECK_DIGIT.start
+            - layout::SERIAL.start
+            - self.procedure.is_some() as usize
+            - self
+                .declaration_office
+                .map_or(0, |office| office.as_str().len())
+    }
+
+    /// Number of distinct MRNs with these parameters, the possible values of their free segment
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::{MrnConfig, Procedure};
+    ///
+    /// let config = MrnConfig::from_input("DK", Some("B1"), None, Some("DK004700")).unwrap();
+    /// assert_eq!(36u128.pow(4), config.capacity());
+    /// ```
+    pub fn capacity(&self) -> u128 {
+        (source::ALPHABET.len() as u128).pow(self.segment_len() as u32)
+    }
 }
 
 /// Returns a valid MRN given a country code
-/// 
+///
 /// ## Example
 /// ```
-/// use mrn_generator::generate_random_mrn; 
-/// 
+/// use mrn_generator::generate_random_mrn;
+///
 /// let mrn = generate_random_mrn("DK", None, Some("004700")).unwrap();
 /// println!("{mrn}");
 /// ```
+#[cfg(feature = "generate")]
 pub fn generate_random_mrn(
     country_code: &str,
     procedure: Option<Procedure>,
     declaration_office: Option<&str>,
 ) -> Result<String, MrnGeneratorError> {
-    use MrnGeneratorError::*;
+    generate_mrn_with_source(
+        country_code,
+        procedure,
+        declaration_office,
+        RandomSource::default(),
+    )
+}
 
-    let curr_year: String = Utc::now().year().to_string().chars().skip(2).collect();
+/// Returns a valid MRN given a country code, taking the free segment from the given source
+///
+/// ## Example
+/// ```
+/// use mrn_generator::generate_mrn_with_source;
+/// use mrn_generator::source::SequentialSource;
+///
+/// let mut source = SequentialSource::starting_at(1);
+/// let mrn = generate_mrn_with_source("DK", None, Some("004700"), &mut source).unwrap();
+/// assert_eq!("0000001", &mrn[10..17]);
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_mrn_with_source(
+    country_code: &str,
+    procedure: Option<Procedure>,
+    declaration_office: Option<&str>,
+    source: impl source::IdSource,
+) -> Result<String, MrnGeneratorError> {
+    let declaration_office = declaration_office.map(DeclarationOffice::new).transpose()?;
+    let config = MrnConfig {
+        country_code,
+        procedure,
+        declaration_office,
+        year: None,
+    };
+    generate_mrn(&config, source)
+}
 
-    let random_str_len = 14 - declaration_office.map_or(0, |decoffice| decoffice.len());
+/// Returns a valid MRN with the given parameters, taking the free segment from the given source
+///
+/// ## Example
+/// ```
+/// use mrn_generator::source::RandomSource;
+/// use mrn_generator::{generate_mrn, DeclarationOffice, MrnConfig};
+///
+/// let config = MrnConfig {
+///     country_code: "DK",
+///     declaration_office: Some(DeclarationOffice::new("004700").unwrap()),
+///     year: Some(21),
+///     ..Default::default()
+/// };
+/// let mrn = generate_mrn(&config, RandomSource::default()).unwrap();
+/// assert!(mrn.starts_with("21DK004700"));
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_mrn(
+    config: &MrnConfig,
+    mut source: impl source::IdSource,
+) -> Result<String, MrnGeneratorError> {
+    use MrnGeneratorError::*;
 
-    let random_str: String = Alphanumeric
-        .sample_iter(&mut rand::thread_rng())
-        .take(random_str_len)
-        .map(|c| c.to_ascii_uppercase() as char)
-        .collect();
+    let MrnConfig {
+        country_code,
+        procedure,
+        declaration_office,
+        year,
+    } = *config;
 
     if country_code.len() != 2 {
         return Err(CountryCodeLength(country_code.to_string()));
     }
 
+    let curr_year = match year {
+        Some(year) if year > 99 => return Err(IssueYear(year)),
+        Some(year) => format!("{year:02}"),
+        None => current_year(),
+    };
+
+    let segment = source.next_segment(config.segment_len())?;
+    let declaration_office = declaration_office.map_or("", |office| office.as_str());
+
     let mut mrn = format!(
         "{}{}{}{}",
         curr_year,
         capitalize(country_code),
-        declaration_office.unwrap_or(""),
-        random_str
+        declaration_office,
+        segment
     );
 
     if let Some(procedure) = procedure {
-        let proctgr_char = procecure_category_to_char(procedure).to_string();
-
-        // Replace n-1 char with regime char
-        mrn.replace_range(16..17, &proctgr_char);
+        // n-1 char is the regime char
+        mrn.push(procecure_category_to_char(procedure));
     }
 
+    // Placeholder for the check digit
+    mrn.push('0');
+
     // Check MRN, and replace last character if invalid
     let last_digit = is_mrn_valid(&mrn)?;
 
@@ -72,36 +431,285 @@ pub fn generate_random_mrn(
     }
 }
 
+/// Returns the MRN at an index of the dataset selected by a seed, the same wherever it is
+/// computed. MRNs at different indexes differ for the same seed and config, so that test runners
+/// can each generate their own slice of one dataset. The config should have a year, otherwise the
+/// dataset changes with the current year.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{generate_nth, MrnConfig};
+///
+/// let config = MrnConfig {
+///     country_code: "DK",
+///     year: Some(24),
+///     ..Default::default()
+/// };
+/// let mrn = generate_nth(7, 1_000_000, &config).unwrap();
+/// assert_eq!(mrn, generate_nth(7, 1_000_000, &config).unwrap());
+/// assert_ne!(mrn, generate_nth(7, 1_000_001, &config).unwrap());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_nth(seed: u64, index: u64, config: &MrnConfig) -> Result<Mrn, MrnGeneratorError> {
+    let mrn = generate_mrn(config, source::PermutedSource::starting_at(seed, index))?;
+    Mrn::try_from(mrn)
+}
+
+/// A generated MRN broken down into the parameters it was generated with, so that emitters don't
+/// parse them back out of it. Unlike [parse_components], it knows where the office ends and
+/// whether the MRN has a procedure character.
+#[cfg(feature = "generate")]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeneratedMrn {
+    pub mrn: Mrn,
+    /// Country code, capitalized as in the MRN
+    pub country: String,
+    /// Last two digits of the issue year
+    pub year: u8,
+    /// Declaration office as it appears in the MRN, with its country code if it was given one
+    pub office: Option<String>,
+    pub procedure: Option<Procedure>,
+    pub check_digit: char,
+}
+
+/// Returns a valid MRN with the given parameters like [generate_mrn], broken down into its
+/// parameters
+///
+/// ## Example
+/// ```
+/// use mrn_generator::source::RandomSource;
+/// use mrn_generator::{generate_mrn_breakdown, DeclarationOffice, MrnConfig, Procedure};
+///
+/// let config = MrnConfig {
+///     country_code: "dk",
+///     procedure: Some(Procedure::ExportOnly),
+///     declaration_office: Some(DeclarationOffice::new("004700").unwrap()),
+///     year: Some(24),
+/// };
+/// let generated = generate_mrn_breakdown(&config, RandomSource::default()).unwrap();
+/// assert!(generated.mrn.as_str().starts_with("24DK004700"));
+/// assert_eq!("DK", generated.country);
+/// assert_eq!(Some("004700"), generated.office.as_deref());
+/// assert_eq!(generated.mrn.components().check_digit, generated.check_digit);
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_mrn_breakdown(
+    config: &MrnConfig,
+    source: impl source::IdSource,
+) -> Result<GeneratedMrn, MrnGeneratorError> {
+    let mrn = Mrn::try_from(generate_mrn(config, source)?)?;
+    let components = mrn.components();
+    Ok(GeneratedMrn {
+        country: components.country_code.to_string(),
+        year: components
+            .year
+            .parse()
+            .expect("generated MRNs start with two year digits"),
+        office: config
+            .declaration_office
+            .map(|office| office.as_str().to_string()),
+        procedure: config.procedure,
+        check_digit: components.check_digit,
+        mrn,
+    })
+}
+
 /// Returns None if MRN is valid, and correct last character if it's invalid
-/// 
+///
 /// ## Example
 /// ```
-/// use mrn_generator::{generate_random_mrn, is_mrn_valid}; 
-/// 
+/// # #[cfg(feature = "generate")] {
+/// use mrn_generator::{generate_random_mrn, is_mrn_valid};
+///
 /// let mrn = generate_random_mrn("DK", None, Some("004700")).unwrap();
 /// assert_eq!(None, is_mrn_valid(&mrn).unwrap());
+/// # }
 /// ```
 pub fn is_mrn_valid(mrn: &str) -> Result<Option<char>, MrnGeneratorError> {
-    let mut mrn_iter = mrn.chars();
-    let last_digit = mrn_iter.next_back().unwrap();
-
-    let mrn_temp: String = mrn_iter.collect();
+    let mut mrn_iter = mrn.char_indices();
+    let Some((last_position, last_digit)) = mrn_iter.next_back() else {
+        return Err(MrnGeneratorError::MrnLength(mrn.to_string()));
+    };
+    let invalid = |position, found| MrnGeneratorError::InvalidCharacter { position, found };
+    check_character_value(last_digit).map_err(|_| invalid(last_position, last_digit))?;
 
     // Multiply each char value with it's power of 2 and sum them
-    let multiplied_sum: u32 = mrn_temp
-        .chars()
-        .zip(0..mrn_temp.len())
-        .map(|(c, m)| (check_character_value(c).map(|value| (value as u32) << m)))
-        .collect::<Result<Vec<u32>, MrnGeneratorError>>()?
-        .iter()
-        .sum();
+    let multiplied_sum: u32 = mrn_iter
+        .zip(0..)
+        .map(|((position, c), m)| {
+            check_character_value(c)
+                .map(|value| (value as u32) << m)
+                .map_err(|_| invalid(position, c))
+        })
+        .sum::<Result<u32, MrnGeneratorError>>()?;
 
     let check_digit: u8 = (multiplied_sum % 11).try_into().unwrap();
     Ok(check_remainder_value(check_digit, last_digit))
 }
 
-/// Procedure types
+/// Checks that the MRN is structurally valid and has a correct check digit
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{validate_mrn, MrnGeneratorError};
+///
+/// assert_eq!(Ok(()), validate_mrn("22ITZXBZYUTJFLJXK6"));
+/// assert_eq!(
+///     Err(MrnGeneratorError::InvalidCheckDigit {
+///         mrn: "22ITZXBZYUTJFLJXK7".to_string(),
+///         expected: '6'
+///     }),
+///     validate_mrn("22ITZXBZYUTJFLJXK7")
+/// );
+/// ```
+pub fn validate_mrn(mrn: &str) -> Result<(), MrnGeneratorError> {
+    use MrnGeneratorError::*;
+
+    if let Some((position, found)) = mrn.char_indices().find(|(_, c)| !c.is_ascii_alphanumeric()) {
+        return Err(InvalidCharacter { position, found });
+    }
+    if mrn.len() != layout::LENGTH {
+        return Err(MrnLength(mrn.to_string()));
+    }
+    if !mrn[layout::YEAR].chars().all(|c| c.is_ascii_digit()) {
+        return Err(InvalidYear(mrn.to_string()));
+    }
+    if !mrn[layout::COUNTRY_CODE]
+        .chars()
+        .all(|c| c.is_ascii_uppercase())
+    {
+        return Err(InvalidCountryCode(mrn.to_string()));
+    }
+    // The check digit values are only defined for digits and uppercase letters
+    if let Some((position, found)) = mrn
+        .char_indices()
+        .skip(layout::COUNTRY_CODE.end)
+        .find(|(_, c)| c.is_ascii_lowercase())
+    {
+        return Err(InvalidCharacter { position, found });
+    }
+
+    match is_mrn_valid(mrn)? {
+        Some(expected) => Err(InvalidCheckDigit {
+            mrn: mrn.to_string(),
+            expected,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Segments of an MRN
 #[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MrnComponents<'a> {
+    /// Last two digits of the issue year
+    pub year: &'a str,
+    pub country_code: &'a str,
+    /// Unique part of the MRN, which may start with the declaration office
+    pub serial: &'a str,
+    pub procedure_char: char,
+    /// Procedure of the procedure character, None if it isn't one of the procedure characters
+    pub procedure: Option<Procedure>,
+    pub check_digit: char,
+}
+
+impl<'a> MrnComponents<'a> {
+    /// Customs office reference embedded at the start of the serial, if the serial starts with
+    /// the country code of the MRN followed by an office number, e.g. `DK004700` in
+    /// `24DKDK004700ABCDE0`
+    ///
+    /// Offices without the country code can't be told apart from the rest of the serial.
+    pub fn declaration_office(&self) -> Option<DeclarationOffice<'a>> {
+        let reference = self.serial.get(..layout::OFFICE_REFERENCE.len())?;
+        if !reference.starts_with(self.country_code) {
+            return None;
+        }
+        DeclarationOffice::new(reference).ok()
+    }
+
+    /// Issue year of the MRN, None if its year isn't two digits
+    ///
+    /// The two digits don't tell the century, so the issue year is taken as the latest year
+    /// ending in them up to the year after `current_year`, which holds for MRNs less than 99
+    /// years old.
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::parse_components;
+    ///
+    /// let components = parse_components("22ITZXBZYUTJFLJXK6").unwrap();
+    /// assert_eq!(Some(2022), components.issue_year(2026));
+    /// assert_eq!(Some(2122), components.issue_year(2121));
+    /// ```
+    pub fn issue_year(&self, current_year: i32) -> Option<i32> {
+        self.issue_year_from(current_year - 98)
+    }
+
+    /// Issue year of the MRN taken as one of the hundred years from `pivot`, None if its year
+    /// isn't two digits
+    ///
+    /// For systems with MRNs from before the last 99 years, e.g. with a pivot of 1990 the years
+    /// 90 to 99 are the 1990s and 00 to 89 the 2000s up to 2089.
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::parse_components;
+    ///
+    /// let components = parse_components("95DK0047002M8N41B3").unwrap();
+    /// assert_eq!(Some(1995), components.issue_year_from(1990));
+    /// assert_eq!(Some(2095), components.issue_year_from(2000));
+    /// ```
+    pub fn issue_year_from(&self, pivot: i32) -> Option<i32> {
+        if !self.year.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let year: i32 = self.year.parse().ok()?;
+        Some(pivot + (year - pivot).rem_euclid(100))
+    }
+}
+
+/// Splits an MRN into its segments, only checking that it is 18 alphanumerics long
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{parse_components, Procedure};
+///
+/// let components = parse_components("22ITZXBZYUTJFLJXK6").unwrap();
+/// assert_eq!("22", components.year);
+/// assert_eq!("IT", components.country_code);
+/// assert_eq!("ZXBZYUTJFLJX", components.serial);
+/// assert_eq!(
+///     Some(Procedure::TransitDeclarationAndExitSummaryDeclaration),
+///     components.procedure
+/// );
+/// assert_eq!('6', components.check_digit);
+/// ```
+pub fn parse_components(mrn: &str) -> Result<MrnComponents<'_>, MrnGeneratorError> {
+    use MrnGeneratorError::*;
+
+    if let Some((position, found)) = mrn.char_indices().find(|(_, c)| !c.is_ascii_alphanumeric()) {
+        return Err(InvalidCharacter { position, found });
+    }
+    if mrn.len() != layout::LENGTH {
+        return Err(MrnLength(mrn.to_string()));
+    }
+
+    let procedure_char = mrn[layout::PROCEDURE].chars().next().unwrap();
+    Ok(MrnComponents {
+        year: &mrn[layout::YEAR],
+        country_code: &mrn[layout::COUNTRY_CODE],
+        serial: &mrn[layout::SERIAL],
+        procedure_char,
+        procedure: char_to_procedure_category(procedure_char),
+        check_digit: mrn[layout::CHECK_DIGIT].chars().next().unwrap(),
+    })
+}
+
+/// Procedure types
+///
+/// With the `serde` feature, procedures serialize as their names and deserialize from their names
+/// or characters, see the `procedure_code` module.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Procedure {
     ExportOnly,
     ExportAndExitSummaryDeclaration,
@@ -120,6 +728,34 @@ pub enum Procedure {
     TemporaryStorageDeclarationAndEntrySummaryDeclaration,
 }
 
+/// Every procedure, in the order of their characters
+const PROCEDURES: [Procedure; 15] = [
+    Procedure::ExportOnly,
+    Procedure::ExportAndExitSummaryDeclaration,
+    Procedure::ExitSummaryDeclarationOnly,
+    Procedure::ReExportNotification,
+    Procedure::DispatchOfGoodsInRelationWithSpecialFiscalTerritories,
+    Procedure::TransitDeclarationOnly,
+    Procedure::TransitDeclarationAndExitSummaryDeclaration,
+    Procedure::TransitDeclarationAndEntrySummaryDeclaration,
+    Procedure::ProofOfTheCustomsStatusOfUnionGoods,
+    Procedure::ImportDeclarationOnly,
+    Procedure::ImportDeclarationAndEntrySummaryDeclaration,
+    Procedure::EntrySummaryDeclarationOnly,
+    Procedure::TemporaryStorageDeclaration,
+    Procedure::IntroductionOfGoodsInRelationWithSpecialFiscalTerritories,
+    Procedure::TemporaryStorageDeclarationAndEntrySummaryDeclaration,
+];
+
+/// Returns every procedure, sorted by procedure character
+pub fn all_procedures() -> &'static [Procedure] {
+    &PROCEDURES
+}
+
+/// Procedure characters of MRNs, one for each procedure
+#[cfg(any(feature = "faker", feature = "proptest"))]
+pub(crate) const PROCEDURE_CHARACTERS: &[u8] = b"ABCDEJKLMRSTUVW";
+
 /// Maps procedure category to a corresponding character
 pub fn procecure_category_to_char(procedure: Procedure) -> char {
     match procedure {
@@ -141,6 +777,217 @@ pub fn procecure_category_to_char(procedure: Procedure) -> char {
     }
 }
 
+/// Maps a procedure character back to its procedure category, the inverse of
+/// [procecure_category_to_char]
+pub fn char_to_procedure_category(c: char) -> Option<Procedure> {
+    match c {
+        'A' => Some(Procedure::ExportOnly),
+        'B' => Some(Procedure::ExportAndExitSummaryDeclaration),
+        'C' => Some(Procedure::ExitSummaryDeclarationOnly),
+        'D' => Some(Procedure::ReExportNotification),
+        'E' => Some(Procedure::DispatchOfGoodsInRelationWithSpecialFiscalTerritories),
+        'J' => Some(Procedure::TransitDeclarationOnly),
+        'K' => Some(Procedure::TransitDeclarationAndExitSummaryDeclaration),
+        'L' => Some(Procedure::TransitDeclarationAndEntrySummaryDeclaration),
+        'M' => Some(Procedure::ProofOfTheCustomsStatusOfUnionGoods),
+        'R' => Some(Procedure::ImportDeclarationOnly),
+        'S' => Some(Procedure::ImportDeclarationAndEntrySummaryDeclaration),
+        'T' => Some(Procedure::EntrySummaryDeclarationOnly),
+        'U' => Some(Procedure::TemporaryStorageDeclaration),
+        'V' => Some(Procedure::IntroductionOfGoodsInRelationWithSpecialFiscalTerritories),
+        'W' => Some(Procedure::TemporaryStorageDeclarationAndEntrySummaryDeclaration),
+        _ => None,
+    }
+}
+
+/// Describes a procedure in words
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{procedure_description, Procedure};
+///
+/// assert_eq!("Export only", procedure_description(Procedure::ExportOnly));
+/// ```
+pub fn procedure_description(procedure: Procedure) -> &'static str {
+    match procedure {
+        Procedure::ExportOnly => "Export only",
+        Procedure::ExportAndExitSummaryDeclaration => "Export and exit summary declaration",
+        Procedure::ExitSummaryDeclarationOnly => "Exit summary declaration only",
+        Procedure::ReExportNotification => "Re-export notification",
+        Procedure::DispatchOfGoodsInRelationWithSpecialFiscalTerritories => {
+            "Dispatch of goods in relation with special fiscal territories"
+        }
+        Procedure::TransitDeclarationOnly => "Transit declaration only",
+        Procedure::TransitDeclarationAndExitSummaryDeclaration => {
+            "Transit declaration and exit summary declaration"
+        }
+        Procedure::TransitDeclarationAndEntrySummaryDeclaration => {
+            "Transit declaration and entry summary declaration"
+        }
+        Procedure::ProofOfTheCustomsStatusOfUnionGoods => {
+            "Proof of the customs status of Union goods"
+        }
+        Procedure::ImportDeclarationOnly => "Import declaration only",
+        Procedure::ImportDeclarationAndEntrySummaryDeclaration => {
+            "Import declaration and entry summary declaration"
+        }
+        Procedure::EntrySummaryDeclarationOnly => "Entry summary declaration only",
+        Procedure::TemporaryStorageDeclaration => "Temporary storage declaration",
+        Procedure::IntroductionOfGoodsInRelationWithSpecialFiscalTerritories => {
+            "Introduction of goods in relation with special fiscal territories"
+        }
+        Procedure::TemporaryStorageDeclarationAndEntrySummaryDeclaration => {
+            "Temporary storage declaration and entry summary declaration"
+        }
+    }
+}
+
+/// Stable snake_case name of a procedure, as in procedure rules files
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{procedure_name, Procedure};
+///
+/// assert_eq!("export_only", procedure_name(Procedure::ExportOnly));
+/// ```
+pub fn procedure_name(procedure: Procedure) -> &'static str {
+    match procedure {
+        Procedure::ExportOnly => "export_only",
+        Procedure::ExportAndExitSummaryDeclaration => "export_and_exit_summary_declaration",
+        Procedure::ExitSummaryDeclarationOnly => "exit_summary_declaration_only",
+        Procedure::ReExportNotification => "re_export_notification",
+        Procedure::DispatchOfGoodsInRelationWithSpecialFiscalTerritories => {
+            "dispatch_of_goods_in_relation_with_special_fiscal_territories"
+        }
+        Procedure::TransitDeclarationOnly => "transit_declaration_only",
+        Procedure::TransitDeclarationAndExitSummaryDeclaration => {
+            "transit_declaration_and_exit_summary_declaration"
+        }
+        Procedure::TransitDeclarationAndEntrySummaryDeclaration => {
+            "transit_declaration_and_entry_summary_declaration"
+        }
+        Procedure::ProofOfTheCustomsStatusOfUnionGoods => {
+            "proof_of_the_customs_status_of_union_goods"
+        }
+        Procedure::ImportDeclarationOnly => "import_declaration_only",
+        Procedure::ImportDeclarationAndEntrySummaryDeclaration => {
+            "import_declaration_and_entry_summary_declaration"
+        }
+        Procedure::EntrySummaryDeclarationOnly => "entry_summary_declaration_only",
+        Procedure::TemporaryStorageDeclaration => "temporary_storage_declaration",
+        Procedure::IntroductionOfGoodsInRelationWithSpecialFiscalTerritories => {
+            "introduction_of_goods_in_relation_with_special_fiscal_territories"
+        }
+        Procedure::TemporaryStorageDeclarationAndEntrySummaryDeclaration => {
+            "temporary_storage_declaration_and_entry_summary_declaration"
+        }
+    }
+}
+
+/// Looks up a procedure by its character, e.g. `A`, or its name, e.g. `export_only`, the
+/// inverse of both [procecure_category_to_char] and [procedure_name]
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{lookup_procedure, Procedure};
+///
+/// assert_eq!(Ok(Procedure::ExportOnly), lookup_procedure("A"));
+/// assert_eq!(Ok(Procedure::ExportOnly), lookup_procedure("export_only"));
+/// assert!(lookup_procedure("export").is_err());
+/// ```
+pub fn lookup_procedure(code: &str) -> Result<Procedure, MrnGeneratorError> {
+    let mut chars = code.chars();
+    let by_char = match (chars.next(), chars.next()) {
+        (Some(c), None) => char_to_procedure_category(c),
+        _ => None,
+    };
+    by_char
+        .or_else(|| {
+            PROCEDURES
+                .into_iter()
+                .find(|&procedure| procedure_name(procedure) == code)
+        })
+        .ok_or_else(|| MrnGeneratorError::UnknownProcedure(code.to_string()))
+}
+
+impl core::str::FromStr for Procedure {
+    type Err = MrnGeneratorError;
+
+    /// Parses a procedure character or name, see [lookup_procedure]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lookup_procedure(s)
+    }
+}
+
+/// How a row of the procedure table treats the combined procedure category
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Combination {
+    /// The procedure category is not combined with another one
+    Standalone,
+    /// The procedure category is combined with the given one
+    With(&'static str),
+    /// The row applies whether or not the procedure category is combined
+    Any,
+}
+
+/// A row of the procedure table
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ProcedureMapping {
+    pub category: &'static str,
+    pub combination: Combination,
+    pub procedure: Procedure,
+}
+
+impl ProcedureMapping {
+    /// Returns true if the row applies to the procedure category and combination
+    pub fn matches(&self, proctgr: &str, combined: Option<&str>) -> bool {
+        self.category == proctgr
+            && match (self.combination, combined) {
+                (Combination::Any, _) | (Combination::Standalone, None) => true,
+                (Combination::With(c), Some(combined)) => c == combined,
+                _ => false,
+            }
+    }
+}
+
+/// A declaration category and its description
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CategoryInfo {
+    pub code: &'static str,
+    pub description: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/procedure_table.rs"));
+
+/// Returns the procedure table, compiled from `data/procedure_table.txt`
+///
+#[doc = include_str!(concat!(env!("OUT_DIR"), "/procedure_table.md"))]
+pub fn all_mappings() -> &'static [ProcedureMapping] {
+    MAPPINGS
+}
+
+/// Returns all declaration categories with their descriptions, compiled from
+/// `data/categories.txt`
+pub fn all_categories() -> &'static [CategoryInfo] {
+    CATEGORIES
+}
+
+/// Returns the description of a declaration category
+///
+/// ## Example
+/// ```
+/// use mrn_generator::category_description;
+///
+/// assert_eq!(Some("Release for free circulation"), category_description("H1"));
+/// assert_eq!(None, category_description("Z9"));
+/// ```
+pub fn category_description(category: &str) -> Option<&'static str> {
+    CATEGORIES
+        .iter()
+        .find(|c| c.code == category)
+        .map(|c| c.description)
+}
+
 /// Matches a procedure category code (optionally combined with another one) and returns
 /// the corresponding customs procedure
 pub fn match_procedure(
@@ -149,59 +996,119 @@ pub fn match_procedure(
 ) -> Result<Procedure, MrnGeneratorError> {
     use MrnGeneratorError::*;
 
-    let exit_combined = ["A"];
-    let entry_combined = ["F"];
-    match proctgr {
-        "B1" | "B2" | "B3" | "C1" if combined.is_none() => Ok(Procedure::ExportOnly),
-        "B1" | "B2" | "B3" | "C1" if combined.is_some_and(|c| exit_combined.contains(&c)) => {
-            Ok(Procedure::ExportAndExitSummaryDeclaration)
-        }
-        "A1" | "A2" => Ok(Procedure::ExitSummaryDeclarationOnly),
-        "A3" => Ok(Procedure::ReExportNotification),
-        "B4" => Ok(Procedure::DispatchOfGoodsInRelationWithSpecialFiscalTerritories),
-        "D1" | "D2" | "D3" if combined.is_none() => Ok(Procedure::TransitDeclarationOnly),
-        "D1" | "D2" | "D3" if combined.is_some_and(|c| exit_combined.contains(&c)) => {
-            Ok(Procedure::TransitDeclarationAndExitSummaryDeclaration)
-        }
-        "D1" | "D2" | "D3" if combined.is_some_and(|c| entry_combined.contains(&c)) => {
-            Ok(Procedure::TransitDeclarationAndEntrySummaryDeclaration)
-        }
-        "E1" | "E2" => Ok(Procedure::ProofOfTheCustomsStatusOfUnionGoods),
-        "H1" | "H2" | "H3" | "H4" | "H6" | "I1" if combined.is_none() => {
-            Ok(Procedure::ImportDeclarationOnly)
-        }
-        "H1" | "H2" | "H3" | "H4" | "H6" | "I1"
-            if combined.is_some_and(|c| entry_combined.contains(&c)) =>
-        {
-            Ok(Procedure::ImportDeclarationAndEntrySummaryDeclaration)
-        }
-        "F1a" | "F1b" | "F1c" | "F1d" | "F2a" | "F2b" | "F2c" | "F2d" | "F3a" | "F3b" | "F4a"
-        | "F4b" | "F4c" | "F5" => Ok(Procedure::EntrySummaryDeclarationOnly),
-        "H5" => Ok(Procedure::IntroductionOfGoodsInRelationWithSpecialFiscalTerritories),
-        "G4" if combined.is_none() => Ok(Procedure::TemporaryStorageDeclaration),
-        "G4" if combined.is_some_and(|c| entry_combined.contains(&c)) => {
-            Ok(Procedure::TemporaryStorageDeclarationAndEntrySummaryDeclaration)
-        }
-        _ => {
-            if let Some(c) = combined {
-                Err(InvalidProcedureCategoryCombination {
+    match MAPPINGS.iter().find(|m| m.matches(proctgr, combined)) {
+        Some(mapping) => Ok(mapping.procedure),
+        None => match combined {
+            Some(c) if MAPPINGS.iter().any(|m| m.category == proctgr) => {
+                Err(InvalidCombinedCategory {
                     procedure_category: proctgr.to_string(),
-                    combination: c.to_string(),
+                    combined: c.to_string(),
+                    allowed: combined_categories(proctgr)
+                        .into_iter()
+                        .map(ToString::to_string)
+                        .collect(),
                 })
-            } else {
-                Err(InvalidProcedureCategory(proctgr.to_string()))
+            }
+            Some(c) => Err(InvalidProcedureCategoryCombination {
+                procedure_category: proctgr.to_string(),
+                combination: c.to_string(),
+            }),
+            None => Err(InvalidProcedureCategory {
+                value: proctgr.to_string(),
+                suggestion: closest(proctgr, MAPPINGS.iter().map(|m| m.category))
+                    .map(ToString::to_string),
+            }),
+        },
+    }
+}
+
+/// Returns the categories a procedure category can be combined with in the procedure table, in
+/// table order. Categories combinable with any category (`*`) have none listed.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::combined_categories;
+///
+/// assert_eq!(vec!["A", "F"], combined_categories("D1"));
+/// assert!(combined_categories("H5").is_empty());
+/// ```
+pub fn combined_categories(proctgr: &str) -> Vec<&'static str> {
+    let mut categories = Vec::new();
+    for mapping in MAPPINGS.iter().filter(|m| m.category == proctgr) {
+        if let Combination::With(c) = mapping.combination {
+            if !categories.contains(&c) {
+                categories.push(c);
             }
         }
     }
+    categories
+}
+
+/// Matches an optional procedure category like [match_procedure], a combined procedure category
+/// without a procedure category is an error rather than ignored
+///
+/// ## Example
+/// ```
+/// use mrn_generator::{match_optional_procedure, Procedure};
+///
+/// assert_eq!(Ok(None), match_optional_procedure(None, None));
+/// assert_eq!(
+///     Ok(Some(Procedure::ExportOnly)),
+///     match_optional_procedure(Some("B1"), None)
+/// );
+/// assert!(match_optional_procedure(None, Some("A")).is_err());
+/// ```
+pub fn match_optional_procedure(
+    proctgr: Option<&str>,
+    combined: Option<&str>,
+) -> Result<Option<Procedure>, MrnGeneratorError> {
+    match (proctgr, combined) {
+        (Some(proctgr), combined) => match_procedure(proctgr, combined).map(Some),
+        (None, Some(combined)) => Err(MrnGeneratorError::CombinedWithoutCategory(
+            combined.to_string(),
+        )),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Last two digits of the current year
+#[cfg(feature = "generate")]
+pub(crate) fn current_year() -> String {
+    Utc::now().year().to_string().chars().skip(2).collect()
+}
+
+/// Returns the customs office reference list (COL) identifier of an office, which starts with the
+/// country code, e.g. `DK004700` for office `004700` in Denmark
+///
+/// Offices that already start with the country code are returned unchanged.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::office_reference;
+///
+/// assert_eq!("DK004700", office_reference("DK", "004700"));
+/// assert_eq!("DK004700", office_reference("dk", "DK004700"));
+/// ```
+pub fn office_reference(country_code: &str, office: &str) -> String {
+    let country_code: String = country_code
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if office.starts_with(&country_code) {
+        office.to_string()
+    } else {
+        alloc::format!("{country_code}{office}")
+    }
 }
 
 /// Capitalizes string
-fn capitalize(s: &str) -> String {
+pub(crate) fn capitalize(s: &str) -> String {
     s.chars().map(|c| c.to_ascii_uppercase()).collect()
 }
 
 /// Replaces last character of string with new character
-fn replace_last_char(s: &str, c: char) -> String {
+#[cfg(feature = "generate")]
+pub(crate) fn replace_last_char(s: &str, c: char) -> String {
     let mut new_str = s.to_string();
     new_str.pop();
     new_str.push(c);
@@ -210,8 +1117,9 @@ fn replace_last_char(s: &str, c: char) -> String {
 
 /// Remainder values according to tables in ISO 6346
 pub fn check_remainder_value(check_digit: u8, last_digit: char) -> Option<char> {
-    if check_digit % 10 != last_digit as u8 - 48 {
-        char::from_digit((check_digit % 10) as u32, 10)
+    let check_digit = u32::from(check_digit % 10);
+    if last_digit.to_digit(10) != Some(check_digit) {
+        char::from_digit(check_digit, 10)
     } else {
         None
     }
@@ -241,8 +1149,10 @@ pub fn check_character_value(c: char) -> Result<u8, MrnGeneratorError> {
 mod tests {
 
     use super::*;
+    use alloc::vec;
 
     #[test]
+    #[cfg(feature = "generate")]
     fn generate_random_mrn_test() {
         let mrn = generate_random_mrn("DK", Some(Procedure::ExportOnly), None).unwrap();
 
@@ -258,6 +1168,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "generate")]
     fn generate_random_mrn_test_without_procedure() {
         let mrn = generate_random_mrn("DK", None, None).unwrap();
 
@@ -271,6 +1182,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "generate")]
     fn generate_random_mrn_test_with_declaration_office() {
         let mrn = generate_random_mrn("DK", None, Some("004700")).unwrap();
 
@@ -285,10 +1197,258 @@ mod tests {
         assert_eq!(None, is_mrn_valid(&mrn).unwrap());
     }
 
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_mrn_test() {
+        let config = MrnConfig {
+            country_code: "DK",
+            procedure: Some(Procedure::ExportOnly),
+            declaration_office: None,
+            year: Some(5),
+        };
+        let mrn = generate_mrn(&config, RandomSource::default()).unwrap();
+        assert!(mrn.starts_with("05DK"));
+        assert_eq!(None, is_mrn_valid(&mrn).unwrap());
+
+        let config = MrnConfig {
+            year: Some(100),
+            ..config
+        };
+        assert_eq!(
+            Err(MrnGeneratorError::IssueYear(100)),
+            generate_mrn(&config, RandomSource::default())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_mrn_breakdown_test() {
+        use source::SequentialSource;
+
+        let config = MrnConfig {
+            country_code: "DK",
+            procedure: None,
+            declaration_office: Some(DeclarationOffice::new("DK004700").unwrap()),
+            year: Some(24),
+        };
+        let mrn = generate_mrn(&config, SequentialSource::starting_at(10)).unwrap();
+        assert_eq!(
+            GeneratedMrn {
+                mrn: Mrn::try_from(mrn.as_str()).unwrap(),
+                country: "DK".to_string(),
+                year: 24,
+                office: Some("DK004700".to_string()),
+                procedure: None,
+                check_digit: mrn.chars().last().unwrap(),
+            },
+            generate_mrn_breakdown(&config, SequentialSource::starting_at(10)).unwrap()
+        );
+
+        let config = MrnConfig {
+            country_code: "dk",
+            procedure: Some(Procedure::ExportOnly),
+            declaration_office: None,
+            year: Some(5),
+        };
+        let generated = generate_mrn_breakdown(&config, RandomSource::default()).unwrap();
+        assert_eq!("DK", generated.country);
+        assert_eq!(5, generated.year);
+        assert_eq!(None, generated.office);
+        assert_eq!(Some(Procedure::ExportOnly), generated.procedure);
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_nth_test() {
+        use source::PermutedSource;
+
+        let config = MrnConfig {
+            country_code: "DK",
+            procedure: Some(Procedure::ExportOnly),
+            declaration_office: None,
+            year: Some(24),
+        };
+        // The same MRNs as generating the whole dataset in order
+        let mut source = PermutedSource::new(7);
+        for index in 0..100 {
+            let mrn = generate_nth(7, index, &config).unwrap();
+            assert_eq!(generate_mrn(&config, &mut source).unwrap(), mrn.as_str());
+        }
+        let config = MrnConfig {
+            declaration_office: Some(DeclarationOffice::new("DK004700").unwrap()),
+            ..config
+        };
+        assert_eq!(
+            Err(MrnGeneratorError::SourceExhausted),
+            generate_nth(7, u64::MAX, &config)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_mrn_with_source_test() {
+        use source::SequentialSource;
+
+        let mut source = SequentialSource::starting_at(42);
+        let mrn =
+            generate_mrn_with_source("DK", Some(Procedure::ExportOnly), None, &mut source).unwrap();
+        assert_eq!("000000000042", &mrn[4..16]);
+        assert_eq!('A', mrn.chars().nth(16).unwrap());
+        assert_eq!(None, is_mrn_valid(&mrn).unwrap());
+
+        let next =
+            generate_mrn_with_source("DK", Some(Procedure::ExportOnly), None, &mut source).unwrap();
+        assert_eq!("000000000043", &next[4..16]);
+
+        assert_eq!(
+            Err(MrnGeneratorError::DeclarationOfficeLength(
+                "0047000047000".to_string()
+            )),
+            generate_mrn_with_source(
+                "DK",
+                Some(Procedure::ExportOnly),
+                Some("0047000047000"),
+                &mut source
+            )
+        );
+    }
+
     #[test]
     fn is_mrn_valid_test() {
         assert_eq!(None, is_mrn_valid("22ITZXBZYUTJFLJXK6").unwrap());
         assert_eq!(Some('1'), is_mrn_valid("22DK1V0QQK2S6J7TU2").unwrap());
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidCharacter {
+                position: 3,
+                found: '_'
+            }),
+            is_mrn_valid("22D_1V0QQK2S6J7TU2")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidCharacter {
+                position: 17,
+                found: '-'
+            }),
+            is_mrn_valid("22ITZXBZYUTJFLJXK-")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::MrnLength(String::new())),
+            is_mrn_valid("")
+        );
+    }
+
+    #[test]
+    fn validate_mrn_test() {
+        use MrnGeneratorError::*;
+
+        assert_eq!(Ok(()), validate_mrn("22ITZXBZYUTJFLJXK6"));
+        assert_eq!(
+            Err(MrnLength("22ITZXBZYUTJFLJXK".to_string())),
+            validate_mrn("22ITZXBZYUTJFLJXK")
+        );
+        assert_eq!(
+            Err(InvalidYear("2XITZXBZYUTJFLJXK6".to_string())),
+            validate_mrn("2XITZXBZYUTJFLJXK6")
+        );
+        assert_eq!(
+            Err(InvalidCountryCode("22I1ZXBZYUTJFLJXK6".to_string())),
+            validate_mrn("22I1ZXBZYUTJFLJXK6")
+        );
+        assert_eq!(
+            Err(InvalidCharacter {
+                position: 4,
+                found: '-'
+            }),
+            validate_mrn("22IT-XBZYUTJFLJXK6")
+        );
+        assert_eq!(
+            Err(InvalidCharacter {
+                position: 6,
+                found: 'é'
+            }),
+            validate_mrn("22ITZXéZYUTJFLJXK6")
+        );
+        assert_eq!(
+            Err(InvalidCharacter {
+                position: 4,
+                found: 'z'
+            }),
+            validate_mrn("22ITzXBZYUTJFLJXK1")
+        );
+    }
+
+    #[test]
+    fn parse_components_test() {
+        let components = parse_components("26DK0047002M8N41B3").unwrap();
+        assert_eq!("0047002M8N41", components.serial);
+        assert_eq!('B', components.procedure_char);
+        assert_eq!(
+            Some(Procedure::ExportAndExitSummaryDeclaration),
+            components.procedure
+        );
+        assert_eq!(None, components.declaration_office());
+        assert_eq!(
+            Err(MrnGeneratorError::MrnLength("26DK".to_string())),
+            parse_components("26DK")
+        );
+
+        let components = parse_components("24DKDK004700ABCDE0").unwrap();
+        assert_eq!(
+            Some("004700"),
+            components
+                .declaration_office()
+                .map(|office| office.office_number())
+        );
+    }
+
+    #[test]
+    fn issue_year_test() {
+        let year =
+            |mrn: &str, current_year| parse_components(mrn).unwrap().issue_year(current_year);
+        assert_eq!(Some(2026), year("26DK0047002M8N41B3", 2026));
+        // MRNs dated next year are accepted around New Year
+        assert_eq!(Some(2027), year("27DK0047002M8N41B3", 2026));
+        assert_eq!(Some(1928), year("28DK0047002M8N41B3", 2026));
+        assert_eq!(Some(2009), year("09DK0047002M8N41B3", 2026));
+        assert_eq!(None, year("2ADK0047002M8N41B3", 2026));
+
+        let components = parse_components("90DK0047002M8N41B3").unwrap();
+        assert_eq!(Some(1990), components.issue_year_from(1990));
+        assert_eq!(Some(2090), components.issue_year_from(1991));
+        assert_eq!(Some(2090), components.issue_year_from(2000));
+    }
+
+    #[test]
+    fn char_to_procedure_category_test() {
+        assert_eq!(
+            Some(Procedure::EntrySummaryDeclarationOnly),
+            char_to_procedure_category('T')
+        );
+        assert_eq!(None, char_to_procedure_category('F'));
+        assert_eq!(
+            Some(Procedure::TransitDeclarationOnly),
+            char_to_procedure_category(procecure_category_to_char(
+                Procedure::TransitDeclarationOnly
+            ))
+        );
+    }
+
+    #[test]
+    fn lookup_procedure_test() {
+        for &procedure in all_procedures() {
+            let c = procecure_category_to_char(procedure).to_string();
+            assert_eq!(Ok(procedure), lookup_procedure(&c));
+            assert_eq!(Ok(procedure), procedure_name(procedure).parse());
+        }
+        assert!(all_procedures()
+            .windows(2)
+            .all(|w| procecure_category_to_char(w[0]) < procecure_category_to_char(w[1])));
+        assert_eq!(
+            Err(MrnGeneratorError::UnknownProcedure("a".to_string())),
+            lookup_procedure("a")
+        );
+        assert!(lookup_procedure("").is_err());
+        assert!(lookup_procedure("Export only").is_err());
     }
 
     #[test]
@@ -305,21 +1465,38 @@ mod tests {
         use MrnGeneratorError::*;
 
         assert_eq!(
-            Err(InvalidProcedureCategoryCombination {
+            Err(InvalidCombinedCategory {
                 procedure_category: "B2".to_string(),
-                combination: "B".to_string()
+                combined: "B".to_string(),
+                allowed: vec!["A".to_string()]
             }),
             match_procedure("B2", Some("B"))
         );
+        assert_eq!(
+            "G4 cannot be combined with A, it can only be combined with F",
+            match_procedure("G4", Some("A")).unwrap_err().to_string()
+        );
 
         let invalid_procedure_category = "not a valid procedure 🤡";
 
         assert_eq!(
-            Err(InvalidProcedureCategory(
-                invalid_procedure_category.to_string()
-            )),
+            Err(InvalidProcedureCategory {
+                value: invalid_procedure_category.to_string(),
+                suggestion: None
+            }),
             match_procedure(invalid_procedure_category, None)
         );
+        assert_eq!(
+            "h6 is not a valid procedure category, did you mean H6?",
+            match_procedure("h6", None).unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(InvalidProcedureCategory {
+                value: "F1e".to_string(),
+                suggestion: Some("F1a".to_string())
+            }),
+            match_procedure("F1e", None)
+        );
         assert_eq!(
             Err(InvalidProcedureCategoryCombination {
                 procedure_category: invalid_procedure_category.to_string(),
@@ -327,6 +1504,56 @@ mod tests {
             }),
             match_procedure(invalid_procedure_category, Some("F"))
         );
+        assert_eq!(
+            Err(CombinedWithoutCategory("A".to_string())),
+            match_optional_procedure(None, Some("A"))
+        );
+    }
+
+    #[test]
+    fn mrn_config_from_input_test() {
+        assert_eq!(
+            Ok(MrnConfig {
+                country_code: "DK",
+                ..Default::default()
+            }),
+            MrnConfig::from_input("DK", None, None, None)
+        );
+        assert_eq!(
+            Err(vec![
+                MrnGeneratorError::CountryCodeLength("D".to_string()),
+                MrnGeneratorError::CombinedWithoutCategory("A".to_string()),
+                MrnGeneratorError::DeclarationOfficeLength("0047".to_string()),
+            ]),
+            MrnConfig::from_input("D", None, Some("A"), Some("0047"))
+        );
+    }
+
+    #[test]
+    fn all_mappings_test() {
+        assert!(!PROCEDURE_TABLE_VERSION.is_empty());
+        assert!(all_mappings().iter().all(|m| match_procedure(
+            m.category,
+            match m.combination {
+                Combination::With(c) => Some(c),
+                _ => None,
+            }
+        ) == Ok(m.procedure)));
+        assert_eq!(
+            Ok(Procedure::ExitSummaryDeclarationOnly),
+            match_procedure("A1", Some("F"))
+        );
+    }
+
+    #[test]
+    fn category_description_test() {
+        assert!(all_mappings()
+            .iter()
+            .all(|m| category_description(m.category).is_some()));
+        assert_eq!(
+            Some("Export declaration and re-export declaration"),
+            category_description("B1")
+        );
     }
 
     #[test]
@@ -335,16 +1562,26 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "generate")]
     fn replace_last_char_test() {
         assert_eq!("bar", replace_last_char("bat", 'r'))
     }
 
+    #[test]
+    fn closest_test() {
+        assert_eq!(3, edit_distance("kitten", "SITTING"));
+        assert_eq!(Some("H5"), closest("h5", ["H1", "H5"]));
+        assert_eq!(Some("Sweden"), closest("Swden", ["Spain", "Sweden"]));
+        assert_eq!(None, closest("Z9", ["Spain", "Sweden"]));
+    }
+
     #[test]
     fn check_remainder_value_test() {
         assert_eq!(None, check_remainder_value(3, '3'));
         assert_eq!(None, check_remainder_value(10, '0'));
         assert_eq!(Some('3'), check_remainder_value(3, '5'));
         assert_eq!(Some('0'), check_remainder_value(10, '9'));
+        assert_eq!(Some('3'), check_remainder_value(3, '-'));
     }
 
     #[test]
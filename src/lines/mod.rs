@@ -0,0 +1,93 @@
+//! Reading of inputs line by line in constant memory, however long their lines are
+
+use std::io::{self, BufRead, Read};
+
+/// Lines of a reader cut to a maximum length, the rest of a longer line being skipped without
+/// being kept in memory. Invalid UTF-8 is replaced rather than failing the whole input.
+pub struct BoundedLines<R> {
+    reader: R,
+    max_length: usize,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> BoundedLines<R> {
+    pub fn new(reader: R, max_length: usize) -> Self {
+        BoundedLines {
+            reader,
+            max_length,
+            buffer: Vec::with_capacity(max_length + 1),
+        }
+    }
+
+    /// Reads the next line into the buffer, returning false at the end of the input
+    fn read_line(&mut self) -> io::Result<bool> {
+        self.buffer.clear();
+        let mut line = Read::take(&mut self.reader, self.max_length as u64 + 1);
+        if line.read_until(b'\n', &mut self.buffer)? == 0 {
+            return Ok(false);
+        }
+        if self.buffer.last() == Some(&b'\n') {
+            self.buffer.pop();
+            if self.buffer.last() == Some(&b'\r') {
+                self.buffer.pop();
+            }
+            return Ok(true);
+        }
+
+        // Skip the rest of a line over the maximum length
+        self.buffer.truncate(self.max_length);
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok(true);
+            }
+            match available.iter().position(|&b| b == b'\n') {
+                Some(end) => {
+                    self.reader.consume(end + 1);
+                    return Ok(true);
+                }
+                None => {
+                    let len = available.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for BoundedLines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_line() {
+            Ok(true) => Some(Ok(String::from_utf8_lossy(&self.buffer).into_owned())),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::io::BufReader;
+
+    #[test]
+    fn bounded_lines_test() {
+        let lines = |input: &[u8]| {
+            // A tiny buffer makes long lines span several reads
+            BoundedLines::new(BufReader::with_capacity(4, input), 8)
+                .collect::<io::Result<Vec<String>>>()
+                .unwrap()
+        };
+        assert_eq!(vec!["a", "", "bc"], lines(b"a\n\r\nbc"));
+        assert_eq!(vec!["12345678", "x"], lines(b"12345678\nx\n"));
+        assert_eq!(
+            vec!["12345678", "x", "12345678"],
+            lines(b"123456789abcdefghij\nx\n123456789")
+        );
+        assert_eq!(vec!["a\u{FFFD}b"], lines(b"a\xFFb\n"));
+        assert!(lines(b"").is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Local Reference Numbers (LRN), the declarant's own reference for a declaration
+
+use crate::source::IdSource;
+use crate::MrnGeneratorError;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// Maximum length of an LRN
+pub const LRN_MAX_LENGTH: usize = 22;
+
+/// Returns an LRN of `length` characters, starting with `prefix` and followed by a segment taken
+/// from the given source
+///
+/// National conventions usually fix a prefix (e.g. the declarant's EORI or software code) and a
+/// length; using a sequential source keeps the LRNs unique per declarant.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::lrn::generate_lrn_with_source;
+/// use mrn_generator::source::SequentialSource;
+///
+/// let lrn = generate_lrn_with_source("DECL-", 12, SequentialSource::starting_at(7)).unwrap();
+/// assert_eq!("DECL-0000007", lrn);
+/// ```
+pub fn generate_lrn_with_source(
+    prefix: &str,
+    length: usize,
+    mut source: impl IdSource,
+) -> Result<String, MrnGeneratorError> {
+    use MrnGeneratorError::*;
+
+    if length > LRN_MAX_LENGTH || prefix.len() > length {
+        return Err(LrnLength(length));
+    }
+    if !prefix.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(InvalidLrnPrefix(prefix.to_string()));
+    }
+
+    let segment = source.next_segment(length - prefix.len())?;
+    Ok(format!("{prefix}{segment}"))
+}
+
+/// Returns a random LRN of `length` characters starting with `prefix`
+///
+/// ## Example
+/// ```
+/// use mrn_generator::lrn::generate_random_lrn;
+///
+/// let lrn = generate_random_lrn("DK", 22).unwrap();
+/// assert_eq!(22, lrn.len());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_random_lrn(prefix: &str, length: usize) -> Result<String, MrnGeneratorError> {
+    generate_lrn_with_source(prefix, length, crate::source::RandomSource::default())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::source::SequentialSource;
+
+    #[test]
+    fn generate_lrn_with_source_test() {
+        let mut source = SequentialSource::starting_at(1);
+        assert_eq!(
+            "LRN001",
+            generate_lrn_with_source("LRN", 6, &mut source).unwrap()
+        );
+        assert_eq!(
+            "LRN002",
+            generate_lrn_with_source("LRN", 6, &mut source).unwrap()
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::LrnLength(23)),
+            generate_lrn_with_source("LRN", 23, &mut source)
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::LrnLength(2)),
+            generate_lrn_with_source("LRN", 2, &mut source)
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidLrnPrefix("L N".to_string())),
+            generate_lrn_with_source("L N", 6, &mut source)
+        );
+    }
+}
@@ -1,23 +1,1095 @@
+mod audit;
+mod bench;
+mod bloom;
+mod commands;
+mod complete;
+mod config;
+mod dataset;
+mod distribution;
+mod exec;
+mod fix;
+mod fixtures;
+mod input;
+mod lines;
+mod manifest;
+mod mcp;
+mod output;
 mod parser;
+mod pipeline;
+mod plan;
+mod preset;
+mod profile;
+mod registry;
+mod schema;
+mod serve;
+#[cfg(feature = "online")]
+mod webhook;
+mod weights;
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{bail, Context, Result};
+use audit::AuditLog;
+use bloom::SeenFilter;
+use chrono::Datelike;
+use commands::MAX_ATTEMPTS;
+use distribution::{DateSpread, WeightedChoice};
+use exec::ExecHook;
+use mrn_generator::i18n::{Lang, Localizer};
+use mrn_generator::lrn::{generate_lrn_with_source, LRN_MAX_LENGTH};
+use mrn_generator::source::{IdSource, RandomSource};
 use mrn_generator::*;
-use parser::Args;
+use parser::{Args, Command, IdSourceArgs, IdSourceKind, OutputFormat};
+use pipeline::{Generated, Writer};
+use profile::Traffic;
+use rand::distributions::Bernoulli;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use registry::Registry;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+use weights::Weights;
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let declaration_office = args.declaration_office.as_deref();
+fn main() -> ExitCode {
+    let lang = parser::requested_lang()
+        .or_else(Lang::from_env)
+        .unwrap_or_default();
+    let localizer = Localizer::new(lang);
+    let args = parser::parse_localized(&localizer);
+    let json_errors = args.command.is_none() && args.format == OutputFormat::Json;
+    let result = match args.command {
+        Some(Command::Grn(grn_args)) => commands::grn(grn_args),
+        Some(Command::Lrn(lrn_args)) => commands::lrn(lrn_args),
+        Some(Command::Dataset(dataset_args)) => dataset::dataset(dataset_args),
+        Some(Command::Eori(eori_args)) => commands::eori(eori_args),
+        Some(Command::Container(container_args)) => commands::container(container_args),
+        Some(Command::Ucr(ucr_args)) => commands::ucr(ucr_args),
+        Some(Command::Validate(validate_args)) => commands::validate(validate_args, &localizer),
+        Some(Command::ListCategories) => commands::list_categories(&localizer),
+        Some(Command::Explain(explain_args)) => commands::explain(explain_args, &localizer),
+        Some(Command::Complete(complete_args)) => complete::complete(complete_args),
+        Some(Command::CheckDuplicates(check_args)) => commands::check_duplicates(check_args),
+        Some(Command::Grep(grep_args)) => commands::grep(grep_args),
+        Some(Command::Fix(fix_args)) => fix::fix(fix_args),
+        Some(Command::Fixtures(fixtures_args)) => fixtures::print_fixtures(fixtures_args),
+        #[cfg(feature = "online")]
+        Some(Command::Verify(verify_args)) => commands::verify(verify_args, &localizer),
+        Some(Command::Mcp) => mcp::serve(&localizer),
+        Some(Command::Schema(schema_args)) => schema::print_schema(schema_args.kind),
+        Some(Command::Bench(bench_args)) => bench::bench(bench_args, &localizer),
+        Some(Command::Serve(serve_args)) => serve::serve(serve_args, &localizer),
+        None => generate(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if json_errors => {
+            report_json_error(&e, &localizer);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            report_error(&e, &localizer);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Several invalid arguments, reported together
+#[derive(Debug)]
+struct ArgumentErrors(Vec<MrnGeneratorError>);
+
+impl std::fmt::Display for ArgumentErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let errors: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        f.write_str(&errors.join("\n"))
+    }
+}
+
+impl std::error::Error for ArgumentErrors {}
+
+/// Describes an error or one of its causes in the language of the localizer
+fn describe(cause: &(dyn std::error::Error + 'static), localizer: &Localizer) -> String {
+    match cause.downcast_ref::<MrnGeneratorError>() {
+        Some(e) => localizer.error(e),
+        None => cause.to_string(),
+    }
+}
+
+/// Prints an error and its causes like anyhow does, in the language of the localizer
+fn report_error(error: &anyhow::Error, localizer: &Localizer) {
+    let describe = |cause| describe(cause, localizer);
+    let label = |id: &str, default: &str| {
+        localizer
+            .message(id, None)
+            .unwrap_or_else(|| default.to_string())
+    };
+
+    if let Some(ArgumentErrors(errors)) = error.downcast_ref() {
+        for e in errors {
+            eprintln!("{}: {}", label("cli-error", "Error"), localizer.error(e));
+        }
+        return;
+    }
+
+    eprintln!(
+        "{}: {}",
+        label("cli-error", "Error"),
+        describe(error.as_ref())
+    );
+    let mut causes = error.chain().skip(1).peekable();
+    if causes.peek().is_some() {
+        eprintln!("\n{}:", label("cli-caused-by", "Caused by"));
+        for cause in causes {
+            eprintln!("    {}", describe(cause));
+        }
+    }
+}
+
+/// Prints an error and its causes as a JSON object, as described by `schemas/error.schema.json`
+fn report_json_error(error: &anyhow::Error, localizer: &Localizer) {
+    if let Some(ArgumentErrors(errors)) = error.downcast_ref() {
+        for e in errors {
+            let error = serde_json::json!({
+                "error": {
+                    "message": localizer.error(e),
+                    "causes": [],
+                },
+            });
+            eprintln!("{error}");
+        }
+        return;
+    }
+    let causes: Vec<String> = error
+        .chain()
+        .skip(1)
+        .map(|cause| describe(cause, localizer))
+        .collect();
+    let error = serde_json::json!({
+        "error": {
+            "message": describe(error.as_ref(), localizer),
+            "causes": causes,
+        },
+    });
+    eprintln!("{error}");
+}
+
+/// Generates MRNs according to the top level arguments
+fn generate(mut args: Args) -> Result<()> {
+    if (args.parallel || args.audit_log.is_some()) && !args.dry_run {
+        // The seeds of the chunks derive from one seed, and the chunks of the permuted source
+        // need the same key. The audit log records the seed, so its MRNs can be regenerated.
+        // A dry run leaves it to the run, which would choose another one.
+        args.source.seed.get_or_insert_with(rand::random);
+    }
+    if let Some(count) = args.count_per_procedure {
+        args.number_of_mrns = count * all_procedures().len();
+    }
+    let mut weights = args.weights.as_deref().map(Weights::load).transpose()?;
+    if weights.is_none() {
+        // The countries, procedures and offices of a preset are defaults, those of the command
+        // line replace them
+        weights = args.preset.and_then(preset::weights).map(|mut weights| {
+            if !args.country_code.is_empty() {
+                weights.countries.clear();
+                weights
+                    .offices
+                    .retain(|country, _| args.country_code.contains(country));
+            }
+            if args.procedure_category.is_some() || args.combined.is_some() {
+                weights.procedures.clear();
+            }
+            if args.declaration_office.is_some() || args.random_office {
+                weights.offices.clear();
+            }
+            weights
+        });
+    }
+    if let Some(weights) = &weights {
+        if !weights.countries.is_empty() {
+            if !args.country_code.is_empty() {
+                bail!("the countries are given both by --country-code and the weights file");
+            }
+            let (codes, country_weights) = weights.countries.iter().cloned().unzip();
+            args.country_code = codes;
+            args.country_weights = Some(country_weights);
+        } else if args.country_code.is_empty() {
+            bail!("the weights file has no countries, give them with --country-code");
+        }
+        weights.check_offices(&args.country_code)?;
+        if weights.procedures().is_some() && args.procedure_category.is_some() {
+            bail!("the procedures are given both by --procedure-category and the weights file");
+        }
+    }
+    if let Some(count) = args.all_countries {
+        if args.country_code.is_empty() {
+            args.country_code = country::all_countries()
+                .iter()
+                .map(|country| country.code.to_string())
+                .collect();
+        }
+        args.country_weights = None;
+        args.country_counts = Some(vec![count; args.country_code.len()]);
+    }
+    if let Some(counts) = &args.country_counts {
+        args.number_of_mrns = counts.iter().sum();
+    }
+    if args.country_code.is_empty() && args.profile.is_none() {
+        bail!("the preset has no countries, give them with --country-code");
+    }
+    let parameters =
+        (args.manifest || args.audit_log.is_some()).then(|| manifest::parameters(&args));
+    let mut audit_log = args
+        .audit_log
+        .as_deref()
+        .filter(|_| !args.dry_run)
+        .map(|path| {
+            AuditLog::open(path)
+                .with_context(|| format!("could not open audit log {}", path.display()))
+        })
+        .transpose()?;
+    if args.skip_existing && args.format != OutputFormat::Plain {
+        bail!("--skip-existing only works with plain output");
+    }
+    if args.issued_at && args.format == OutputFormat::Plain {
+        bail!("--issued-at needs json, tsv, xlsx or parquet output");
+    }
+    if args.delimiter.is_some() && args.format != OutputFormat::Tsv {
+        bail!("--delimiter only works with tsv output");
+    }
+    let mut errors = Vec::new();
+    if let Some(Err(e)) = args
+        .declaration_office
+        .as_deref()
+        .map(DeclarationOffice::new)
+    {
+        errors.push(e);
+    }
+    let traffic = args.profile.map(profile::traffic);
+    let (country_codes, _) = countries(&args, traffic);
+    for country_code in country_codes.iter().filter(|_| errors.is_empty()) {
+        if let Some(office) = args.declaration_office.as_deref() {
+            if args.check_office && !col::office_exists(country_code, office) {
+                bail!("office {office} is not in the customs office list for {country_code}");
+            }
+        }
+        if args.random_office && col::random_office(country_code).is_none() {
+            bail!("the customs office list has no offices for {country_code}");
+        }
+    }
     let combined = args.combined.as_deref();
-    let procedure = args
-        .procedure_category
-        .map(|proctg| match_procedure(&proctg, combined))
+    let rules = args
+        .rules
+        .as_deref()
+        .map(commands::load_rules)
+        .transpose()?
+        .unwrap_or_default();
+    let procedure = match args.procedure_category.as_deref() {
+        Some(proctg) => rules.match_procedure(proctg, combined).map(Some),
+        None => match_optional_procedure(None, combined),
+    }
+    .and_then(|procedure| match args.preset {
+        Some(preset) => preset::check_procedure(preset, procedure),
+        None => Ok(procedure),
+    })
+    .unwrap_or_else(|e| {
+        errors.push(e);
+        None
+    });
+    if let Some(preset) = args.preset {
+        let weighted = weights.iter().flat_map(|weights| &weights.procedures);
+        for &(procedure, _) in weighted {
+            if let Err(e) = preset::check_procedure(preset, Some(procedure)) {
+                errors.push(e);
+            }
+        }
+        for country_code in &country_codes {
+            if let Err(e) = preset::check_country(preset, country_code) {
+                errors.push(e);
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ArgumentErrors(errors).into());
+    }
+
+    let capacity = capacity(&args, traffic, weights.as_ref(), procedure);
+    let requested = args.number_of_mrns as u128;
+    if let Some(count) = args.count_per_procedure {
+        // Every procedure gets its own group, limited by the MRNs possible with its character
+        for &procedure in all_procedures() {
+            let capacity = self::capacity(&args, traffic, weights.as_ref(), Some(procedure));
+            if count as u128 > capacity {
+                bail!(
+                    "{count} MRNs per procedure were requested but only {capacity} distinct MRNs \
+                     are possible for procedure {} with these parameters, add countries or years \
+                     or drop the fixed office",
+                    procecure_category_to_char(procedure)
+                );
+            }
+        }
+    }
+    if requested > capacity {
+        bail!(
+            "{requested} MRNs were requested but only {capacity} distinct MRNs are possible with \
+             these parameters, add countries or years or drop the fixed office"
+        );
+    }
+    if requested > capacity / SAFE_DIVISOR {
+        eprintln!(
+            "warning: {requested} MRNs are more than half of the {capacity} distinct MRNs possible \
+             with these parameters, expect duplicates or slow generation"
+        );
+    }
+
+    if args.dry_run {
+        print!(
+            "{}",
+            plan::Plan {
+                args: &args,
+                countries: countries(&args, traffic),
+                procedures: procedures(traffic, weights.as_ref()),
+                procedure,
+                offices: weights.as_ref().map(|weights| &weights.offices),
+                invalid_rate: weights.as_ref().map_or(0.0, |weights| weights.invalid_rate),
+                capacity,
+            }
+        );
+        return Ok(());
+    }
+
+    let mut seen_filter = args
+        .seen_filter
+        .as_deref()
+        .map(|path| {
+            SeenFilter::load_or_new(path)
+                .with_context(|| format!("could not load seen filter {}", path.display()))
+        })
         .transpose()?;
 
-    for _ in 0..args.number_of_mrns {
-        let mrn: &str = &generate_random_mrn(&args.country_code, procedure, declaration_office)?;
-        println!("{mrn}");
+    let mut registry = args
+        .registry
+        .as_deref()
+        .map(|path| {
+            Registry::open(path)
+                .with_context(|| format!("could not open registry {}", path.display()))
+        })
+        .transpose()?;
+
+    let mut existing = match args.output.as_deref() {
+        Some(path) if args.skip_existing => read_existing(path)?,
+        _ => HashSet::new(),
+    };
+
+    let sink = match (args.split_by, args.output_dir.as_deref()) {
+        (Some(split_by), Some(dir)) => output::split_sink(
+            split_by,
+            dir,
+            args.format,
+            args.compress,
+            args.delimiter,
+            args.count_per_procedure.is_some(),
+        )?,
+        _ => output::sink(
+            args.format,
+            args.output.as_deref(),
+            args.append,
+            args.compress,
+            args.delimiter,
+            args.count_per_procedure.is_some(),
+        )?,
+    };
+
+    let fail_fast = args.failure.fail_fast(true);
+    let exec = args
+        .exec
+        .as_deref()
+        .map(|command| ExecHook::new(command, args.exec_jobs, fail_fast))
+        .transpose()?;
+    let writer = Writer::spawn(sink, exec, args.queue_size);
+    #[cfg(feature = "online")]
+    let mut batch = args.webhook.as_ref().map(|_| webhook::Batch::default());
+
+    let mut failed = 0;
+    // Handles a generated MRN, returning false once generation should stop
+    let mut send = |generated: Result<Generated>| -> Result<bool> {
+        match generated {
+            Ok(generated) => {
+                if let (Some(log), Some(parameters)) = (audit_log.as_mut(), parameters.as_ref()) {
+                    log.record(&generated.mrn, parameters, args.source.seed, None)?;
+                }
+                #[cfg(feature = "online")]
+                if let Some(batch) = batch.as_mut() {
+                    batch.add(&generated.mrn);
+                }
+                Ok(writer.send(generated))
+            }
+            Err(e) if !fail_fast => {
+                eprintln!("{e:#}");
+                failed += 1;
+                Ok(true)
+            }
+            Err(e) => Err(e),
+        }
+    };
+    if args.parallel {
+        let jobs = args
+            .jobs
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get));
+        pipeline::generate_parallel(
+            args.number_of_mrns,
+            jobs,
+            args.ordered,
+            |chunk, len| {
+                let mut generator =
+                    Generator::new(&args, traffic, weights.as_ref(), procedure, Some(chunk))?;
+                Ok((0..len).map(|_| generator.next(|_| false)).collect())
+            },
+            send,
+        )?;
+    } else {
+        let mut generator = Generator::new(&args, traffic, weights.as_ref(), procedure, None)?;
+        for _ in 0..args.number_of_mrns {
+            let generated = generator.next(|mrn| {
+                existing.contains(mrn)
+                    || registry.as_ref().is_some_and(|r| r.contains(mrn))
+                    || seen_filter.as_ref().is_some_and(|f| f.contains(mrn))
+            });
+            if let Ok(generated) = &generated {
+                if let Some(filter) = seen_filter.as_mut() {
+                    filter.insert(&generated.mrn);
+                }
+                if let Some(registry) = registry.as_mut() {
+                    registry.record(&generated.mrn)?;
+                }
+                if args.skip_existing {
+                    existing.insert(generated.mrn.clone());
+                }
+            }
+            // The writer stops on an error, which joining it returns
+            if !send(generated)? {
+                break;
+            }
+        }
+    }
+
+    // Persist the registry before the output is flushed, so an MRN is never
+    // handed out without being recorded
+    let outputs = writer.join();
+    if let Some(registry) = registry {
+        registry.commit().context("could not write registry")?;
+    }
+    if let Some(log) = audit_log {
+        log.commit().context("could not write audit log")?;
+    }
+    let (sink, exec) = outputs?;
+    let files = sink.finish()?;
+    if let Some(exec) = exec {
+        exec.finish()?;
+    }
+
+    // Only read by the webhook
+    #[cfg_attr(not(feature = "online"), allow(unused_variables))]
+    let manifest = match parameters.filter(|_| args.manifest) {
+        Some(parameters) => {
+            let dir = match (args.output_dir.as_deref(), args.output.as_deref()) {
+                (Some(dir), _) => dir,
+                (None, Some(path)) => path.parent().unwrap_or(Path::new("")),
+                (None, None) => unreachable!("--manifest requires an output"),
+            };
+            Some(manifest::write_manifest(dir, &files, parameters)?)
+        }
+        None => None,
+    };
+
+    #[cfg(feature = "online")]
+    if let (Some(url), Some(batch)) = (args.webhook.as_deref(), batch) {
+        let mut summary = batch.summary();
+        summary["failed"] = failed.into();
+        summary["manifest_sha256"] = manifest
+            .as_deref()
+            .map(manifest::sha256)
+            .transpose()?
+            .into();
+        webhook::Webhook::new(url).notify(&summary)?;
+    }
+
+    if let (Some(filter), Some(path)) = (seen_filter, args.seen_filter.as_deref()) {
+        filter
+            .save(path)
+            .with_context(|| format!("could not save seen filter {}", path.display()))?;
+    }
+
+    if failed > 0 {
+        bail!(
+            "{failed} of {} MRNs could not be generated",
+            args.number_of_mrns
+        );
     }
 
     Ok(())
 }
+
+/// Country codes to generate MRNs for and their weights, those of the traffic profile if there
+/// is one
+fn countries(args: &Args, traffic: Option<&Traffic>) -> (Vec<String>, Option<Vec<u32>>) {
+    match traffic {
+        Some(traffic) => {
+            let (codes, weights) = traffic.countries();
+            (codes, Some(weights))
+        }
+        None => (args.country_code.clone(), args.country_weights.clone()),
+    }
+}
+
+/// Procedures to generate MRNs for and their weights, those of the traffic profile or of the
+/// weights file if there is one
+fn procedures(
+    traffic: Option<&Traffic>,
+    weights: Option<&Weights>,
+) -> Option<(Vec<Procedure>, Vec<u32>)> {
+    match traffic {
+        Some(traffic) => Some(traffic.procedures()),
+        None => weights.and_then(Weights::procedures),
+    }
+}
+
+/// Divisor of the number of distinct MRNs possible for a run, above which it warns that the
+/// MRNs are running out
+const SAFE_DIVISOR: u128 = 2;
+
+/// Number of distinct MRNs a run can generate, over its countries, offices, procedures and years
+fn capacity(
+    args: &Args,
+    traffic: Option<&Traffic>,
+    weights: Option<&Weights>,
+    procedure: Option<Procedure>,
+) -> u128 {
+    let procedures = match procedures(traffic, weights) {
+        Some((procedures, _)) => procedures.into_iter().map(Some).collect(),
+        None => vec![procedure],
+    };
+    let years = match (args.year_range, args.spread_over) {
+        (Some(range), _) => range.years().len(),
+        (None, Some(range)) => (range.end.year() - range.start.year() + 1) as usize,
+        (None, None) => 1,
+    } as u128;
+    let namespaces = match args.source.namespace {
+        Some(_) => source::ALPHABET.len() as u128,
+        None => 1,
+    };
+
+    // Segments possible for a country and procedure, times the offices they may be issued by
+    let (country_codes, _) = countries(args, traffic);
+    let mut spaces = Vec::new();
+    for country_code in &country_codes {
+        let weighted = weights.and_then(|weights| weights.offices.get(country_code));
+        let (offices, office) = match (weighted, args.declaration_office.as_deref()) {
+            (Some(offices), _) => (
+                offices.len(),
+                offices.first().map(|(office, _)| office.clone()),
+            ),
+            _ if args.random_office => {
+                let offices = col::offices_for_country(country_code);
+                let office = offices
+                    .first()
+                    .map(|office| office.office_number().to_string());
+                (offices.len(), office)
+            }
+            (None, Some(office)) => (1, Some(office.to_string())),
+            (None, None) => (1, None),
+        };
+        let office = office.map(|office| match args.office_with_country {
+            true => office_reference(country_code, &office),
+            false => office,
+        });
+        for &procedure in &procedures {
+            let config = MrnConfig {
+                country_code,
+                procedure,
+                declaration_office: office
+                    .as_deref()
+                    .and_then(|office| DeclarationOffice::new(office).ok()),
+                year: None,
+            };
+            spaces.push((config.capacity() / namespaces, offices as u128));
+        }
+    }
+
+    let possible: u128 = spaces
+        .iter()
+        .map(|(segments, offices)| segments * offices)
+        .sum::<u128>()
+        * years;
+    match args.source.id_source {
+        IdSourceKind::Random => possible,
+        // One counter is shared by every MRN of the run, so it runs out after its segments
+        kind => {
+            let start = match kind {
+                IdSourceKind::Timestamp => 0,
+                _ => args.source.sequence_start as u128,
+            };
+            let segments = spaces.iter().map(|(segments, _)| *segments).max();
+            possible.min(segments.unwrap_or(0).saturating_sub(start))
+        }
+    }
+}
+
+/// Picks the country, office, procedure and issue date of each MRN and generates it
+struct Generator<'a> {
+    args: &'a Args,
+    procedure: Option<Procedure>,
+    source: Box<dyn IdSource>,
+    years: Option<WeightedChoice<u8>>,
+    countries: WeightedChoice<String>,
+    procedures: Option<WeightedChoice<Procedure>>,
+    spread: Option<DateSpread>,
+    /// Source of the --with-lrn LRNs, apart from the MRNs so that they don't shift the MRNs
+    lrns: Option<RandomSource<StdRng>>,
+    /// Offices of the countries with offices in the weights file, by country code
+    offices: HashMap<String, WeightedChoice<String>>,
+    /// Whether an MRN gets a wrong check digit, for the invalid rate of the weights file
+    invalid: Option<(Bernoulli, StdRng)>,
+    /// Number of MRNs generated so far, to move on to the next procedure with
+    /// --count-per-procedure or to the next country with counts
+    generated: usize,
+    /// Number of MRNs generated once each country with counts is done
+    country_ends: Option<Vec<usize>>,
+}
+
+impl<'a> Generator<'a> {
+    /// Creates the generator of a run or, with --parallel, of one of its chunks, seeded apart
+    /// from the other chunks
+    fn new(
+        args: &'a Args,
+        traffic: Option<&Traffic>,
+        weights: Option<&Weights>,
+        procedure: Option<Procedure>,
+        chunk: Option<usize>,
+    ) -> Result<Self> {
+        let (seed, source) = match chunk {
+            None => (args.source.seed, commands::id_source(&args.source)),
+            Some(chunk) => {
+                let seed = args
+                    .source
+                    .seed
+                    .map(|seed| seed ^ (chunk as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+                let source = commands::id_source(&IdSourceArgs {
+                    id_source: args.source.id_source,
+                    // Chunks of the permuted source share its key, to stay slices of one permutation
+                    seed: match args.source.id_source {
+                        IdSourceKind::Permuted => args.source.seed,
+                        _ => seed,
+                    },
+                    sequence_start: args.source.sequence_start
+                        + (chunk * pipeline::CHUNK_SIZE) as u64,
+                    namespace: args.source.namespace,
+                });
+                (seed, source)
+            }
+        };
+
+        let years = args
+            .year_range
+            .map(|range| WeightedChoice::new(range.years(), args.year_weights.as_deref(), seed))
+            .transpose()?;
+
+        // Offset the seed so that countries and years aren't picked in lockstep
+        let (country_codes, country_weights) = countries(args, traffic);
+        let countries = WeightedChoice::new(
+            country_codes,
+            country_weights.as_deref(),
+            seed.map(|seed| seed.wrapping_add(1)),
+        )?;
+
+        let procedures = procedures(traffic, weights)
+            .map(|(procedures, weights)| {
+                WeightedChoice::new(
+                    procedures,
+                    Some(&weights),
+                    seed.map(|seed| seed.wrapping_add(3)),
+                )
+            })
+            .transpose()?;
+
+        let spread = args
+            .spread_over
+            .map(|range| {
+                DateSpread::new(
+                    range.start,
+                    range.end,
+                    seed.map(|seed| seed.wrapping_add(2)),
+                )
+            })
+            .transpose()?;
+
+        let lrns = args.with_lrn.then(|| {
+            RandomSource::seeded(seed.map_or_else(rand::random, |seed| seed.wrapping_add(4)))
+        });
+
+        let offices = weights
+            .into_iter()
+            .flat_map(|weights| &weights.offices)
+            .enumerate()
+            .map(|(i, (country_code, offices))| {
+                let (offices, weights): (Vec<_>, Vec<_>) = offices.iter().cloned().unzip();
+                let seed = seed.map(|seed| seed.wrapping_add(6 + i as u64));
+                Ok((
+                    country_code.clone(),
+                    WeightedChoice::new(offices, Some(&weights), seed)?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        let invalid = weights
+            .filter(|weights| weights.invalid_rate > 0.0)
+            .map(|weights| {
+                let rng = StdRng::seed_from_u64(
+                    seed.map_or_else(rand::random, |seed| seed.wrapping_add(5)),
+                );
+                Ok::<_, anyhow::Error>((Bernoulli::new(weights.invalid_rate)?, rng))
+            })
+            .transpose()?;
+
+        Ok(Generator {
+            args,
+            procedure,
+            source,
+            years,
+            countries,
+            procedures,
+            spread,
+            lrns,
+            offices,
+            invalid,
+            generated: 0,
+            country_ends: args.country_counts.as_ref().map(|counts| {
+                counts
+                    .iter()
+                    .scan(0, |end, count| {
+                        *end += count;
+                        Some(*end)
+                    })
+                    .collect()
+            }),
+        })
+    }
+
+    /// Generates the next MRN, generating it again while `seen` returns true for it
+    fn next(&mut self, seen: impl Fn(&str) -> bool) -> Result<Generated> {
+        let args = self.args;
+        let issued_at = self.spread.as_mut().map(DateSpread::pick);
+        let index = self.generated;
+        self.generated += 1;
+        let procedure = match (args.count_per_procedure, self.procedures.as_mut()) {
+            (Some(count), _) => Some(all_procedures()[index / count]),
+            (None, Some(procedures)) => Some(*procedures.pick()),
+            (None, None) => self.procedure,
+        };
+        let mut attempts = 0;
+        loop {
+            let country_code = match &self.country_ends {
+                Some(ends) => args.country_code[ends.partition_point(|&end| end <= index)].as_str(),
+                None => self.countries.pick().as_str(),
+            };
+            let weighted = self.offices.get_mut(country_code);
+            let declaration_office = match (weighted, args.declaration_office.as_deref()) {
+                (Some(offices), _) => Some(offices.pick().clone()),
+                _ if args.random_office => col::random_office(country_code)
+                    .map(|office| office.office_number().to_string()),
+                (None, Some(office)) => Some(office.to_string()),
+                (None, None) => None,
+            }
+            .map(|office| match args.office_with_country {
+                true => office_reference(country_code, &office),
+                false => office,
+            });
+            let config = MrnConfig {
+                country_code,
+                procedure,
+                declaration_office: declaration_office
+                    .as_deref()
+                    .map(DeclarationOffice::new)
+                    .transpose()?,
+                year: match issued_at {
+                    Some(date) => Some((date.year() % 100) as u8),
+                    None => self.years.as_mut().map(|years| *years.pick()),
+                },
+            };
+            let mut mrn = generate_mrn(&config, &mut *self.source)?;
+            if !seen(&mrn) {
+                if let Some((invalid, rng)) = self.invalid.as_mut() {
+                    if rng.sample(*invalid) {
+                        spoil_check_digit(&mut mrn, rng);
+                    }
+                }
+                return Ok(Generated {
+                    mrn,
+                    procedure,
+                    declaration_office,
+                    issued_at: issued_at
+                        .filter(|_| args.issued_at)
+                        .map(|date| date.to_string()),
+                    lrn: self
+                        .lrns
+                        .as_mut()
+                        .map(|lrns| generate_lrn_with_source("", LRN_MAX_LENGTH, lrns))
+                        .transpose()?,
+                });
+            }
+            attempts += 1;
+            if attempts == MAX_ATTEMPTS {
+                bail!("could not generate an unseen MRN after {MAX_ATTEMPTS} attempts");
+            }
+        }
+    }
+}
+
+/// Replaces the check digit of an MRN with another digit, so that it fails validation
+fn spoil_check_digit(mrn: &mut String, rng: &mut StdRng) {
+    let digit = mrn
+        .pop()
+        .and_then(|c| c.to_digit(10))
+        .expect("MRNs end with a check digit");
+    let spoiled = (digit + rng.gen_range(1..10)) % 10;
+    mrn.push(char::from_digit(spoiled, 10).expect("the digit is below 10"));
+}
+
+/// Reads the MRNs already present in a file, one per line
+fn read_existing(path: &Path) -> Result<HashSet<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(HashSet::new()),
+        Err(e) => Err(e).with_context(|| format!("could not read output file {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use clap::Parser;
+
+    /// Peak of allocated bytes while generating MRNs to /dev/null
+    fn peak_bytes(number_of_mrns: &str) -> usize {
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK",
+            "-n",
+            number_of_mrns,
+            "--id-source",
+            "permuted",
+            "--output",
+            "/dev/null",
+        ]);
+        bench::reset_peak_bytes();
+        generate(args).unwrap();
+        bench::peak_bytes()
+    }
+
+    #[test]
+    fn capacity_test() {
+        let capacity = |extra: &[&str]| {
+            let mut argv = vec!["mrn-generator", "-p", "B1", "-o", "DK004700"];
+            argv.extend(extra);
+            let args = Args::parse_from(argv);
+            capacity(&args, None, None, Some(Procedure::ExportOnly))
+        };
+        assert_eq!(36u128.pow(4), capacity(&["-c", "DK"]));
+        assert_eq!(36u128.pow(3), capacity(&["-c", "DK", "--namespace", "3"]));
+        assert_eq!(
+            2 * 3 * 36u128.pow(4),
+            capacity(&["-c", "DK,IT", "--year-range", "2023..=2025"])
+        );
+        // The counter of the sequential source is shared by the countries
+        assert_eq!(
+            36u128.pow(4) - 100,
+            capacity(&[
+                "-c",
+                "DK,IT",
+                "--id-source",
+                "sequential",
+                "--sequence-start",
+                "100"
+            ])
+        );
+
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK",
+            "-o",
+            "DK004700",
+            "-p",
+            "B1",
+            "-n",
+            "2000000",
+        ]);
+        assert!(generate(args).is_err());
+
+        // Each office of the weights file is issued the segments of a fixed office
+        let weights = Weights {
+            offices: std::collections::BTreeMap::from([(
+                "DK".to_string(),
+                vec![("004700".to_string(), 2), ("002000".to_string(), 1)],
+            )]),
+            ..Default::default()
+        };
+        let args = Args::parse_from(["mrn-generator", "-c", "DK", "-p", "B1"]);
+        assert_eq!(
+            2 * 36u128.pow(6),
+            super::capacity(&args, None, Some(&weights), Some(Procedure::ExportOnly))
+        );
+    }
+
+    #[test]
+    fn ordered_test() {
+        let generate_with = |jobs: &str| {
+            let path = std::env::temp_dir().join(format!("mrn-generator-ordered-{jobs}.txt"));
+            let args = Args::parse_from([
+                "mrn-generator",
+                "-c",
+                "DK,GR",
+                "-n",
+                "5000",
+                "--seed",
+                "7",
+                "--parallel",
+                "--jobs",
+                jobs,
+                "--ordered",
+                "--output",
+                path.to_str().unwrap(),
+            ]);
+            generate(args).unwrap();
+            let mrns = fs::read_to_string(&path).unwrap();
+            fs::remove_file(path).unwrap();
+            mrns
+        };
+        let mrns = generate_with("1");
+        assert_eq!(5000, mrns.lines().count());
+        assert_eq!(mrns, generate_with("4"));
+    }
+
+    #[test]
+    fn count_per_procedure_test() {
+        let path = std::env::temp_dir().join(format!(
+            "mrn-generator-per-procedure-{}.txt",
+            std::process::id()
+        ));
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK",
+            "--count-per-procedure",
+            "2",
+            "--output",
+            path.to_str().unwrap(),
+        ]);
+        generate(args).unwrap();
+        let lines = fs::read_to_string(&path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        let lines: Vec<(char, &str)> = lines
+            .lines()
+            .map(|line| {
+                let (label, mrn) = line.split_once(' ').unwrap();
+                (label.chars().next().unwrap(), mrn)
+            })
+            .collect();
+        assert_eq!(2 * all_procedures().len(), lines.len());
+        for (group, &procedure) in lines.chunks(2).zip(all_procedures()) {
+            for &(label, mrn) in group {
+                assert_eq!(procecure_category_to_char(procedure), label);
+                assert_eq!(Ok(()), validate_mrn(mrn));
+                assert_eq!(&mrn[layout::PROCEDURE], label.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn count_per_procedure_capacity_test() {
+        // Fits in the segments of the combined procedures, but not in those of a single one
+        let count = (36u32.pow(4) + 1).to_string();
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK",
+            "-o",
+            "DK004700",
+            "--count-per-procedure",
+            &count,
+        ]);
+        let e = generate(args).unwrap_err();
+        assert!(e.to_string().contains("per procedure"), "{e}");
+    }
+
+    #[test]
+    fn all_countries_test() {
+        let countries = |argv: &[&str]| {
+            let path = std::env::temp_dir().join(format!(
+                "mrn-generator-all-countries-{}.txt",
+                std::process::id()
+            ));
+            let mut args = vec!["mrn-generator", "--output", path.to_str().unwrap()];
+            args.extend(argv);
+            generate(Args::parse_from(args)).unwrap();
+            let mrns = fs::read_to_string(&path).unwrap();
+            fs::remove_file(path).unwrap();
+            mrns.lines()
+                .map(|mrn| mrn[layout::COUNTRY_CODE].to_string())
+                .collect::<Vec<_>>()
+        };
+        let codes: Vec<&str> = country::all_countries()
+            .iter()
+            .map(|country| country.code)
+            .collect();
+        assert_eq!(codes, countries(&["--all-countries"]));
+        assert_eq!(
+            ["IT", "IT", "DK", "DK"],
+            *countries(&["--all-countries", "2", "-c", "IT,DK"])
+        );
+    }
+
+    #[test]
+    fn country_counts_test() {
+        let path = std::env::temp_dir().join(format!(
+            "mrn-generator-country-counts-{}.txt",
+            std::process::id()
+        ));
+        let mut args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK,IT,GR",
+            "--output",
+            path.to_str().unwrap(),
+        ]);
+        // As read from a countries file of DK,3 IT,0 and GR,2
+        args.country_counts = Some(vec![3, 0, 2]);
+        generate(args).unwrap();
+        let mrns = fs::read_to_string(&path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        let countries: Vec<&str> = mrns.lines().map(|mrn| &mrn[layout::COUNTRY_CODE]).collect();
+        assert_eq!(["DK", "DK", "DK", "GR", "GR"], *countries);
+    }
+
+    #[test]
+    #[ignore = "generates 100M MRNs, run with cargo test --release -- --ignored"]
+    fn constant_memory_test() {
+        let small = peak_bytes("1000");
+        let large = peak_bytes("100_000_000");
+        assert!(
+            large <= small + 64 * 1024,
+            "{small} bytes, then {large} bytes"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Manifests of the generated files, so that test datasets can be verified and regenerated
+
+use crate::parser::Args;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use mrn_generator::sink::WrittenFile;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest file, written next to the output files
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Writes the manifest of the files into their directory, with their row counts and SHA-256
+/// checksums and the parameters they were generated with, returning its path
+pub fn write_manifest(dir: &Path, files: &[WrittenFile], parameters: Value) -> Result<PathBuf> {
+    let files = files
+        .iter()
+        .map(|file| {
+            Ok(json!({
+                "path": file.path.file_name().map(|name| name.to_string_lossy()),
+                "rows": file.rows,
+                "sha256": sha256(&file.path)?,
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let manifest = json!({
+        "generator": concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")),
+        "parameters": parameters,
+        "files": files,
+    });
+
+    let path = dir.join(MANIFEST_FILE);
+    fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")
+        .with_context(|| format!("could not write manifest {}", path.display()))?;
+    Ok(path)
+}
+
+/// Parameters of an MRN generation run
+pub fn parameters(args: &Args) -> Value {
+    json!({
+        "country_codes": args.country_code,
+        "country_weights": args.country_weights,
+        "countries_file": args.countries_file,
+        "country_counts": args.country_counts,
+        "weights": args.weights,
+        "number_of_mrns": args.number_of_mrns,
+        "count_per_procedure": args.count_per_procedure,
+        "all_countries": args.all_countries,
+        "procedure_category": args.procedure_category,
+        "combined": args.combined,
+        "declaration_office": args.declaration_office,
+        "office_with_country": args.office_with_country,
+        "random_office": args.random_office,
+        "preset": args.preset.map(value_name),
+        "profile": args.profile.map(value_name),
+        "year_range": args.year_range.map(|range| format!("{}..={}", range.start, range.end)),
+        "year_weights": args.year_weights,
+        "spread_over": args.spread_over.map(|range| format!("{}..{}", range.start, range.end)),
+        "issued_at": args.issued_at,
+        "with_lrn": args.with_lrn,
+        "id_source": value_name(args.source.id_source),
+        "seed": args.source.seed,
+        "sequence_start": args.source.sequence_start,
+        "namespace": args.source.namespace,
+        "format": value_name(args.format),
+        "compress": args.compress.map(value_name),
+        "split_by": args.split_by.map(value_name),
+    })
+}
+
+/// Command line name of a value
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Hex encoded SHA-256 checksum of a file
+pub fn sha256(path: &Path) -> Result<String> {
+    let mut file = File::open(path)
+        .with_context(|| format!("could not read output file {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn write_manifest_test() {
+        let dir = std::env::temp_dir().join(format!("mrn-manifest-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("DK.txt");
+        fs::write(&path, "abc").unwrap();
+
+        let files = [WrittenFile { path, rows: 1 }];
+        write_manifest(&dir, &files, json!({"seed": 7})).unwrap();
+
+        let manifest: Value =
+            serde_json::from_str(&fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap()).unwrap();
+        assert_eq!(
+            json!([{
+                "path": "DK.txt",
+                "rows": 1,
+                "sha256": "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            }]),
+            manifest["files"]
+        );
+        assert_eq!(json!(7), manifest["parameters"]["seed"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
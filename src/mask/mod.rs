@@ -0,0 +1,72 @@
+//! Masks selecting MRNs by some of their characters, e.g. `??DK0047*` for the MRNs of an office
+
+/// Character of a mask matching any one character
+pub const ANY_CHARACTER: char = '?';
+
+/// Character of a mask matching any number of characters, none included
+pub const ANY_CHARACTERS: char = '*';
+
+/// Checks whether an MRN matches a mask, where `?` stands for any one character and `*` for any
+/// number of them. Letters match regardless of case.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::matches;
+///
+/// assert!(matches("26DK0047002M8N41B6", "??DK0047*"));
+/// assert!(matches("26DK0047002M8N41B6", "*b?"));
+/// assert!(!matches("26DK0047002M8N41B6", "??IT*"));
+/// assert!(!matches("26DK0047002M8N41B6", "26DK"));
+/// ```
+pub fn matches(mrn: &str, mask: &str) -> bool {
+    let (mrn, mask) = (mrn.as_bytes(), mask.as_bytes());
+    let (mut m, mut k) = (0, 0);
+    // Position after the last `*` of the mask and the MRN position it was last tried at, to
+    // backtrack to when the rest of the mask doesn't match
+    let mut backtrack = None;
+    while m < mrn.len() {
+        match mask.get(k) {
+            Some(&c) if c == ANY_CHARACTERS as u8 => {
+                k += 1;
+                backtrack = Some((k, m));
+            }
+            Some(&c) if c == ANY_CHARACTER as u8 || c.eq_ignore_ascii_case(&mrn[m]) => {
+                m += 1;
+                k += 1;
+            }
+            _ => match backtrack {
+                // Let the `*` take one more character
+                Some((after_star, tried)) => {
+                    k = after_star;
+                    m = tried + 1;
+                    backtrack = Some((after_star, m));
+                }
+                None => return false,
+            },
+        }
+    }
+    mask[k..].iter().all(|&c| c == ANY_CHARACTERS as u8)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn matches_test() {
+        let mrn = "26DK0047002M8N41B6";
+        assert!(matches(mrn, mrn));
+        assert!(matches(mrn, "26dk0047002m8n41b6"));
+        assert!(matches(mrn, "*"));
+        assert!(matches(mrn, "??????????????????"));
+        assert!(!matches(mrn, "???????????????????"));
+        assert!(matches(mrn, "26*41*6"));
+        assert!(matches(mrn, "*4*"));
+        assert!(matches(mrn, "**DK**"));
+        assert!(!matches(mrn, "*DK"));
+        assert!(!matches(mrn, "26DK0047002M8N41B"));
+        assert!(!matches(mrn, ""));
+        assert!(matches("", "*"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Model Context Protocol server exposing MRN generation, validation and parsing as tools
+//!
+//! Messages are JSON-RPC 2.0 requests and responses, one per line on standard input and output.
+
+use anyhow::Result;
+use chrono::{Datelike, Utc};
+use mrn_generator::country::resolve_country;
+use mrn_generator::i18n::Localizer;
+use mrn_generator::{
+    generate_random_mrn, match_optional_procedure, procedure_description, validate_mrn, Mrn,
+    MrnGeneratorError,
+};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+/// Protocol version implemented by the server
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Maximum number of MRNs generated by one tool call
+const MAX_GENERATED: u64 = 1000;
+
+/// Serves requests from standard input until it is closed
+pub fn serve(localizer: &Localizer) -> Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => handle(&request, localizer),
+            Err(e) => Some(error_response(Value::Null, -32700, &e.to_string())),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Response to a request, None for notifications
+fn handle(request: &Value, localizer: &Localizer) -> Option<Value> {
+    let id = request.get("id")?.clone();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let result = match request["method"].as_str().unwrap_or_default() {
+        "initialize" => json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {"tools": {}},
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({"tools": tools()}),
+        "tools/call" => call_tool(&params, localizer),
+        method => {
+            return Some(error_response(
+                id,
+                -32601,
+                &format!("unknown method {method}"),
+            ))
+        }
+    };
+    Some(json!({"jsonrpc": "2.0", "id": id, "result": result}))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
+
+/// Descriptions of the tools
+fn tools() -> Value {
+    let mrn_input = json!({
+        "type": "object",
+        "properties": {"mrn": {"type": "string", "description": "MRN, 18 characters"}},
+        "required": ["mrn"],
+    });
+    json!([
+        {
+            "name": "generate_mrn",
+            "description": "Generate valid MRNs for testing",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "country_code": {"type": "string", "description": "Two letter country code or country name"},
+                    "count": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_GENERATED,
+                        "description": "Number of MRNs, 1 by default",
+                    },
+                    "procedure_category": {"type": "string", "description": "Declaration category, e.g. B1"},
+                    "combined": {"type": "string", "description": "Combined procedure category"},
+                    "declaration_office": {"type": "string", "description": "Customs office of declaration"},
+                },
+                "required": ["country_code"],
+            },
+        },
+        {
+            "name": "validate_mrn",
+            "description": "Check the structure and check digit of an MRN",
+            "inputSchema": mrn_input,
+        },
+        {
+            "name": "parse_mrn",
+            "description": "Split an MRN into its year, country code, serial, procedure and check digit",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "mrn": {"type": "string", "description": "MRN, 18 characters"},
+                    "retention_years": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Years MRNs are kept, to flag an MRN issued before them",
+                    },
+                },
+                "required": ["mrn"],
+            },
+        },
+    ])
+}
+
+/// Result of a tool call, errors are reported in the result so the model can see them
+fn call_tool(params: &Value, localizer: &Localizer) -> Value {
+    let arguments = &params["arguments"];
+    let result = match params["name"].as_str().unwrap_or_default() {
+        "generate_mrn" => generate(arguments),
+        "validate_mrn" => string_argument(arguments, "mrn").and_then(|mrn| {
+            validate_mrn(mrn)
+                .map(|()| format!("{mrn} is a valid MRN"))
+                .map_err(ToolError::from)
+        }),
+        "parse_mrn" => string_argument(arguments, "mrn")
+            .and_then(|mrn| parse(mrn, arguments["retention_years"].as_u64())),
+        name => Err(ToolError::Message(format!("unknown tool {name}"))),
+    };
+    let (text, is_error) = match result {
+        Ok(text) => (text, false),
+        Err(ToolError::Mrn(e)) => (localizer.error(&e), true),
+        Err(ToolError::Message(message)) => (message, true),
+    };
+    json!({"content": [{"type": "text", "text": text}], "isError": is_error})
+}
+
+enum ToolError {
+    Mrn(MrnGeneratorError),
+    Message(String),
+}
+
+impl From<MrnGeneratorError> for ToolError {
+    fn from(e: MrnGeneratorError) -> Self {
+        ToolError::Mrn(e)
+    }
+}
+
+fn string_argument<'a>(arguments: &'a Value, name: &str) -> Result<&'a str, ToolError> {
+    arguments[name]
+        .as_str()
+        .ok_or_else(|| ToolError::Message(format!("missing string argument {name}")))
+}
+
+fn generate(arguments: &Value) -> Result<String, ToolError> {
+    let country_code = resolve_country(string_argument(arguments, "country_code")?)?;
+    let count = match &arguments["count"] {
+        Value::Null => 1,
+        count => count
+            .as_u64()
+            .filter(|count| (1..=MAX_GENERATED).contains(count))
+            .ok_or_else(|| {
+                ToolError::Message(format!("count must be between 1 and {MAX_GENERATED}"))
+            })?,
+    };
+    let procedure = match_optional_procedure(
+        arguments["procedure_category"].as_str(),
+        arguments["combined"].as_str(),
+    )?;
+    let declaration_office = arguments["declaration_office"].as_str();
+
+    let mrns = (0..count)
+        .map(|_| generate_random_mrn(&country_code, procedure, declaration_office))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(mrns.join("\n"))
+}
+
+fn parse(mrn: &str, retention_years: Option<u64>) -> Result<String, ToolError> {
+    let mrn = Mrn::parse_strict(mrn)?;
+    let components = mrn.components();
+    let current_year = Utc::now().year();
+    let year = components
+        .issue_year(current_year)
+        .expect("a valid MRN has a two digit year");
+    Ok(json!({
+        "year": year.to_string(),
+        "issued_between": [format!("{year}-01-01"), format!("{year}-12-31")],
+        // Only once the retention period is over for the whole issue year
+        "older_than_retention": retention_years
+            .map(|retention| i64::from(current_year) > i64::from(year) + retention as i64),
+        "country_code": components.country_code,
+        "serial": components.serial,
+        "procedure_character": components.procedure_char.to_string(),
+        "procedure": components.procedure.map(procedure_description),
+        "check_digit": components.check_digit.to_string(),
+    })
+    .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use mrn_generator::i18n::Lang;
+
+    fn call(name: &str, arguments: Value) -> Value {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {"name": name, "arguments": arguments},
+        });
+        handle(&request, &Localizer::new(Lang::En)).unwrap()["result"].clone()
+    }
+
+    #[test]
+    fn tools_test() {
+        let result = call("generate_mrn", json!({"country_code": "DK", "count": 3}));
+        assert_eq!(json!(false), result["isError"]);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.lines().all(|mrn| validate_mrn(mrn).is_ok()));
+        assert_eq!(3, text.lines().count());
+
+        let result = call(
+            "generate_mrn",
+            json!({"country_code": "DK", "count": 100000}),
+        );
+        assert_eq!(json!(true), result["isError"]);
+
+        let result = call("validate_mrn", json!({"mrn": "22ITZXBZYUTJFLJXK7"}));
+        assert_eq!(json!(true), result["isError"]);
+
+        let result = call("parse_mrn", json!({"mrn": "22ITZXBZYUTJFLJXK6"}));
+        let parsed: Value =
+            serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(json!("IT"), parsed["country_code"]);
+        assert_eq!(json!("2022"), parsed["year"]);
+        assert_eq!(json!(null), parsed["older_than_retention"]);
+
+        let result = call(
+            "parse_mrn",
+            json!({"mrn": "22ITZXBZYUTJFLJXK6", "retention_years": 1}),
+        );
+        let parsed: Value =
+            serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(json!(true), parsed["older_than_retention"]);
+        assert_eq!(
+            json!(["2022-01-01", "2022-12-31"]),
+            parsed["issued_between"]
+        );
+    }
+
+    #[test]
+    fn protocol_test() {
+        let localizer = Localizer::new(Lang::En);
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert_eq!(None, handle(&notification, &localizer));
+
+        let request = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+        assert_eq!(
+            3,
+            handle(&request, &localizer).unwrap()["result"]["tools"]
+                .as_array()
+                .unwrap()
+                .len()
+        );
+
+        let request = json!({"jsonrpc": "2.0", "id": 3, "method": "resources/list"});
+        assert_eq!(
+            json!(-32601),
+            handle(&request, &localizer).unwrap()["error"]["code"]
+        );
+    }
+}
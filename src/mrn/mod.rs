@@ -0,0 +1,243 @@
+//! A validated MRN
+
+use crate::{
+    capitalize, is_mrn_valid, layout, parse_components, procecure_category_to_char, validate_mrn,
+    DeclarationOffice, MrnComponents, MrnGeneratorError, Procedure,
+};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+/// An MRN that is structurally valid and has a correct check digit
+///
+/// It can only be built by validating, so holding one is proof of validity.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::Mrn;
+///
+/// let mrn = Mrn::parse_strict("22ITZXBZYUTJFLJXK6").unwrap();
+/// assert_eq!("IT", mrn.components().country_code);
+/// assert!(Mrn::try_from("22ITZXBZYUTJFLJXK7").is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
+pub struct Mrn(String);
+
+impl Mrn {
+    /// Validates an MRN, see [validate_mrn]
+    pub fn parse_strict(mrn: &str) -> Result<Self, MrnGeneratorError> {
+        validate_mrn(mrn)?;
+        Ok(Mrn(mrn.to_string()))
+    }
+
+    /// Segments of the MRN
+    pub fn components(&self) -> MrnComponents<'_> {
+        parse_components(&self.0).expect("a valid MRN has all its segments")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// The MRN issued by another country, with its check digit recomputed
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::Mrn;
+    ///
+    /// let mrn = Mrn::parse_strict("26DK0047002M8N41B6").unwrap();
+    /// assert_eq!("26IT0047002M8N41B7", mrn.with_country("it").unwrap().as_str());
+    /// assert!(mrn.with_country("I").is_err());
+    /// ```
+    pub fn with_country(&self, country_code: &str) -> Result<Self, MrnGeneratorError> {
+        if country_code.len() != 2 {
+            return Err(MrnGeneratorError::CountryCodeLength(
+                country_code.to_string(),
+            ));
+        }
+        self.with_replaced(layout::COUNTRY_CODE, &capitalize(country_code))
+    }
+
+    /// The MRN issued in another year, given by its last two digits, with its check digit
+    /// recomputed
+    pub fn with_year(&self, year: u8) -> Result<Self, MrnGeneratorError> {
+        if year > 99 {
+            return Err(MrnGeneratorError::IssueYear(year));
+        }
+        self.with_replaced(layout::YEAR, &format!("{year:02}"))
+    }
+
+    /// The MRN issued by another declaration office, with its check digit recomputed. The office
+    /// replaces the start of the serial, eight characters of it if it has a country code.
+    pub fn with_office(&self, office: DeclarationOffice) -> Self {
+        let office = office.as_str();
+        self.with_replaced(
+            layout::SERIAL.start..layout::SERIAL.start + office.len(),
+            office,
+        )
+        .expect("declaration offices are digits and capital letters")
+    }
+
+    /// The MRN of another procedure, with its check digit recomputed. An MRN generated without a
+    /// procedure gets one in place of the last character of its serial.
+    pub fn with_procedure(&self, procedure: Procedure) -> Self {
+        let procedure = procecure_category_to_char(procedure);
+        self.with_replaced(layout::PROCEDURE, procedure.encode_utf8(&mut [0; 4]))
+            .expect("procedure characters are capital letters")
+    }
+
+    /// Replaces some characters of the MRN and recomputes its check digit, validating the result
+    fn with_replaced(
+        &self,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Result<Self, MrnGeneratorError> {
+        let mut mrn = self.0.clone();
+        mrn.replace_range(range, replacement);
+        mrn.replace_range(layout::CHECK_DIGIT, "0");
+        if let Some(check_digit) = is_mrn_valid(&mrn)? {
+            mrn.replace_range(layout::CHECK_DIGIT, check_digit.encode_utf8(&mut [0; 4]));
+        }
+        Mrn::try_from(mrn)
+    }
+}
+
+impl TryFrom<&str> for Mrn {
+    type Error = MrnGeneratorError;
+
+    fn try_from(mrn: &str) -> Result<Self, Self::Error> {
+        Mrn::parse_strict(mrn)
+    }
+}
+
+impl TryFrom<String> for Mrn {
+    type Error = MrnGeneratorError;
+
+    fn try_from(mrn: String) -> Result<Self, Self::Error> {
+        validate_mrn(&mrn)?;
+        Ok(Mrn(mrn))
+    }
+}
+
+impl FromStr for Mrn {
+    type Err = MrnGeneratorError;
+
+    fn from_str(mrn: &str) -> Result<Self, Self::Err> {
+        Mrn::parse_strict(mrn)
+    }
+}
+
+impl AsRef<str> for Mrn {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Mrn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Mrn> for String {
+    fn from(mrn: Mrn) -> Self {
+        mrn.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Mrn {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// Deserializing an MRN validates it
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Mrn {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mrn = String::deserialize(deserializer)?;
+        Mrn::try_from(mrn).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn mrn_test() {
+        let mrn: Mrn = "26DK0047002M8N41B6".parse().unwrap();
+        assert_eq!("26DK0047002M8N41B6", mrn.to_string());
+        assert_eq!('B', mrn.components().procedure_char);
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidCheckDigit {
+                mrn: "26DK0047002M8N41B3".to_string(),
+                expected: '6'
+            }),
+            Mrn::try_from("26DK0047002M8N41B3".to_string())
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::MrnLength("26DK".to_string())),
+            Mrn::parse_strict("26DK")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidCharacter {
+                position: 4,
+                found: 'z'
+            }),
+            Mrn::parse_strict("22ITzXBZYUTJFLJXK1")
+        );
+    }
+
+    #[test]
+    fn with_test() {
+        let mrn: Mrn = "26DK0047002M8N41B6".parse().unwrap();
+        let changed = mrn
+            .with_year(5)
+            .unwrap()
+            .with_country("IT")
+            .unwrap()
+            .with_office(DeclarationOffice::new("IT123456").unwrap())
+            .with_procedure(Procedure::ImportDeclarationOnly);
+        assert_eq!("05ITIT1234568N41R", &changed.as_str()[..17]);
+        assert_eq!(Ok(()), validate_mrn(changed.as_str()));
+        assert_eq!(mrn, mrn.with_year(26).unwrap());
+
+        assert_eq!(Err(MrnGeneratorError::IssueYear(100)), mrn.with_year(100));
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidCountryCode(
+                "26120047002M8N41B0".to_string()
+            )),
+            mrn.with_country("12")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn mrn_serde_test() {
+        use serde::de::value::{Error as ValueError, StrDeserializer};
+        use serde::de::IntoDeserializer;
+        use serde::Deserialize;
+
+        let deserialize = |mrn: &str| {
+            let mrn: StrDeserializer<ValueError> = mrn.into_deserializer();
+            Mrn::deserialize(mrn)
+        };
+        assert_eq!(
+            Ok("26DK0047002M8N41B6"),
+            deserialize("26DK0047002M8N41B6").as_ref().map(Mrn::as_str)
+        );
+        assert!(deserialize("26DK0047002M8N41B3")
+            .unwrap_err()
+            .to_string()
+            .contains("check digit should be 6"));
+    }
+}
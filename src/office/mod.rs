@@ -0,0 +1,98 @@
+//! Customs offices of declaration embedded in MRNs
+
+use crate::MrnGeneratorError;
+use alloc::string::ToString;
+use core::fmt;
+
+/// Length of an office number, e.g. `004700`
+pub const OFFICE_NUMBER_LENGTH: usize = 6;
+
+/// A customs office of declaration, an office number optionally preceded by its country code as
+/// in customs office list reference numbers, e.g. `004700` or `DK004700`
+///
+/// ## Example
+/// ```
+/// use mrn_generator::DeclarationOffice;
+///
+/// let office = DeclarationOffice::new("DK004700").unwrap();
+/// assert_eq!(Some("DK"), office.country_prefix());
+/// assert_eq!("004700", office.office_number());
+/// assert!(DeclarationOffice::new("0047").is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DeclarationOffice<'a>(&'a str);
+
+impl<'a> DeclarationOffice<'a> {
+    /// Checks that the office is six digits or capital letters, optionally preceded by a two
+    /// letter country code
+    pub fn new(office: &'a str) -> Result<Self, MrnGeneratorError> {
+        if let Some((position, found)) = office
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_digit() && !c.is_ascii_uppercase())
+        {
+            return Err(MrnGeneratorError::InvalidCharacter { position, found });
+        }
+        let prefix_len = office.len().wrapping_sub(OFFICE_NUMBER_LENGTH);
+        match prefix_len {
+            0 => Ok(DeclarationOffice(office)),
+            2 if office[..2].chars().all(|c| c.is_ascii_uppercase()) => {
+                Ok(DeclarationOffice(office))
+            }
+            _ => Err(MrnGeneratorError::DeclarationOfficeLength(
+                office.to_string(),
+            )),
+        }
+    }
+
+    /// Country code preceding the office number, if any
+    pub fn country_prefix(&self) -> Option<&'a str> {
+        let prefix_len = self.0.len() - OFFICE_NUMBER_LENGTH;
+        (prefix_len > 0).then(|| &self.0[..prefix_len])
+    }
+
+    /// Office number without the country code
+    pub fn office_number(&self) -> &'a str {
+        &self.0[self.0.len() - OFFICE_NUMBER_LENGTH..]
+    }
+
+    /// The office as embedded in the MRN
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl fmt::Display for DeclarationOffice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn declaration_office_test() {
+        let office = DeclarationOffice::new("004700").unwrap();
+        assert_eq!(
+            (None, "004700"),
+            (office.country_prefix(), office.office_number())
+        );
+        assert_eq!("004700", office.to_string());
+        assert_eq!(
+            Err(MrnGeneratorError::DeclarationOfficeLength(
+                "12004700".to_string()
+            )),
+            DeclarationOffice::new("12004700")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidCharacter {
+                position: 1,
+                found: 'k'
+            }),
+            DeclarationOffice::new("Dk004700")
+        );
+        assert!(DeclarationOffice::new("").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Online verification that an MRN was actually issued, through an MRN follow-up service
+//!
+//! This is separate from the offline checks of [validate_mrn](crate::validate_mrn): a structurally
+//! valid MRN may never have been issued, and checking it needs network access to a service that
+//! knows the issued MRNs.
+//!
+//! The public MRN follow-up service of the European Commission is a web page rather than a
+//! documented API, so the endpoint is given as a URL template, e.g. the URL of a gateway in front
+//! of it. The service is expected to answer with a success status for an MRN it knows and with
+//! 404 Not Found otherwise.
+
+use crate::{validate_mrn, MrnGeneratorError};
+
+/// Outcome of an online verification
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MrnStatus {
+    /// The service knows the MRN
+    Found,
+    /// The MRN is structurally valid but the service doesn't know it
+    NotFound,
+}
+
+/// Online verification against an MRN follow-up service
+#[derive(Debug, Clone)]
+pub struct FollowUpClient {
+    endpoint: String,
+    agent: ureq::Agent,
+}
+
+impl FollowUpClient {
+    /// Client of the service at the endpoint, a URL where `{}` stands for the MRN, e.g.
+    /// `https://mrn-gateway.example.com/follow-up?mrn={}`
+    pub fn new(endpoint: &str) -> Result<Self, MrnGeneratorError> {
+        if !endpoint.contains("{}") {
+            return Err(MrnGeneratorError::OnlineVerification(format!(
+                "the endpoint {endpoint} has no {{}} placeholder for the MRN"
+            )));
+        }
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Ok(FollowUpClient {
+            endpoint: endpoint.to_string(),
+            agent,
+        })
+    }
+
+    /// URL queried for an MRN
+    fn url(&self, mrn: &str) -> String {
+        self.endpoint.replace("{}", mrn)
+    }
+
+    /// Checks offline that the MRN is valid, then asks the service whether it knows it
+    pub fn verify(&self, mrn: &str) -> Result<MrnStatus, MrnGeneratorError> {
+        validate_mrn(mrn)?;
+        let online_error = |e: ureq::Error| MrnGeneratorError::OnlineVerification(e.to_string());
+        let mut response = self
+            .agent
+            .get(&self.url(mrn))
+            .call()
+            .map_err(online_error)?;
+        // Read the body so the connection can be reused for the next MRN
+        response.body_mut().read_to_vec().map_err(online_error)?;
+        match response.status().as_u16() {
+            200..=299 => Ok(MrnStatus::Found),
+            404 => Ok(MrnStatus::NotFound),
+            status => Err(MrnGeneratorError::OnlineVerification(format!(
+                "the service answered with status {status}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn follow_up_client_test() {
+        let client = FollowUpClient::new("https://example.com/mrn/{}?lang=en").unwrap();
+        assert_eq!(
+            "https://example.com/mrn/22ITZXBZYUTJFLJXK6?lang=en",
+            client.url("22ITZXBZYUTJFLJXK6")
+        );
+        assert!(matches!(
+            client.verify("22ITZXBZYUTJFLJXK7"),
+            Err(MrnGeneratorError::InvalidCheckDigit { .. })
+        ));
+        assert!(FollowUpClient::new("https://example.com/mrn").is_err());
+    }
+}
@@ -0,0 +1,579 @@
+#[cfg(feature = "parquet")]
+mod parquet;
+
+use crate::parser::{Compression, OutputFormat, SplitBy};
+use anyhow::{bail, Context, Result};
+use flate2::write::GzEncoder;
+use mrn_generator::sink::{
+    MrnRecord, OutputSink, WrittenFile, COLUMNS, ISSUED_AT_COLUMN, LRN_COLUMN,
+};
+use mrn_generator::{layout, render};
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// Sinks of the command line utility
+pub type MrnSink = dyn OutputSink<Error = anyhow::Error> + Send;
+
+/// Opens the sink of an output format, standard output if there is no path
+pub fn sink(
+    format: OutputFormat,
+    path: Option<&Path>,
+    append: bool,
+    compression: Option<Compression>,
+    delimiter: Option<u8>,
+    labeled: bool,
+) -> Result<Box<MrnSink>> {
+    if compression.is_some() && !is_text(format) {
+        bail!("only plain, json and tsv output can be compressed, the other formats are compressed already");
+    }
+    // Tables are for reading in a terminal, files and pipes get plain output
+    let format = match format {
+        OutputFormat::Table if path.is_some() || !io::stdout().is_terminal() => OutputFormat::Plain,
+        format => format,
+    };
+    match format {
+        OutputFormat::Plain => Ok(Box::new(TextSink {
+            out: TextOutput::new(open_text(path, append)?, compression)?,
+            line: match labeled {
+                true => render::labeled,
+                false => render::plain,
+            },
+            path: path.map(Path::to_path_buf),
+            rows: 0,
+        })),
+        OutputFormat::Json => Ok(Box::new(TextSink {
+            out: TextOutput::new(open_text(path, append)?, compression)?,
+            line: render::json,
+            path: path.map(Path::to_path_buf),
+            rows: 0,
+        })),
+        OutputFormat::Tsv => {
+            // Appending to a file that has rows already doesn't repeat the header
+            let has_header = append
+                && path.is_some_and(|path| fs::metadata(path).is_ok_and(|file| file.len() > 0));
+            Ok(Box::new(DelimitedSink {
+                out: TextOutput::new(open_text(path, append)?, compression)?,
+                delimiter: char::from(delimiter.unwrap_or(b'\t')),
+                header_pending: !has_header,
+                path: path.map(Path::to_path_buf),
+                rows: 0,
+            }))
+        }
+        OutputFormat::Table => Ok(Box::new(TableSink::default())),
+        OutputFormat::Xlsx => {
+            let Some(path) = path else {
+                bail!("xlsx output needs an output file, set with --output");
+            };
+            if append {
+                bail!("xlsx output can't be appended to");
+            }
+            Ok(Box::new(XlsxSink::new(path)?))
+        }
+        #[cfg(feature = "parquet")]
+        OutputFormat::Parquet => {
+            let Some(path) = path else {
+                bail!("parquet output needs an output file, set with --output");
+            };
+            if append {
+                bail!("parquet output can't be appended to");
+            }
+            Ok(Box::new(parquet::ParquetSink::new(path)?))
+        }
+    }
+}
+
+/// Opens a sink writing one file per group of MRNs in a directory, each file is created once its
+/// group gets its first MRN
+pub fn split_sink(
+    split_by: SplitBy,
+    dir: &Path,
+    format: OutputFormat,
+    compression: Option<Compression>,
+    delimiter: Option<u8>,
+    labeled: bool,
+) -> Result<Box<MrnSink>> {
+    if compression.is_some() && !is_text(format) {
+        bail!("only plain, json and tsv output can be compressed, the other formats are compressed already");
+    }
+    fs::create_dir_all(dir)
+        .with_context(|| format!("could not create output directory {}", dir.display()))?;
+    Ok(Box::new(SplitSink {
+        split_by,
+        dir: dir.to_path_buf(),
+        format,
+        compression,
+        delimiter,
+        labeled,
+        sinks: BTreeMap::new(),
+    }))
+}
+
+/// Returns true for the formats written line by line, which can be compressed, tables being
+/// written to files as plain output
+fn is_text(format: OutputFormat) -> bool {
+    matches!(
+        format,
+        OutputFormat::Plain | OutputFormat::Json | OutputFormat::Tsv | OutputFormat::Table
+    )
+}
+
+/// File name extension of an output format
+fn extension(format: OutputFormat, compression: Option<Compression>) -> String {
+    let extension = match format {
+        OutputFormat::Plain | OutputFormat::Table => "txt",
+        OutputFormat::Json => "jsonl",
+        OutputFormat::Tsv => "tsv",
+        OutputFormat::Xlsx => "xlsx",
+        #[cfg(feature = "parquet")]
+        OutputFormat::Parquet => "parquet",
+    };
+    match compression {
+        None => extension.to_string(),
+        Some(Compression::Gzip) => format!("{extension}.gz"),
+        Some(Compression::Zstd) => format!("{extension}.zst"),
+    }
+}
+
+/// Opens a text output file, or standard output if there is no path
+fn open_text(path: Option<&Path>, append: bool) -> Result<Box<dyn Write + Send>> {
+    Ok(match path {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(append)
+                .truncate(!append)
+                .open(path)
+                .with_context(|| format!("could not open output file {}", path.display()))?;
+            Box::new(BufWriter::new(file))
+        }
+        None => Box::new(BufWriter::new(io::stdout())),
+    })
+}
+
+/// Text output, compressed as it is written if requested
+enum TextOutput {
+    Uncompressed(Box<dyn Write + Send>),
+    Gzip(GzEncoder<Box<dyn Write + Send>>),
+    Zstd(zstd::Encoder<'static, Box<dyn Write + Send>>),
+}
+
+impl TextOutput {
+    fn new(out: Box<dyn Write + Send>, compression: Option<Compression>) -> Result<Self> {
+        Ok(match compression {
+            None => TextOutput::Uncompressed(out),
+            Some(Compression::Gzip) => {
+                TextOutput::Gzip(GzEncoder::new(out, flate2::Compression::default()))
+            }
+            Some(Compression::Zstd) => TextOutput::Zstd(zstd::Encoder::new(out, 0)?),
+        })
+    }
+
+    /// Writes the trailer of the compressed stream, if any, and flushes the output
+    fn finish(self) -> io::Result<()> {
+        let mut out = match self {
+            TextOutput::Uncompressed(out) => out,
+            TextOutput::Gzip(encoder) => encoder.finish()?,
+            TextOutput::Zstd(encoder) => encoder.finish()?,
+        };
+        out.flush()
+    }
+}
+
+impl Write for TextOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            TextOutput::Uncompressed(out) => out.write(buf),
+            TextOutput::Gzip(encoder) => encoder.write(buf),
+            TextOutput::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            TextOutput::Uncompressed(out) => out.flush(),
+            TextOutput::Gzip(encoder) => encoder.flush(),
+            TextOutput::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// One MRN per line, formatted by `line`
+struct TextSink {
+    out: TextOutput,
+    line: fn(&MrnRecord) -> String,
+    /// Output file, None for standard output
+    path: Option<PathBuf>,
+    rows: u64,
+}
+
+impl OutputSink for TextSink {
+    type Error = anyhow::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<()> {
+        writeln!(self.out, "{}", (self.line)(record))?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<WrittenFile>> {
+        self.out.finish()?;
+        Ok(self
+            .path
+            .into_iter()
+            .map(|path| WrittenFile {
+                path,
+                rows: self.rows,
+            })
+            .collect())
+    }
+}
+
+/// Header row and one row per MRN and its components, with the fields separated by a delimiter
+struct DelimitedSink {
+    out: TextOutput,
+    delimiter: char,
+    /// True until the header is written
+    header_pending: bool,
+    /// Output file, None for standard output
+    path: Option<PathBuf>,
+    rows: u64,
+}
+
+impl OutputSink for DelimitedSink {
+    type Error = anyhow::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<()> {
+        if self.header_pending {
+            writeln!(
+                self.out,
+                "{}",
+                render::delimited(&render::header(record), self.delimiter)
+            )?;
+            self.header_pending = false;
+        }
+        writeln!(
+            self.out,
+            "{}",
+            render::delimited(&render::row(record), self.delimiter)
+        )?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Vec<WrittenFile>> {
+        if self.header_pending {
+            writeln!(self.out, "{}", render::delimited(&COLUMNS, self.delimiter))?;
+        }
+        self.out.finish()?;
+        Ok(self
+            .path
+            .into_iter()
+            .map(|path| WrittenFile {
+                path,
+                rows: self.rows,
+            })
+            .collect())
+    }
+}
+
+/// Aligned table of the MRNs and their components, printed to standard output once they are all
+/// generated
+#[derive(Default)]
+struct TableSink {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl OutputSink for TableSink {
+    type Error = anyhow::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<()> {
+        // Records either all have an issue date or LRN or none of them has one
+        if self.rows.is_empty() {
+            self.header = render::header(record);
+        }
+        self.rows.push(render::row(record));
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<WrittenFile>> {
+        let header = match self.header.is_empty() {
+            true => COLUMNS.to_vec(),
+            false => self.header,
+        };
+        println!("{}", render::table(&header, &self.rows));
+        Ok(vec![])
+    }
+}
+
+/// One file per group of MRNs, in the format of the other sinks
+struct SplitSink {
+    split_by: SplitBy,
+    dir: PathBuf,
+    format: OutputFormat,
+    compression: Option<Compression>,
+    delimiter: Option<u8>,
+    labeled: bool,
+    sinks: BTreeMap<String, Box<MrnSink>>,
+}
+
+impl OutputSink for SplitSink {
+    type Error = anyhow::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<()> {
+        let group = match self.split_by {
+            SplitBy::Country => &record.mrn[layout::COUNTRY_CODE],
+        };
+        if !self.sinks.contains_key(group) {
+            let path = self.dir.join(format!(
+                "{group}.{}",
+                extension(self.format, self.compression)
+            ));
+            let sink = sink(
+                self.format,
+                Some(&path),
+                false,
+                self.compression,
+                self.delimiter,
+                self.labeled,
+            )?;
+            self.sinks.insert(group.to_string(), sink);
+        }
+        self.sinks.get_mut(group).unwrap().write(record)
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<WrittenFile>> {
+        let mut files = vec![];
+        for sink in self.sinks.into_values() {
+            files.extend(sink.finish()?);
+        }
+        Ok(files)
+    }
+}
+
+/// Spreadsheet with one row per MRN and its components
+struct XlsxSink {
+    path: PathBuf,
+    worksheet: Worksheet,
+    row: u32,
+}
+
+/// Maximum number of rows of an XLSX worksheet, including the header
+const XLSX_MAX_ROWS: u32 = 1_048_576;
+
+impl XlsxSink {
+    fn new(path: &Path) -> Result<Self> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_name("MRNs")?;
+        let bold = Format::new().set_bold();
+        for (col, name) in (0..).zip(COLUMNS) {
+            worksheet.write_string_with_format(0, col, name, &bold)?;
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+
+        Ok(XlsxSink {
+            path: path.to_path_buf(),
+            worksheet,
+            row: 1,
+        })
+    }
+}
+
+impl OutputSink for XlsxSink {
+    type Error = anyhow::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<()> {
+        if self.row == XLSX_MAX_ROWS {
+            bail!(
+                "xlsx output is limited to {} MRNs per file",
+                XLSX_MAX_ROWS - 1
+            );
+        }
+        for (col, field) in (0..).zip(record.fields()) {
+            self.worksheet.write_string(self.row, col, field)?;
+        }
+        // Records either all have an issue date or LRN or none of them has one
+        let optional = [
+            (ISSUED_AT_COLUMN, record.issued_at),
+            (LRN_COLUMN, record.lrn),
+        ];
+        let present = optional
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, value?)));
+        for (col, (name, value)) in (COLUMNS.len() as u16..).zip(present) {
+            if self.row == 1 {
+                let bold = Format::new().set_bold();
+                self.worksheet
+                    .write_string_with_format(0, col, name, &bold)?;
+            }
+            self.worksheet.write_string(self.row, col, value)?;
+        }
+        self.row += 1;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Vec<WrittenFile>> {
+        self.worksheet.autofit();
+        let mut workbook = Workbook::new();
+        workbook.push_worksheet(self.worksheet);
+        workbook
+            .save(&self.path)
+            .with_context(|| format!("could not write output file {}", self.path.display()))?;
+        Ok(vec![WrittenFile {
+            path: self.path,
+            rows: u64::from(self.row - 1),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn split_output_test() {
+        let dir = std::env::temp_dir().join(format!("mrn-split-{}", std::process::id()));
+        let mut sink = split_sink(
+            SplitBy::Country,
+            &dir,
+            OutputFormat::Plain,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        for mrn in [
+            "22ITZXBZYUTJFLJXK6",
+            "26DK004700CJNMYMA6",
+            "22ITZXBZYUTJFLJXK6",
+        ] {
+            sink.write(&MrnRecord {
+                mrn,
+                procedure: None,
+                declaration_office: None,
+                issued_at: None,
+                lrn: None,
+            })
+            .unwrap();
+        }
+        sink.finish().unwrap();
+
+        assert_eq!(
+            "22ITZXBZYUTJFLJXK6\n22ITZXBZYUTJFLJXK6\n",
+            fs::read_to_string(dir.join("IT.txt")).unwrap()
+        );
+        assert_eq!(
+            "26DK004700CJNMYMA6\n",
+            fs::read_to_string(dir.join("DK.txt")).unwrap()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn tsv_output_test() {
+        let path = std::env::temp_dir().join(format!("mrn-output-{}.tsv", std::process::id()));
+        for (append, delimiter) in [(false, None), (true, Some(b'\t'))] {
+            let mut sink = sink(
+                OutputFormat::Tsv,
+                Some(&path),
+                append,
+                None,
+                delimiter,
+                false,
+            )
+            .unwrap();
+            sink.write(&MrnRecord {
+                mrn: "22ITZXBZYUTJFLJXK6",
+                procedure: None,
+                declaration_office: None,
+                issued_at: None,
+                lrn: Some("LRN, 1"),
+            })
+            .unwrap();
+            sink.finish().unwrap();
+        }
+
+        let row = "22ITZXBZYUTJFLJXK6\t2022\tIT\t\tZXBZYUTJFLJXK\t\t\t6\tLRN, 1\n";
+        assert_eq!(
+            format!("{}\tLRN\n{row}{row}", COLUMNS.join("\t")),
+            fs::read_to_string(&path).unwrap()
+        );
+
+        let mut sink = sink(
+            OutputFormat::Tsv,
+            Some(&path),
+            false,
+            None,
+            Some(b';'),
+            false,
+        )
+        .unwrap();
+        sink.write(&MrnRecord {
+            mrn: "22ITZXBZYUTJFLJXK6",
+            procedure: None,
+            declaration_office: None,
+            issued_at: None,
+            lrn: Some("LRN; 1"),
+        })
+        .unwrap();
+        sink.finish().unwrap();
+        assert_eq!(
+            format!(
+                "{};LRN\n22ITZXBZYUTJFLJXK6;2022;IT;;ZXBZYUTJFLJXK;;;6;\"LRN; 1\"\n",
+                COLUMNS.join(";")
+            ),
+            fs::read_to_string(&path).unwrap()
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn compressed_output_test() {
+        for compression in [Compression::Gzip, Compression::Zstd] {
+            let path = std::env::temp_dir().join(format!(
+                "mrn-output-{}-{compression:?}.txt",
+                std::process::id()
+            ));
+            let mut sink = sink(
+                OutputFormat::Plain,
+                Some(&path),
+                false,
+                Some(compression),
+                None,
+                false,
+            )
+            .unwrap();
+            for mrn in ["22ITZXBZYUTJFLJXK6", "26DK004700CJNMYMA6"] {
+                sink.write(&MrnRecord {
+                    mrn,
+                    procedure: None,
+                    declaration_office: None,
+                    issued_at: None,
+                    lrn: None,
+                })
+                .unwrap();
+            }
+            sink.finish().unwrap();
+
+            let file = std::fs::File::open(&path).unwrap();
+            let mut contents = String::new();
+            match compression {
+                Compression::Gzip => {
+                    flate2::read::GzDecoder::new(file).read_to_string(&mut contents)
+                }
+                Compression::Zstd => zstd::Decoder::new(file)
+                    .unwrap()
+                    .read_to_string(&mut contents),
+            }
+            .unwrap();
+            assert_eq!("22ITZXBZYUTJFLJXK6\n26DK004700CJNMYMA6\n", contents);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
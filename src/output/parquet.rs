@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use mrn_generator::sink::{MrnRecord, OutputSink, WrittenFile};
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Number of MRNs per row group
+const ROW_GROUP_SIZE: usize = 1 << 17;
+
+const SCHEMA: &str = "
+message mrn {
+    REQUIRED BYTE_ARRAY mrn (UTF8);
+    REQUIRED BYTE_ARRAY year (UTF8);
+    REQUIRED BYTE_ARRAY country_code (UTF8);
+    REQUIRED BYTE_ARRAY declaration_office (UTF8);
+    REQUIRED BYTE_ARRAY serial_number (UTF8);
+    REQUIRED BYTE_ARRAY procedure_character (UTF8);
+    REQUIRED BYTE_ARRAY procedure (UTF8);
+    REQUIRED BYTE_ARRAY check_digit (UTF8);
+    OPTIONAL BYTE_ARRAY issued_at (UTF8);
+    OPTIONAL BYTE_ARRAY lrn (UTF8);
+}
+";
+
+/// Parquet file with one row per MRN and its components, written in row groups
+pub struct ParquetSink {
+    writer: SerializedFileWriter<File>,
+    columns: [Vec<ByteArray>; 8],
+    /// Issue dates and LRNs of the MRNs that have one, with the definition levels of all the MRNs
+    optional: [(Vec<ByteArray>, Vec<i16>); 2],
+    path: PathBuf,
+    rows: u64,
+}
+
+impl ParquetSink {
+    pub fn new(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("could not open output file {}", path.display()))?;
+        let schema = Arc::new(parse_message_type(SCHEMA)?);
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        Ok(ParquetSink {
+            writer: SerializedFileWriter::new(file, schema, Arc::new(properties))?,
+            columns: Default::default(),
+            optional: Default::default(),
+            path: path.to_path_buf(),
+            rows: 0,
+        })
+    }
+
+    /// Writes the buffered MRNs as a row group
+    fn flush_row_group(&mut self) -> Result<()> {
+        if self.columns[0].is_empty() {
+            return Ok(());
+        }
+        let mut row_group = self.writer.next_row_group()?;
+        for values in &mut self.columns {
+            let mut column = row_group
+                .next_column()?
+                .expect("the schema has a column for each field");
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(values, None, None)?;
+            column.close()?;
+            values.clear();
+        }
+        for (values, levels) in &mut self.optional {
+            let mut column = row_group
+                .next_column()?
+                .expect("the schema has a column for each optional field");
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(values, Some(levels), None)?;
+            column.close()?;
+            values.clear();
+            levels.clear();
+        }
+        row_group.close()?;
+        Ok(())
+    }
+}
+
+impl OutputSink for ParquetSink {
+    type Error = anyhow::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<()> {
+        for (values, field) in self.columns.iter_mut().zip(record.fields()) {
+            values.push(ByteArray::from(field.into_bytes()));
+        }
+        let optional = [record.issued_at, record.lrn];
+        for ((values, levels), field) in self.optional.iter_mut().zip(optional) {
+            match field {
+                Some(field) => {
+                    values.push(ByteArray::from(field));
+                    levels.push(1);
+                }
+                None => levels.push(0),
+            }
+        }
+        self.rows += 1;
+        if self.columns[0].len() == ROW_GROUP_SIZE {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Vec<WrittenFile>> {
+        self.flush_row_group()?;
+        self.writer.close()?;
+        Ok(vec![WrittenFile {
+            path: self.path,
+            rows: self.rows,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    #[test]
+    fn parquet_round_trip_test() {
+        let path = std::env::temp_dir().join(format!("mrn-output-{}.parquet", std::process::id()));
+        let mut sink = Box::new(ParquetSink::new(&path).unwrap());
+        for (mrn, issued_at, lrn) in [
+            ("22ITZXBZYUTJFLJXK6", None, None),
+            (
+                "26DK004700CJNMYMA6",
+                Some("2026-01-31"),
+                Some("DECL-0000001"),
+            ),
+        ] {
+            sink.write(&MrnRecord {
+                mrn,
+                procedure: None,
+                declaration_office: None,
+                issued_at,
+                lrn,
+            })
+            .unwrap();
+        }
+        sink.finish().unwrap();
+
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(2, metadata.num_rows());
+        assert_eq!(10, metadata.schema_descr().num_columns());
+        std::fs::remove_file(path).unwrap();
+    }
+}
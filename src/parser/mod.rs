@@ -1,17 +1,105 @@
-use clap::Parser;
+use crate::config::Config;
+use anyhow::Context;
+use chrono::{Datelike, NaiveDate};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgGroup, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use mrn_generator::country::{check_known_country, resolve_country};
+use mrn_generator::i18n::{Lang, Localizer};
+use mrn_generator::validation::{DeclarationDate, DEFAULT_CENTURY_PIVOT};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
 
 /// Command line utility to generate valid MRNs
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(group(ArgGroup::new("office").args(["declaration_office", "random_office"])))]
+#[command(group(ArgGroup::new("destination").args(["output", "output_dir"])))]
+#[command(group(ArgGroup::new("countries").args(["country_code", "countries_file"])))]
 pub struct Args {
-    /// Country code of MRN
-    #[arg(short, long)]
-    pub country_code: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Country code or name of MRN, or comma separated country codes to spread the MRNs over
+    #[arg(
+        short,
+        long,
+        required_unless_present_any = ["profile", "countries_file", "weights", "preset", "all_countries"],
+        value_delimiter = ',',
+        value_parser = parse_country
+    )]
+    pub country_code: Vec<String>,
+
+    /// File with a country code or name per line, optionally followed by a comma and the number
+    /// of MRNs to generate of it, e.g. DK,100
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["country_weights", "profile"])]
+    pub countries_file: Option<PathBuf>,
+
+    /// Number of MRNs of each country, given by the counts of the countries file or by
+    /// --all-countries, generated in groups in the order of the countries
+    #[arg(skip)]
+    pub country_counts: Option<Vec<usize>>,
+
+    /// TOML or JSON file with the weights of the countries, procedures and offices and the share
+    /// of MRNs with a wrong check digit
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["profile", "countries_file", "country_weights"]
+    )]
+    pub weights: Option<PathBuf>,
+
+    /// Only accept the country codes of known member states and transit countries
+    #[arg(long, requires = "countries")]
+    pub known_countries: bool,
+
+    /// Comma separated weights of the country codes, e.g. 3,1
+    #[arg(long, value_delimiter = ',')]
+    pub country_weights: Option<Vec<u32>>,
 
     /// Number of MRNs to generate
-    #[arg(short, long, default_value_t = 1)]
+    #[arg(short, long, default_value_t = 1, value_parser = parse_count)]
     pub number_of_mrns: usize,
 
+    /// Generate this many MRNs for every procedure, A to W, in groups labelled with their
+    /// procedure character in plain output
+    #[arg(
+        long,
+        value_name = "COUNT",
+        value_parser = parse_count,
+        conflicts_with_all = [
+            "number_of_mrns",
+            "procedure_category",
+            "combined",
+            "weights",
+            "profile",
+            "preset",
+            "parallel",
+            "skip_existing",
+        ]
+    )]
+    pub count_per_procedure: Option<usize>,
+
+    /// Generate this many MRNs, 1 if not given, for every country given by --country-code, a
+    /// countries, weights file or preset, or for every known country if none is given
+    #[arg(
+        long,
+        value_name = "COUNT",
+        num_args = 0..=1,
+        default_missing_value = "1",
+        value_parser = parse_count,
+        conflicts_with_all = [
+            "number_of_mrns",
+            "country_weights",
+            "profile",
+            "parallel",
+            "count_per_procedure",
+        ]
+    )]
+    pub all_countries: Option<usize>,
+
     /// Procedure category
     #[arg(short, long)]
     pub procedure_category: Option<String>,
@@ -23,4 +111,1243 @@ pub struct Args {
     /// Customs office of declaration
     #[arg(short = 'o', long)]
     pub declaration_office: Option<String>,
+
+    /// Prefix the declaration office with the country code, as in customs office reference list
+    /// identifiers (e.g. DK004700)
+    #[arg(long, requires = "office")]
+    pub office_with_country: bool,
+
+    /// Use a random office of the country from the customs office list as declaration office
+    #[arg(long)]
+    pub random_office: bool,
+
+    /// Check that the declaration office is in the customs office list
+    #[arg(long, requires = "declaration_office")]
+    pub check_office: bool,
+
+    /// Apply the conventions of a specific system
+    #[arg(long, value_enum)]
+    pub preset: Option<Preset>,
+
+    /// Spread the MRNs over the countries and procedures of a realistic traffic profile, or use
+    /// the arguments of a profile of the config file
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["country_code", "country_weights", "procedure_category", "combined", "preset"]
+    )]
+    pub profile: Option<Profile>,
+
+    /// Config file with the profiles of --profile, mrn-generator.toml of the working directory by
+    /// default
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// TOML or JSON file with procedure mapping rules that extend or override the built-in ones
+    #[arg(long, value_name = "FILE")]
+    pub rules: Option<PathBuf>,
+
+    /// Spread the MRNs over a range of issue years, e.g. 20..=24 or 2020..2025
+    #[arg(long, value_parser = parse_year_range)]
+    pub year_range: Option<YearRange>,
+
+    /// Comma separated weights of the years in the year range, e.g. 1,1,2,4,8
+    #[arg(long, value_delimiter = ',', requires = "year_range")]
+    pub year_weights: Option<Vec<u32>>,
+
+    /// Spread the MRNs over the days of a date range, both ends included, so that each issue year
+    /// gets MRNs in proportion to its days, e.g. 2023-01-01..2024-12-31
+    #[arg(long, value_parser = parse_date_range, conflicts_with = "year_range")]
+    pub spread_over: Option<DateRange>,
+
+    /// Add the issue date picked with --spread-over to the output, as an issued_at column
+    #[arg(long, requires = "spread_over")]
+    pub issued_at: bool,
+
+    /// Pair each MRN with a random LRN, written before it in plain output and as an lrn column
+    /// otherwise
+    #[arg(long, conflicts_with = "skip_existing")]
+    pub with_lrn: bool,
+
+    #[command(flatten)]
+    pub source: IdSourceArgs,
+
+    /// Bloom filter file of previously issued MRNs, used to avoid repeating them across runs
+    #[arg(long, value_name = "FILE")]
+    pub seen_filter: Option<PathBuf>,
+
+    /// Registry file of issued MRNs, locked while running so it can be shared between concurrent runs
+    #[arg(long, value_name = "FILE")]
+    pub registry: Option<PathBuf>,
+
+    /// JSON lines file to append every generated MRN to, with the time, parameters and seed it
+    /// was generated with
+    #[arg(long, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+
+    /// URL to POST a summary of the generated MRNs to once they are written, with their count, a
+    /// sample and checksums
+    #[cfg(feature = "online")]
+    #[arg(long, value_name = "URL")]
+    pub webhook: Option<String>,
+
+    /// File to write the generated MRNs to instead of standard output
+    #[arg(long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Write the generated MRNs to one file per group in the output directory
+    #[arg(long, value_enum, requires = "output_dir")]
+    pub split_by: Option<SplitBy>,
+
+    /// Directory of the files written with --split-by
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires = "split_by",
+        conflicts_with = "output"
+    )]
+    pub output_dir: Option<PathBuf>,
+
+    /// Format of the generated MRNs
+    #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+    pub format: OutputFormat,
+
+    /// Separator of the fields of tsv output instead of a tab, a single character or `tab`
+    #[arg(long, value_parser = parse_delimiter)]
+    pub delimiter: Option<u8>,
+
+    /// Compress the plain, json or tsv output files as they are written
+    #[arg(
+        long,
+        value_enum,
+        requires = "destination",
+        conflicts_with = "skip_existing"
+    )]
+    pub compress: Option<Compression>,
+
+    /// Write a manifest.json next to the output files, with their row counts, SHA-256 checksums
+    /// and the generation parameters
+    #[arg(long, requires = "destination", conflicts_with = "append")]
+    pub manifest: bool,
+
+    /// Shell command to run for each generated MRN, with `{}` replaced by the MRN, `{country}` by
+    /// its country code, `{office}` by its declaration office and `{lrn}` by its --with-lrn LRN
+    #[arg(long, value_name = "COMMAND")]
+    pub exec: Option<String>,
+
+    /// Maximum number of --exec commands running at the same time
+    #[arg(long, default_value_t = 1, requires = "exec")]
+    pub exec_jobs: usize,
+
+    /// Number of generated MRNs waiting to be written before generation waits for the output
+    #[arg(long, default_value_t = 1024)]
+    pub queue_size: usize,
+
+    /// Generate using all cores, in chunks of 1024 MRNs that are each seeded from --seed
+    #[arg(long, conflicts_with_all = ["seen_filter", "registry", "skip_existing"])]
+    pub parallel: bool,
+
+    /// Number of threads used with --parallel, all cores by default
+    #[arg(long, requires = "parallel")]
+    pub jobs: Option<usize>,
+
+    /// Write the MRNs of --parallel in the order of their chunks, so that the output is the same
+    /// for the same seed whatever the number of threads
+    #[arg(long, requires = "parallel")]
+    pub ordered: bool,
+
+    /// Append to the output file instead of overwriting it
+    #[arg(long, requires = "output")]
+    pub append: bool,
+
+    /// Never generate an MRN that is already present in the output file
+    #[arg(long, requires = "append")]
+    pub skip_existing: bool,
+
+    #[command(flatten)]
+    pub failure: FailurePolicyArgs,
+
+    /// Print the generation plan, the run as resolved from the config file profile, weights file,
+    /// preset and command line, without generating
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Language of the messages, the language of the environment by default
+    #[arg(long, global = true, value_parser = parse_lang())]
+    pub lang: Option<Lang>,
+}
+
+/// Parser of the supported languages, listing them as possible values
+fn parse_lang() -> impl TypedValueParser<Value = Lang> {
+    PossibleValuesParser::new(["en", "de", "fr", "el"]).map(|lang| lang.parse::<Lang>().unwrap())
+}
+
+/// Value of an option, looked up before parsing
+fn requested(args: &[String], option: &str) -> Option<(usize, String)> {
+    let mut args = args.iter().enumerate().skip(1);
+    while let Some((i, arg)) = args.next() {
+        if arg == option {
+            return args.next().map(|(_, value)| (i, value.clone()));
+        }
+        if let Some(value) = arg
+            .strip_prefix(option)
+            .and_then(|arg| arg.strip_prefix('='))
+        {
+            return Some((i, value.to_string()));
+        }
+    }
+    None
+}
+
+/// Language requested with `--lang`, looked up before parsing so that the help texts and parsing
+/// errors can be localized too
+pub fn requested_lang() -> Option<Lang> {
+    let args: Vec<String> = env::args().collect();
+    requested(&args, "--lang").and_then(|(_, lang)| lang.parse().ok())
+}
+
+/// Replaces `--profile` with the arguments of the profile when it is one of the config file
+fn expand_profile(mut args: Vec<String>, command: &clap::Command) -> anyhow::Result<Vec<String>> {
+    let Some((i, name)) = requested(&args, "--profile") else {
+        return Ok(args);
+    };
+    let config = requested(&args, "--config").map(|(_, path)| PathBuf::from(path));
+    let config = Config::load(config.as_deref())?;
+    let end = if args[i] == "--profile" { i + 2 } else { i + 1 };
+    let rest: Vec<String> = args[..i].iter().chain(&args[end..]).cloned().collect();
+    if let Some(profile) = config.profile_args(&name, command, &rest)? {
+        args.splice(i..end, profile);
+    }
+    Ok(args)
+}
+
+/// Parses the command line with help texts in the language of the localizer
+pub fn parse_localized(localizer: &Localizer) -> Args {
+    let mut command = localize_command(Args::command(), localizer, None);
+    let args = expand_profile(env::args().collect(), &command).unwrap_or_else(|e| {
+        command
+            .error(ErrorKind::InvalidValue, format!("{e:#}"))
+            .exit()
+    });
+    let mut matches = command.clone().get_matches_from(args);
+    // Arguments that set the number of MRNs or how they are grouped, like the counts of a
+    // countries file
+    let counted = [
+        "number_of_mrns",
+        "all_countries",
+        "count_per_procedure",
+        "parallel",
+    ]
+    .into_iter()
+    .find(|id| matches.value_source(id) == Some(ValueSource::CommandLine));
+    let mut args = Args::from_arg_matches_mut(&mut matches).unwrap_or_else(|e| e.exit());
+    if let Some(path) = args.countries_file.as_deref() {
+        let (country_codes, counts) = read_countries_file(path)
+            .unwrap_or_else(|e| command.error(ErrorKind::Io, format!("{e:#}")).exit());
+        if let (Some(_), Some(id)) = (&counts, counted) {
+            command
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "the countries file {} gives the number of MRNs of each country, it can't \
+                         be used with --{}",
+                        path.display(),
+                        id.replace('_', "-")
+                    ),
+                )
+                .exit();
+        }
+        args.country_code = country_codes;
+        args.country_counts = counts;
+    }
+    if args.known_countries {
+        if let Err(e) = args
+            .country_code
+            .iter()
+            .try_for_each(|c| check_known_country(c))
+        {
+            command
+                .error(ErrorKind::InvalidValue, localizer.error(&e))
+                .exit();
+        }
+    }
+    args
+}
+
+/// Reads the countries of a --countries-file, with the number of MRNs of each if the lines have
+/// one. Blank lines and lines starting with `#` are skipped.
+fn read_countries_file(path: &Path) -> anyhow::Result<(Vec<String>, Option<Vec<usize>>)> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("could not read countries file {}", path.display()))?;
+    let mut countries = Vec::new();
+    let mut counts = Vec::new();
+    for (n, line) in (1..).zip(contents.lines()) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (country, count) = match line.split_once(',') {
+            Some((country, count)) => {
+                let count = parse_count(count.trim())
+                    .map_err(anyhow::Error::msg)
+                    .with_context(|| format!("line {n} of {}", path.display()))?;
+                (country, Some(count))
+            }
+            None => (line, None),
+        };
+        let country =
+            resolve_country(country).with_context(|| format!("line {n} of {}", path.display()))?;
+        countries.push(country);
+        counts.push(count);
+    }
+    if countries.is_empty() {
+        anyhow::bail!("countries file {} has no countries", path.display());
+    }
+    if counts.iter().all(Option::is_none) {
+        return Ok((countries, None));
+    }
+    let counts = counts
+        .into_iter()
+        .zip(&countries)
+        .map(|(count, country)| {
+            count.with_context(|| {
+                format!(
+                    "countries file {}: {country} has no count, give one to every country or none",
+                    path.display()
+                )
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok((countries, Some(counts)))
+}
+
+/// Replaces the about and help texts of a command and its subcommands with the `about-<command>`
+/// and `help-<command>-<arg>` messages of the localizer, where the language has them
+fn localize_command(
+    mut command: clap::Command,
+    localizer: &Localizer,
+    name: Option<&str>,
+) -> clap::Command {
+    let about = name.map_or("about".to_string(), |name| format!("about-{name}"));
+    if let Some(about) = localizer.message(&about, None) {
+        command = command.about(about);
+    }
+
+    let help = name.map_or("help".to_string(), |name| format!("help-{name}"));
+    command = command.mut_args(|arg| {
+        match localizer.message(&format!("{help}-{}", arg.get_id()), None) {
+            Some(text) => arg.help(text),
+            None => arg,
+        }
+    });
+
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for subcommand in subcommands {
+        command = command.mut_subcommand(&subcommand, |c| {
+            localize_command(c, localizer, Some(&subcommand))
+        });
+    }
+    command
+}
+
+/// Subcommands for identifiers other than MRNs
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generate or validate Guarantee Reference Numbers
+    Grn(GrnArgs),
+    /// Generate Local Reference Numbers, optionally paired with MRNs
+    Lrn(LrnArgs),
+    /// Generate synthetic declarations, each with a matching MRN, LRN, EORI, office, procedure and
+    /// lodging and acceptance times
+    Dataset(DatasetArgs),
+    /// Generate or validate EORI numbers
+    Eori(EoriArgs),
+    /// Generate or validate ISO 6346 container numbers
+    Container(ContainerArgs),
+    /// Generate or validate UK Declaration and Master Unique Consignment References
+    Ucr(UcrArgs),
+    /// Validate MRNs
+    Validate(ValidateArgs),
+    /// Print the declaration categories with their combinations, procedures and descriptions
+    ListCategories,
+    /// Print an annotated breakdown of an MRN
+    Explain(ExplainArgs),
+    /// Print the valid MRNs matching an MRN with unknown characters, e.g. 24DK0047??????????
+    Complete(CompleteArgs),
+    /// Report MRNs appearing more than once within or across files
+    CheckDuplicates(CheckDuplicatesArgs),
+    /// Print the MRNs of files matching masks like ??DK0047*, where ? is any character and * any
+    /// number of them
+    Grep(GrepArgs),
+    /// Fix the check digits of files of MRNs, printing the changes as a diff
+    Fix(FixArgs),
+    /// Print a stable set of example MRNs of every procedure and several countries, to check in
+    /// as golden test data
+    Fixtures(FixturesArgs),
+    /// Verify that MRNs were actually issued, through an MRN follow-up service
+    #[cfg(feature = "online")]
+    Verify(VerifyArgs),
+    /// Serve MRN generation, validation and parsing as Model Context Protocol tools over stdio
+    Mcp,
+    /// Print the JSON Schema of the JSON output, errors or manifest
+    Schema(SchemaArgs),
+    /// Measure the generation throughput, with the time and allocations of each stage
+    Bench(BenchArgs),
+    /// Serve MRN generation, validation and leases of MRNs to test orchestrators over HTTP
+    Serve(ServeArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ValidateArgs {
+    /// MRNs to validate, read from standard input if none are given, one per line, from CSV or
+    /// from a JSON array. With --csv, the CSV file to read instead of standard input
+    pub mrns: Vec<String>,
+
+    /// Read CSV and validate one of its columns, writing the rows back with a `valid` column
+    #[arg(long, conflicts_with = "parallel")]
+    pub csv: bool,
+
+    /// Column of the CSV input holding the MRNs
+    #[arg(long, default_value = "MRN", requires = "csv")]
+    pub column: String,
+
+    /// Only write the rows whose MRN is valid or invalid, instead of adding a `valid` column
+    #[arg(long, value_enum, requires = "csv")]
+    pub filter: Option<RowFilter>,
+
+    /// Also check that the MRNs follow the conventions of a specific system
+    #[arg(long, value_enum)]
+    pub preset: Option<Preset>,
+
+    /// Number of years after the current one accepted without a future year warning
+    #[arg(long, default_value_t = 0)]
+    pub future_tolerance: u8,
+
+    /// Treat MRNs with a future year as invalid instead of only warning about them
+    #[arg(long)]
+    pub reject_future: bool,
+
+    /// First year of the hundred years the two year digits are taken from, e.g. 1990 to take 90
+    /// to 99 as the 1990s for archived MRNs instead of the 2090s
+    #[arg(long, value_name = "YEAR", default_value_t = DEFAULT_CENTURY_PIVOT)]
+    pub century_pivot: u16,
+
+    /// Date of the declarations, e.g. 2024-03-01, treating MRNs whose year doesn't match it as
+    /// invalid. MRNs of the year before or after are accepted for dates within a week of New Year.
+    #[arg(long, value_name = "DATE", value_parser = parse_declaration_date)]
+    pub declaration_date: Option<DeclarationDate>,
+
+    /// Treat MRNs with warnings as invalid, for strict pipelines
+    #[arg(long, conflicts_with = "ignore_warnings")]
+    pub warnings_as_errors: bool,
+
+    /// Only report the errors of the MRNs, not their warnings or informational findings
+    #[arg(long)]
+    pub ignore_warnings: bool,
+
+    /// Check that the customs office reference following the country code starts with the same
+    /// country code (e.g. 24DKDK004700...)
+    #[arg(long)]
+    pub check_office_country: bool,
+
+    /// TOML or JSON file with site-specific checks, rules like `office in 004700, 002000` the
+    /// MRNs must pass
+    #[arg(long, value_name = "FILE")]
+    pub checks: Option<PathBuf>,
+
+    /// Suggest the smallest single character correction of invalid MRNs, e.g. of MRNs scanned
+    /// with OCR
+    #[arg(long, conflicts_with = "csv")]
+    pub suggest_repair: bool,
+
+    /// Validate using all cores, for large inputs
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Number of threads used with --parallel, all cores by default
+    #[arg(long, requires = "parallel")]
+    pub jobs: Option<usize>,
+
+    /// Only print the invalid MRNs
+    #[arg(short, long, conflicts_with = "csv")]
+    pub quiet: bool,
+
+    /// Format of standard input, detected from its start by default
+    #[arg(long, value_enum, default_value_t = InputFormat::Auto, conflicts_with = "csv")]
+    pub input_format: InputFormat,
+
+    /// Report the number of MRNs validated and invalid so far on standard error every few
+    /// seconds, for large inputs
+    #[arg(long, conflicts_with = "csv")]
+    pub progress: bool,
+
+    #[command(flatten)]
+    pub failure: FailurePolicyArgs,
+}
+
+#[cfg(feature = "online")]
+#[derive(clap::Args, Debug)]
+pub struct VerifyArgs {
+    /// MRNs to verify
+    #[arg(required = true)]
+    pub mrns: Vec<String>,
+
+    /// Query the follow-up service, otherwise the MRNs are only checked offline
+    #[arg(long, requires = "endpoint")]
+    pub online: bool,
+
+    /// URL of the follow-up service, where `{}` stands for the MRN
+    #[arg(long, value_name = "URL", requires = "online")]
+    pub endpoint: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SchemaArgs {
+    /// Document described by the schema
+    #[arg(value_enum, default_value_t = SchemaKind::Record)]
+    pub kind: SchemaKind,
+}
+
+/// Documents written by the command line utility as JSON
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum SchemaKind {
+    /// A line of the --format json output
+    Record,
+    /// An error written to standard error with --format json
+    Error,
+    /// The manifest.json written with --manifest
+    Manifest,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ExplainArgs {
+    /// MRN to explain
+    pub mrn: String,
+
+    /// Years MRNs are kept, flagging the MRN if it was issued before them
+    #[arg(long, value_name = "YEARS")]
+    pub retention_years: Option<u16>,
+
+    /// First year of the hundred years the two year digits are taken from, by default the
+    /// latest year ending in them up to next year
+    #[arg(long, value_name = "YEAR")]
+    pub century_pivot: Option<u16>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CompleteArgs {
+    /// MRN with ? for each unknown character, the check digit included
+    pub pattern: String,
+
+    /// Most MRNs to print
+    #[arg(short, long, default_value_t = 100)]
+    pub number_of_mrns: usize,
+
+    /// Print random matching MRNs instead of the first ones in order
+    #[arg(long)]
+    pub sample: bool,
+
+    /// Seed for a reproducible sample
+    #[arg(long, requires = "sample")]
+    pub seed: Option<u64>,
+
+    /// Only print the number of matching MRNs
+    #[arg(long, conflicts_with_all = ["number_of_mrns", "sample"])]
+    pub count: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct GrepArgs {
+    /// Mask of the MRNs to print, repeated to print the MRNs matching any of them
+    #[arg(short, long = "mask", required = true)]
+    pub masks: Vec<String>,
+
+    /// Files of MRNs, one per line, standard input if none are given
+    pub files: Vec<PathBuf>,
+
+    /// Print the MRNs matching none of the masks instead
+    #[arg(short = 'v', long)]
+    pub invert_match: bool,
+
+    /// Only print the number of matching MRNs
+    #[arg(short, long)]
+    pub count: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FixArgs {
+    /// Files of MRNs, one per line
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+
+    /// Rewrite the files with the fixed MRNs, instead of only printing the changes
+    #[arg(short, long)]
+    pub in_place: bool,
+
+    /// Keep a copy of each rewritten file, named with this suffix appended, e.g. .bak
+    #[arg(long, value_name = "SUFFIX", requires = "in_place")]
+    pub backup: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FixturesArgs {
+    /// Comma separated country codes or names of the MRNs
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        value_parser = parse_country,
+        default_value = "DK,DE,FR,IT,NL"
+    )]
+    pub country_code: Vec<String>,
+
+    /// Last two digits of the issue year of the MRNs
+    #[arg(long, default_value_t = 24, value_parser = clap::value_parser!(u8).range(0..100))]
+    pub year: u8,
+
+    /// Seed of the MRNs, another seed giving another set of the same shape
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// Print CSV with the country and procedure of each MRN
+    #[arg(long)]
+    pub csv: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CheckDuplicatesArgs {
+    /// Files of MRNs, one per line
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+}
+
+/// Formats of the generated MRNs
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// One MRN per line
+    Plain,
+    /// One JSON object per line with the MRN and its components, see the `schema` command
+    Json,
+    /// Tab separated values with a header row, one row per MRN and its components
+    Tsv,
+    /// Aligned table of the MRNs and their components, plain output if not written to a terminal
+    Table,
+    /// Spreadsheet with one row per MRN and its components, needs --output
+    Xlsx,
+    /// Parquet file with one row per MRN and its components, needs --output
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+/// Groups of the MRNs written to separate files
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum SplitBy {
+    /// One file per country code, named after it
+    Country,
+}
+
+/// Compression formats of the output file
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+/// Formats of the input of `validate`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    /// Plain lines, CSV or a JSON array, detected from the start of the input
+    Auto,
+    /// One MRN per line
+    Plain,
+    /// CSV, with or without a header, the MRNs being in the column whose values look the most
+    /// like MRNs
+    Csv,
+    /// JSON array of MRNs, or of objects with the MRNs in the field whose values look the most
+    /// like MRNs
+    Json,
+}
+
+/// Rows kept by `validate --csv --filter`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum RowFilter {
+    Valid,
+    Invalid,
+}
+
+/// Traffic profiles of `--profile`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Profile {
+    /// Mostly import declarations, in the countries of the main EU ports
+    EuImportHeavy,
+    /// Mostly transit declarations, in the countries along the main road corridors
+    TransitMix,
+}
+
+/// Conventions of specific systems
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Preset {
+    /// ICS2 entry summary declarations, F categories and the office of first entry
+    Ics2Ens,
+    /// Common Transit Convention movements in NCTS, D categories combined with A or F or not, in
+    /// the contracting parties of the convention
+    Ctc,
+    /// Swiss transit declarations, D categories combined with A or not, lodged in CH at its
+    /// offices of departure
+    ChTransit,
+    /// Norwegian transit declarations, D categories combined with A or not, lodged in NO at its
+    /// offices of departure
+    NoTransit,
+    /// UK Customs Declaration Service import and export declarations, H, I, B and C categories,
+    /// lodged in GB at its offices
+    GbCds,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct GrnArgs {
+    /// Country code or name of the guarantee office
+    #[arg(short, long, required_unless_present = "validate", value_parser = parse_country)]
+    pub country_code: Option<String>,
+
+    /// Number of GRNs to generate
+    #[arg(short, long, default_value_t = 1)]
+    pub number_of_grns: usize,
+
+    /// Individual guarantee voucher identifier, appended after the check character
+    #[arg(long)]
+    pub voucher: Option<String>,
+
+    /// Also generate an access code for each GRN
+    #[arg(short, long)]
+    pub access_code: bool,
+
+    /// Validate the given GRN, optionally followed by its access code, instead of generating
+    #[arg(long, num_args = 1..=2, value_names = ["GRN", "ACCESS_CODE"], conflicts_with_all = ["country_code", "voucher", "access_code"])]
+    pub validate: Option<Vec<String>>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct LrnArgs {
+    /// Prefix of every LRN, e.g. the declarant's software code
+    #[arg(long, default_value = "")]
+    pub prefix: String,
+
+    /// Total length of every LRN, prefix included
+    #[arg(short, long, default_value_t = 22)]
+    pub length: usize,
+
+    /// Number of LRNs to generate
+    #[arg(short, long, default_value_t = 1)]
+    pub number_of_lrns: usize,
+
+    /// Print a matching MRN after each LRN
+    #[arg(long, requires = "country_code")]
+    pub with_mrn: bool,
+
+    /// Country code or name of the paired MRNs
+    #[arg(short, long, value_parser = parse_country)]
+    pub country_code: Option<String>,
+
+    /// Procedure category of the paired MRNs
+    #[arg(short, long)]
+    pub procedure_category: Option<String>,
+
+    /// Combined procedure category of the paired MRNs
+    #[arg(short = 'C', long)]
+    pub combined: Option<String>,
+
+    /// Customs office of declaration of the paired MRNs
+    #[arg(short = 'o', long)]
+    pub declaration_office: Option<String>,
+
+    #[command(flatten)]
+    pub source: IdSourceArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DatasetArgs {
+    /// Country code or name of the declarations, or comma separated country codes to spread them
+    /// over
+    #[arg(short, long, required = true, value_delimiter = ',', value_parser = parse_country)]
+    pub country_code: Vec<String>,
+
+    /// Number of declarations to generate
+    #[arg(short, long, default_value_t = 1)]
+    pub number_of_declarations: usize,
+
+    /// Declaration category of every declaration, a random one for each if not given
+    #[arg(short, long)]
+    pub procedure_category: Option<String>,
+
+    /// Combined procedure category of every declaration
+    #[arg(short = 'C', long, requires = "procedure_category")]
+    pub combined: Option<String>,
+
+    /// Prefix of every LRN, e.g. the declarant's software code
+    #[arg(long, default_value = "")]
+    pub lrn_prefix: String,
+
+    /// Spread the acceptance times over the days of a date range, both ends included, e.g.
+    /// 2023-01-01..2024-12-31 [default: the current year up to today]
+    #[arg(long, value_parser = parse_date_range)]
+    pub accepted_over: Option<DateRange>,
+
+    /// Format of the declarations
+    #[arg(long, value_enum, default_value_t = DatasetFormat::Csv)]
+    pub format: DatasetFormat,
+
+    /// Separator of the fields of csv or tsv output instead of a comma or tab, a single character
+    /// or `tab`
+    #[arg(long, value_parser = parse_delimiter)]
+    pub delimiter: Option<u8>,
+
+    /// Seed for a reproducible dataset
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+/// Formats of the generated declarations
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum DatasetFormat {
+    /// CSV with a header row
+    Csv,
+    /// Tab separated values with a header row
+    Tsv,
+    /// One JSON object per line
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct EoriArgs {
+    /// Country code or name of the trader
+    #[arg(short, long, required_unless_present = "validate", value_parser = parse_country)]
+    pub country_code: Option<String>,
+
+    /// Number of EORI numbers to generate
+    #[arg(short, long, default_value_t = 1)]
+    pub number_of_eoris: usize,
+
+    /// Validate the given EORI number instead of generating
+    #[arg(long, value_name = "EORI", conflicts_with = "country_code")]
+    pub validate: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ContainerArgs {
+    /// Owner code of the containers, three letters (random if not given)
+    #[arg(long)]
+    pub owner_code: Option<String>,
+
+    /// Equipment category identifier
+    #[arg(long, value_enum, default_value_t = ContainerCategory::U)]
+    pub category: ContainerCategory,
+
+    /// Number of container numbers to generate
+    #[arg(short, long, default_value_t = 1)]
+    pub number_of_containers: usize,
+
+    /// Validate the given container number instead of generating
+    #[arg(long, value_name = "CONTAINER_NUMBER", conflicts_with_all = ["owner_code", "category"])]
+    pub validate: Option<String>,
+}
+
+/// Equipment category identifiers of container numbers
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ContainerCategory {
+    /// Freight container
+    U,
+    /// Detachable freight container-related equipment
+    J,
+    /// Trailer or chassis
+    Z,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct UcrArgs {
+    /// Kind of consignment reference
+    #[arg(long, value_enum, default_value_t = UcrKind::Ducr)]
+    pub kind: UcrKind,
+
+    /// GB EORI number of the trader (random if not given)
+    #[arg(long)]
+    pub eori: Option<String>,
+
+    /// Number of references to generate
+    #[arg(short, long, default_value_t = 1)]
+    pub number_of_ucrs: usize,
+
+    /// Validate the given reference instead of generating
+    #[arg(long, value_name = "UCR", conflicts_with = "eori")]
+    pub validate: Option<String>,
+}
+
+/// Kinds of UK consignment references
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum UcrKind {
+    /// Declaration Unique Consignment Reference
+    Ducr,
+    /// Master Unique Consignment Reference
+    Mucr,
+}
+
+/// What a batch does when one of its identifiers fails, the last of the two flags wins
+#[derive(clap::Args, Debug)]
+pub struct FailurePolicyArgs {
+    /// Stop at the first identifier that fails, the default when generating
+    #[arg(long, overrides_with = "continue_on_error")]
+    pub fail_fast: bool,
+
+    /// Report the identifiers that fail and carry on with the rest of the batch, failing at the
+    /// end, the default when validating
+    #[arg(long, overrides_with = "fail_fast")]
+    pub continue_on_error: bool,
+}
+
+impl FailurePolicyArgs {
+    /// Returns true if the batch should stop at the first failure, given the default of the
+    /// command
+    pub fn fail_fast(&self, default: bool) -> bool {
+        match (self.fail_fast, self.continue_on_error) {
+            (true, _) => true,
+            (_, true) => false,
+            _ => default,
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BenchArgs {
+    /// Number of MRNs to generate, e.g. 10_000_000
+    #[arg(long, default_value_t = 1_000_000, value_parser = parse_count)]
+    pub count: usize,
+
+    /// Country code or name of the MRNs
+    #[arg(short, long, default_value = "DK", value_parser = parse_country)]
+    pub country_code: String,
+
+    /// Procedure category of the MRNs
+    #[arg(short, long)]
+    pub procedure_category: Option<String>,
+
+    /// Customs office of declaration of the MRNs
+    #[arg(short = 'o', long)]
+    pub declaration_office: Option<String>,
+
+    #[command(flatten)]
+    pub source: IdSourceArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub listen: String,
+
+    /// Seconds until a lease expires and its MRNs can be handed out again, unless the lease
+    /// request gives its own ttl
+    #[arg(long, default_value_t = 300)]
+    pub lease_ttl: u64,
+
+    /// Registry file recording the committed MRNs, so they are never handed out again by this or
+    /// later servers or runs using the same registry
+    #[arg(long, value_name = "FILE")]
+    pub registry: Option<PathBuf>,
+
+    /// JSON lines file to append every generated or leased MRN to, with the time, the request
+    /// and the address of the requester
+    #[arg(long, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+
+    /// URL to POST a summary of each batch of generated or leased MRNs to, with their count, a
+    /// sample and a checksum
+    #[cfg(feature = "online")]
+    #[arg(long, value_name = "URL")]
+    pub webhook: Option<String>,
+
+    /// Maximum number of requests handled at the same time, further connections wait until one
+    /// is answered
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_concurrency: u32,
+
+    /// Seconds a connection may stay idle while its request is read or its response written,
+    /// before it is closed
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+    pub request_timeout: u64,
+
+    /// Comma separated origins allowed to call the server from a browser, e.g.
+    /// https://ui.example.com, or * for any origin
+    #[arg(long, value_delimiter = ',', value_name = "ORIGINS")]
+    pub cors_origins: Vec<String>,
+
+    /// Comma separated methods allowed from the --cors-origins
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "POST",
+        requires = "cors_origins"
+    )]
+    pub cors_methods: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct IdSourceArgs {
+    /// Strategy used to fill the free segment of the generated identifiers
+    #[arg(long, value_enum, default_value_t = IdSourceKind::Random)]
+    pub id_source: IdSourceKind,
+
+    /// Seed for reproducible output with the random or permuted source
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// First counter value of the sequential or permuted source
+    #[arg(long, default_value_t = 0)]
+    pub sequence_start: u64,
+
+    /// Namespace from 0 to 15, reserving the first character of the free segment so that runs in
+    /// different namespaces never generate the same identifiers
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..16))]
+    pub namespace: Option<u8>,
+}
+
+/// Strategies for the free segment of the MRN
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum IdSourceKind {
+    /// Uniformly random characters
+    Random,
+    /// Zero-padded decimal counter
+    Sequential,
+    /// Base-36 encoded timestamp
+    Timestamp,
+    /// Random-looking characters that never repeat, a permutation of a counter keyed by the seed
+    Permuted,
+}
+
+/// Inclusive range of issue years
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YearRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl YearRange {
+    /// Last two digits of every year in the range
+    pub fn years(&self) -> Vec<u8> {
+        (self.start..=self.end).map(|y| (y % 100) as u8).collect()
+    }
+}
+
+/// Range of issue dates, both ends included
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// Parses `start..end` or `start..=end` ranges of `YYYY-MM-DD` dates, both ends included
+fn parse_date_range(s: &str) -> Result<DateRange, String> {
+    let (start, end) = s
+        .split_once("..=")
+        .or_else(|| s.split_once(".."))
+        .ok_or_else(|| format!("{s} is not a date range, expected e.g. 2023-01-01..2024-12-31"))?;
+    let parse = |date: &str| {
+        date.trim()
+            .parse::<NaiveDate>()
+            .map_err(|_| format!("{date} is not a date, expected e.g. 2023-01-01"))
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    if start > end {
+        return Err(format!("{s} ends before it starts"));
+    }
+    Ok(DateRange { start, end })
+}
+
+/// Parses a `YYYY-MM-DD` declaration date
+fn parse_declaration_date(s: &str) -> Result<DeclarationDate, String> {
+    let date = s
+        .trim()
+        .parse::<NaiveDate>()
+        .map_err(|_| format!("{s} is not a date, expected e.g. 2024-03-01"))?;
+    u16::try_from(date.year())
+        .ok()
+        .and_then(|year| DeclarationDate::new(year, date.month() as u8, date.day() as u8))
+        .ok_or_else(|| format!("{s} is not a declaration date"))
+}
+
+/// Parses the separator of delimited output, a single ASCII character other than a quote or line
+/// break, or `tab`
+fn parse_delimiter(s: &str) -> Result<u8, String> {
+    match s.as_bytes() {
+        b"tab" | b"\\t" => Ok(b'\t'),
+        &[b] if b.is_ascii() && !matches!(b, b'"' | b'\n' | b'\r') => Ok(b),
+        _ => Err(format!(
+            "{s} is not a delimiter, expected a single character like ; or tab"
+        )),
+    }
+}
+
+/// Parses a count, allowing underscores as digit separators like 10_000_000
+fn parse_count(s: &str) -> Result<usize, String> {
+    s.replace('_', "")
+        .parse()
+        .map_err(|_| format!("{s} is not a count, expected e.g. 10_000_000"))
+}
+
+/// Resolves a country code or a country name like Denmark to a country code
+fn parse_country(s: &str) -> Result<String, String> {
+    resolve_country(s).map_err(|e| e.to_string())
+}
+
+/// Parses `start..=end` or `start..end` ranges of two or four digit years
+fn parse_year_range(s: &str) -> Result<YearRange, String> {
+    let (start, end, inclusive) = match s.split_once("..=") {
+        Some((start, end)) => (start, end, true),
+        None => {
+            let (start, end) = s
+                .split_once("..")
+                .ok_or_else(|| format!("{s} is not a range, expected e.g. 20..=24"))?;
+            (start, end, false)
+        }
+    };
+
+    let parse = |year: &str| {
+        year.trim()
+            .parse::<u16>()
+            .map_err(|_| format!("{year} is not a year"))
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    let end = if inclusive {
+        Some(end)
+    } else {
+        end.checked_sub(1)
+    };
+
+    match end {
+        Some(end) if start <= end && (end - start) < 100 => Ok(YearRange { start, end }),
+        _ => Err(format!("{s} is not a non-empty range of at most 100 years")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_year_range_test() {
+        assert_eq!(
+            Ok(YearRange { start: 20, end: 24 }),
+            parse_year_range("20..=24")
+        );
+        assert_eq!(
+            Ok(YearRange {
+                start: 2020,
+                end: 2024
+            }),
+            parse_year_range("2020..2025")
+        );
+        assert_eq!(
+            vec![98, 99, 0, 1],
+            parse_year_range("1998..=2001").unwrap().years()
+        );
+        assert!(parse_year_range("24..=20").is_err());
+        assert!(parse_year_range("20..20").is_err());
+        assert!(parse_year_range("20-24").is_err());
+    }
+
+    #[test]
+    fn parse_date_range_test() {
+        let range = parse_date_range("2023-01-01..2024-12-31").unwrap();
+        assert_eq!(
+            (2023, 2024),
+            (
+                chrono::Datelike::year(&range.start),
+                chrono::Datelike::year(&range.end)
+            )
+        );
+        assert_eq!(Ok(range), parse_date_range("2023-01-01..=2024-12-31"));
+        assert!(parse_date_range("2024-01-01..2023-01-01").is_err());
+        assert!(parse_date_range("2024-13-01..2024-12-31").is_err());
+        assert!(parse_date_range("2023-01-01").is_err());
+    }
+
+    #[test]
+    fn parse_count_test() {
+        assert_eq!(Ok(10_000_000), parse_count("10_000_000"));
+        assert_eq!(Ok(1000), parse_count("1000"));
+        assert!(parse_count("1e6").is_err());
+    }
+
+    #[test]
+    fn parse_delimiter_test() {
+        assert_eq!(Ok(b';'), parse_delimiter(";"));
+        assert_eq!(Ok(b'\t'), parse_delimiter("tab"));
+        assert_eq!(Ok(b'|'), parse_delimiter("|"));
+        for delimiter in ["", ";;", "\"", "§"] {
+            assert!(parse_delimiter(delimiter).is_err(), "{delimiter}");
+        }
+    }
+
+    #[test]
+    fn read_countries_file_test() {
+        let path = env::temp_dir().join(format!("mrn-countries-{}.txt", std::process::id()));
+        fs::write(&path, "# Weekly mix\nDK,100\n\nGreece,1_000\n IT , 2 \n").unwrap();
+        assert_eq!(
+            (
+                vec!["DK".to_string(), "GR".to_string(), "IT".to_string()],
+                Some(vec![100, 1000, 2])
+            ),
+            read_countries_file(&path).unwrap()
+        );
+        fs::write(&path, "DK\nIT\n").unwrap();
+        assert_eq!(None, read_countries_file(&path).unwrap().1);
+        fs::write(&path, "DK,3\nGrece\n").unwrap();
+        let error = format!("{:#}", read_countries_file(&path).unwrap_err());
+        assert!(error.starts_with("line 2 of"));
+        assert!(error.contains("did you mean Greece?"));
+        fs::write(&path, "DK,3\nIT\n").unwrap();
+        let error = format!("{:#}", read_countries_file(&path).unwrap_err());
+        assert!(error.contains("IT has no count"), "{error}");
+        fs::write(&path, "DK,many\n").unwrap();
+        assert!(read_countries_file(&path).is_err());
+        fs::remove_file(path).unwrap();
+
+        assert!(Args::try_parse_from(["mrn-generator", "--countries-file", "c.txt"]).is_ok());
+        assert!(
+            Args::try_parse_from(["mrn-generator", "-c", "DK", "--countries-file", "c.txt"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_country_test() {
+        let args = Args::parse_from(["mrn-generator", "-c", "Denmark,gr,Greece"]);
+        assert_eq!(vec!["DK", "GR", "GR"], args.country_code);
+        let args = Args::parse_from(["mrn-generator", "grn", "-c", "Denmark"]);
+        assert!(
+            matches!(args.command, Some(Command::Grn(grn)) if grn.country_code.as_deref() == Some("DK"))
+        );
+        assert!(Args::try_parse_from(["mrn-generator", "eori", "-c", "D1"]).is_err());
+        assert!(parse_country("Grece")
+            .unwrap_err()
+            .contains("did you mean Greece?"));
+    }
+
+    #[test]
+    fn failure_policy_test() {
+        let args = Args::parse_from(["mrn-generator", "-c", "DK"]);
+        assert!(args.failure.fail_fast(true));
+        assert!(!args.failure.fail_fast(false));
+
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK",
+            "--fail-fast",
+            "--continue-on-error",
+        ]);
+        assert!(!args.failure.fail_fast(true));
+    }
 }
@@ -0,0 +1,168 @@
+//! Writing on a thread of its own, fed through a bounded queue, so that a slow output doesn't
+//! stall generation until the queue is full, and generation on several threads feeding it
+
+use crate::exec::ExecHook;
+use crate::output::MrnSink;
+use anyhow::{anyhow, Result};
+use mrn_generator::sink::MrnRecord;
+use mrn_generator::{DeclarationOffice, Procedure};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::thread::{self, JoinHandle};
+
+/// Number of MRNs in a chunk, the work item of a generation thread
+pub const CHUNK_SIZE: usize = 1024;
+
+/// A generated MRN on its way to the writer
+pub struct Generated {
+    pub mrn: String,
+    pub procedure: Option<Procedure>,
+    pub declaration_office: Option<String>,
+    /// Issue date, as `YYYY-MM-DD`
+    pub issued_at: Option<String>,
+    /// LRN paired with the MRN by --with-lrn
+    pub lrn: Option<String>,
+}
+
+impl Generated {
+    fn record(&self) -> Result<MrnRecord<'_>> {
+        Ok(MrnRecord {
+            mrn: &self.mrn,
+            procedure: self.procedure,
+            declaration_office: self
+                .declaration_office
+                .as_deref()
+                .map(DeclarationOffice::new)
+                .transpose()?,
+            issued_at: self.issued_at.as_deref(),
+            lrn: self.lrn.as_deref(),
+        })
+    }
+}
+
+/// Output and --exec hook, handed back by the writer once everything queued is written
+pub type Outputs = (Box<MrnSink>, Option<ExecHook>);
+
+/// Thread writing the generated MRNs to the sink and running the --exec hook on them
+pub struct Writer {
+    queue: SyncSender<Generated>,
+    thread: JoinHandle<Result<Outputs>>,
+}
+
+impl Writer {
+    /// Starts writing, with room for `queue_size` MRNs waiting to be written
+    pub fn spawn(mut sink: Box<MrnSink>, mut exec: Option<ExecHook>, queue_size: usize) -> Self {
+        let (queue, generated) = mpsc::sync_channel::<Generated>(queue_size);
+        let thread = thread::spawn(move || {
+            for generated in generated {
+                let record = generated.record()?;
+                sink.write(&record)?;
+                if let Some(exec) = exec.as_mut() {
+                    exec.run(&record)?;
+                }
+            }
+            Ok((sink, exec))
+        });
+        Writer { queue, thread }
+    }
+
+    /// Queues an MRN, waiting while the queue is full. Returns false if the writer has stopped
+    /// on an error, which [Writer::join] returns.
+    pub fn send(&self, generated: Generated) -> bool {
+        self.queue.send(generated).is_ok()
+    }
+
+    /// Waits until everything queued is written, handing back the sink and the --exec hook
+    /// unfinished
+    pub fn join(self) -> Result<Outputs> {
+        drop(self.queue);
+        self.thread
+            .join()
+            .map_err(|_| anyhow!("the writer thread panicked"))?
+    }
+}
+
+/// Generates `count` MRNs on `jobs` threads, one chunk at a time, with `generate` called with
+/// the number of the chunk and its length. The MRNs are handed to `send` as their chunks are
+/// done or, if `ordered`, in the order of their chunks, until it returns false.
+pub fn generate_parallel(
+    count: usize,
+    jobs: usize,
+    ordered: bool,
+    generate: impl Fn(usize, usize) -> Result<Vec<Result<Generated>>> + Sync,
+    mut send: impl FnMut(Result<Generated>) -> Result<bool>,
+) -> Result<()> {
+    let chunks = count.div_ceil(CHUNK_SIZE);
+    let next_chunk = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        // Chunks are numbered as threads take them, so few are done ahead of the next one in order
+        let (done, chunks_done) = mpsc::sync_channel(jobs);
+        for _ in 0..jobs.max(1) {
+            let (done, next_chunk, generate) = (done.clone(), &next_chunk, &generate);
+            scope.spawn(move || loop {
+                let chunk = next_chunk.fetch_add(1, Ordering::Relaxed);
+                if chunk >= chunks {
+                    break;
+                }
+                let len = CHUNK_SIZE.min(count - chunk * CHUNK_SIZE);
+                if done.send((chunk, generate(chunk, len))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(done);
+
+        let mut pending = BTreeMap::new();
+        let mut next_in_order = 0;
+        for (chunk, generated) in chunks_done {
+            pending.insert(chunk, generated?);
+            while let Some(generated) = match ordered {
+                true => pending.remove(&next_in_order),
+                false => pending.pop_first().map(|(_, generated)| generated),
+            } {
+                next_in_order += 1;
+                for generated in generated {
+                    if !send(generated)? {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::output::sink;
+    use crate::parser::OutputFormat;
+    use std::fs;
+
+    #[test]
+    fn writer_test() {
+        let path = std::env::temp_dir().join("mrn-generator-writer-test.txt");
+        let sink = sink(OutputFormat::Plain, Some(&path), false, None, None, false).unwrap();
+
+        // A queue of one makes generation wait for the writer
+        let writer = Writer::spawn(sink, None, 1);
+        for mrn in ["22ITZXBZYUTJFLJXK6", "26DK004700CJNMYMA6"] {
+            assert!(writer.send(Generated {
+                mrn: mrn.to_string(),
+                procedure: None,
+                declaration_office: None,
+                issued_at: None,
+                lrn: None,
+            }));
+        }
+        let (sink, _) = writer.join().unwrap();
+        sink.finish().unwrap();
+        assert_eq!(
+            "22ITZXBZYUTJFLJXK6\n26DK004700CJNMYMA6\n",
+            fs::read_to_string(&path).unwrap()
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
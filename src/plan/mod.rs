@@ -0,0 +1,276 @@
+//! Generation plan printed by --dry-run, the run as resolved from the config file profile, the
+//! weights file, the preset and the command line
+
+use crate::parser::{Args, IdSourceKind, OutputFormat};
+use clap::ValueEnum;
+use mrn_generator::Procedure;
+use mrn_generator::{all_procedures, procecure_category_to_char, procedure_description};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// What a run would generate and where it would write it
+pub struct Plan<'a> {
+    pub args: &'a Args,
+    /// Country codes and their weights
+    pub countries: (Vec<String>, Option<Vec<u32>>),
+    /// Procedures and their weights, of the traffic profile or weights file
+    pub procedures: Option<(Vec<Procedure>, Vec<u32>)>,
+    /// Procedure of every MRN otherwise
+    pub procedure: Option<Procedure>,
+    /// Offices of the weights file and their weights, by country code
+    pub offices: Option<&'a BTreeMap<String, Vec<(String, u32)>>>,
+    /// Share of the MRNs with a wrong check digit
+    pub invalid_rate: f64,
+    /// Number of distinct MRNs possible
+    pub capacity: u128,
+}
+
+/// Values with their weights if they have any, e.g. `DK (3), IT (1)`
+fn weighted(values: &[String], weights: Option<&[u32]>) -> String {
+    let values = values.iter().enumerate().map(|(i, value)| {
+        match weights.and_then(|weights| weights.get(i)) {
+            Some(weight) => format!("{value} ({weight})"),
+            None => value.clone(),
+        }
+    });
+    values.collect::<Vec<_>>().join(", ")
+}
+
+/// Procedure character and description, e.g. `A Export only`
+fn procedure_label(procedure: Procedure) -> String {
+    format!(
+        "{} {}",
+        procecure_category_to_char(procedure),
+        procedure_description(procedure)
+    )
+}
+
+/// Command line name of a value
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+impl fmt::Display for Plan<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args = self.args;
+        let line = |f: &mut fmt::Formatter<'_>, label: &str, value: &str| {
+            writeln!(f, "{:<13}{value}", format!("{label}:"))
+        };
+
+        line(
+            f,
+            "MRNs",
+            &format!(
+                "{} of {} distinct possible MRNs",
+                args.number_of_mrns, self.capacity
+            ),
+        )?;
+
+        let (codes, weights) = &self.countries;
+        let countries = weighted(codes, weights.as_deref());
+        match (args.all_countries, &args.country_counts) {
+            (Some(count), _) => line(f, "Countries", &format!("{countries}, {count} MRNs each"))?,
+            (None, Some(counts)) => {
+                let counts = codes
+                    .iter()
+                    .zip(counts)
+                    .map(|(code, count)| format!("{code} ({count} MRNs)"));
+                line(f, "Countries", &counts.collect::<Vec<_>>().join(", "))?
+            }
+            (None, None) => line(f, "Countries", &countries)?,
+        }
+
+        let procedures = match (args.count_per_procedure, &self.procedures, self.procedure) {
+            (Some(count), _, _) => format!(
+                "every procedure, {} to {}, {count} MRNs each",
+                procecure_category_to_char(all_procedures()[0]),
+                procecure_category_to_char(*all_procedures().last().unwrap())
+            ),
+            (None, Some((procedures, weights)), _) => {
+                let names: Vec<String> = procedures.iter().copied().map(procedure_label).collect();
+                weighted(&names, Some(weights))
+            }
+            (None, None, Some(procedure)) => procedure_label(procedure),
+            (None, None, None) => "none, the procedure character is part of the serial".to_string(),
+        };
+        line(f, "Procedures", &procedures)?;
+
+        let offices = match (self.offices, args.declaration_office.as_deref()) {
+            (Some(offices), _) if !offices.is_empty() => offices
+                .iter()
+                .map(|(country, offices)| {
+                    let (numbers, weights): (Vec<String>, Vec<u32>) =
+                        offices.iter().cloned().unzip();
+                    format!("{country} {}", weighted(&numbers, Some(&weights)))
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+            _ if args.random_office => "random, from the customs office list".to_string(),
+            (_, Some(office)) => office.to_string(),
+            _ => "none".to_string(),
+        };
+        line(f, "Offices", &offices)?;
+
+        let years = match (args.year_range, args.spread_over) {
+            (Some(range), _) => {
+                let years: Vec<String> = (range.start..=range.end).map(|y| y.to_string()).collect();
+                weighted(&years, args.year_weights.as_deref())
+            }
+            (None, Some(range)) => format!("issued from {} to {}", range.start, range.end),
+            (None, None) => "the current year".to_string(),
+        };
+        line(f, "Years", &years)?;
+
+        let source = &args.source;
+        let mut id_source = value_name(source.id_source);
+        if matches!(
+            source.id_source,
+            IdSourceKind::Sequential | IdSourceKind::Permuted
+        ) {
+            id_source += &format!(", starting from {}", source.sequence_start);
+        }
+        if let Some(namespace) = source.namespace {
+            id_source += &format!(", namespace {namespace}");
+        }
+        line(f, "ID source", &id_source)?;
+        match source.seed {
+            Some(seed) => line(f, "Seed", &seed.to_string())?,
+            None if args.parallel || args.audit_log.is_some() => {
+                line(f, "Seed", "random, chosen at run time")?
+            }
+            None => line(f, "Seed", "none, every run differs")?,
+        }
+        if self.invalid_rate > 0.0 {
+            line(
+                f,
+                "Invalid",
+                &format!(
+                    "{}% with a wrong check digit",
+                    (self.invalid_rate * 100.0).round()
+                ),
+            )?;
+        }
+        if args.with_lrn {
+            line(f, "LRNs", "paired with each MRN")?;
+        }
+
+        let mut output = match (args.split_by, &args.output_dir, &args.output) {
+            (Some(split_by), Some(dir), _) => format!(
+                "one {} file per {} in {}",
+                value_name(args.format),
+                value_name(split_by),
+                dir.display()
+            ),
+            (_, _, Some(path)) => format!("{} as {}", path.display(), value_name(args.format)),
+            _ => format!("standard output as {}", value_name(args.format)),
+        };
+        if let Some(compression) = args.compress {
+            output += &format!(", {} compressed", value_name(compression));
+        }
+        if args.append {
+            output += ", appended";
+        }
+        if args.format == OutputFormat::Tsv {
+            if let Some(delimiter) = args.delimiter {
+                output += &format!(", separated by {:?}", char::from(delimiter));
+            }
+        }
+        line(f, "Output", &output)?;
+        if args.manifest {
+            line(f, "Manifest", "manifest.json next to the output")?;
+        }
+        if let Some(command) = &args.exec {
+            line(
+                f,
+                "Exec",
+                &format!("{command} ({} at a time)", args.exec_jobs),
+            )?;
+        }
+        for (label, path) in [
+            ("Registry", &args.registry),
+            ("Seen filter", &args.seen_filter),
+            ("Audit log", &args.audit_log),
+        ] {
+            if let Some(path) = path {
+                line(f, label, &path.display().to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn plan_test() {
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK,IT",
+            "--country-weights",
+            "3,1",
+            "-n",
+            "10",
+            "-p",
+            "B1",
+            "--seed",
+            "7",
+            "--format",
+            "tsv",
+            "--output",
+            "mrns.tsv",
+        ]);
+        let plan = Plan {
+            args: &args,
+            countries: (args.country_code.clone(), args.country_weights.clone()),
+            procedures: None,
+            procedure: Some(Procedure::ExportOnly),
+            offices: None,
+            invalid_rate: 0.0,
+            capacity: 1000,
+        };
+        assert_eq!(
+            "\
+MRNs:        10 of 1000 distinct possible MRNs
+Countries:   DK (3), IT (1)
+Procedures:  A Export only
+Offices:     none
+Years:       the current year
+ID source:   random
+Seed:        7
+Output:      mrns.tsv as tsv
+",
+            plan.to_string()
+        );
+
+        // The timestamp source has no start and the seed of a parallel run is chosen as it runs
+        let args = Args::parse_from([
+            "mrn-generator",
+            "-c",
+            "DK",
+            "--id-source",
+            "timestamp",
+            "--sequence-start",
+            "100",
+            "--parallel",
+        ]);
+        let plan = Plan {
+            args: &args,
+            countries: (args.country_code.clone(), None),
+            ..plan
+        };
+        let plan = plan.to_string();
+        assert!(plan.contains("ID source:   timestamp\n"), "{plan}");
+        assert!(
+            plan.contains("Seed:        random, chosen at run time\n"),
+            "{plan}"
+        );
+    }
+}
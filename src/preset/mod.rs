@@ -0,0 +1,136 @@
+//! Presets, the conventions of specific systems: the checks of their MRNs and, for some, the
+//! countries, procedures and offices generated when the command line doesn't give them, read from
+//! the weights files under `data/presets`
+
+use crate::parser::Preset;
+use crate::weights::Weights;
+use mrn_generator::{cds, ens, layout, transit, MrnGeneratorError, Procedure};
+
+/// Default countries, procedures and offices of a preset, None if it has none
+pub fn weights(preset: Preset) -> Option<Weights> {
+    let contents = match preset {
+        Preset::Ics2Ens => return None,
+        Preset::Ctc => include_str!("../../data/presets/ctc.toml"),
+        Preset::ChTransit => include_str!("../../data/presets/ch-transit.toml"),
+        Preset::NoTransit => include_str!("../../data/presets/no-transit.toml"),
+        Preset::GbCds => include_str!("../../data/presets/gb-cds.toml"),
+    };
+    Some(Weights::from_toml(contents).expect("the preset data files are valid weights files"))
+}
+
+/// Checks that a procedure follows the conventions of a preset, defaulting to the usual procedure
+/// of the preset if there is none
+pub fn check_procedure(
+    preset: Preset,
+    procedure: Option<Procedure>,
+) -> Result<Option<Procedure>, MrnGeneratorError> {
+    match preset {
+        Preset::Ics2Ens => ens::check_ens_procedure(procedure).map(Some),
+        Preset::Ctc | Preset::ChTransit | Preset::NoTransit => {
+            transit::check_transit_procedure(procedure).map(Some)
+        }
+        Preset::GbCds => cds::check_cds_procedure(procedure).map(Some),
+    }
+}
+
+/// The only country of a preset, None if it has several or any
+fn pinned_country(preset: Preset) -> Option<&'static str> {
+    match preset {
+        Preset::ChTransit => Some("CH"),
+        Preset::NoTransit => Some("NO"),
+        Preset::GbCds => Some(cds::CDS_COUNTRY),
+        Preset::Ics2Ens | Preset::Ctc => None,
+    }
+}
+
+/// Checks that MRNs of a country can follow the conventions of a preset
+pub fn check_country(preset: Preset, country_code: &str) -> Result<(), MrnGeneratorError> {
+    match (preset, pinned_country(preset)) {
+        (Preset::Ctc, _) if !transit::is_ctc_country(country_code) => {
+            Err(MrnGeneratorError::NotACtcCountry(country_code.to_string()))
+        }
+        (_, Some(expected)) if country_code != expected => {
+            Err(MrnGeneratorError::UnexpectedCountry {
+                found: country_code.to_string(),
+                expected: expected.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that an MRN is valid and plausible under the conventions of a preset
+pub fn validate_mrn(preset: Preset, mrn: &str) -> Result<(), MrnGeneratorError> {
+    match preset {
+        Preset::Ics2Ens => ens::validate_ens_mrn(mrn),
+        Preset::Ctc => transit::validate_ctc_mrn(mrn),
+        Preset::ChTransit | Preset::NoTransit => {
+            transit::validate_ctc_mrn(mrn)?;
+            check_country(preset, &mrn[layout::COUNTRY_CODE])
+        }
+        Preset::GbCds => cds::validate_cds_mrn(mrn),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn preset_weights_test() {
+        // The data files follow the conventions of their presets
+        for &preset in Preset::value_variants() {
+            let Some(weights) = weights(preset) else {
+                continue;
+            };
+            for (country, _) in &weights.countries {
+                assert_eq!(Ok(()), check_country(preset, country));
+            }
+            for &(procedure, _) in &weights.procedures {
+                assert_eq!(
+                    Ok(Some(procedure)),
+                    check_procedure(preset, Some(procedure))
+                );
+            }
+        }
+
+        let ctc = weights(Preset::Ctc).unwrap();
+        assert_eq!(transit::CTC_COUNTRIES.len(), ctc.countries.len());
+        assert_eq!(
+            Err(MrnGeneratorError::NotACtcCountry("SM".to_string())),
+            check_country(Preset::Ctc, "SM")
+        );
+        for preset in [Preset::ChTransit, Preset::NoTransit, Preset::GbCds] {
+            let weights = weights(preset).unwrap();
+            let country = pinned_country(preset).unwrap();
+            assert_eq!(vec![(country.to_string(), 1)], weights.countries);
+            assert!(weights.offices.contains_key(country));
+        }
+        assert_eq!(
+            Err(MrnGeneratorError::UnexpectedCountry {
+                found: "DE".to_string(),
+                expected: "CH".to_string()
+            }),
+            check_country(Preset::ChTransit, "DE")
+        );
+    }
+
+    #[test]
+    fn validate_mrn_test() {
+        assert_eq!(Ok(()), validate_mrn(Preset::Ctc, "21BEQ22D4Q2F66DEJ1"));
+        assert_eq!(
+            Ok(()),
+            validate_mrn(Preset::ChTransit, "26CH0010413FENHXJ7")
+        );
+        assert_eq!(Ok(()), validate_mrn(Preset::GbCds, "26GB000084ZANVFSA4"));
+        assert_eq!(
+            Err(MrnGeneratorError::UnexpectedCountry {
+                found: "BE".to_string(),
+                expected: "NO".to_string()
+            }),
+            validate_mrn(Preset::NoTransit, "21BEQ22D4Q2F66DEJ1")
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Procedures in JSON and other serde formats, as the names or characters user interfaces use for
+//! them
+//!
+//! [Procedure] serializes as its stable snake_case [name](crate::procedure_name), e.g.
+//! `"export_only"`. Fields annotated with `#[serde(with = "mrn_generator::procedure_code::character")]`
+//! serialize as the procedure character of MRNs instead, e.g. `"A"`, and
+//! [character::option] does the same for `Option<Procedure>`. Either way a procedure deserializes
+//! from its name or its character, so values round-trip whichever form the other side picked.
+//!
+//! ## Example
+//! ```
+//! use mrn_generator::Procedure;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Form {
+//!     procedure: Procedure,
+//!     #[serde(with = "mrn_generator::procedure_code::character")]
+//!     dropdown: Procedure,
+//! }
+//!
+//! let form = Form {
+//!     procedure: Procedure::ExportOnly,
+//!     dropdown: Procedure::TransitDeclarationOnly,
+//! };
+//! let json = serde_json::to_string(&form).unwrap();
+//! assert_eq!(r#"{"procedure":"export_only","dropdown":"J"}"#, json);
+//! assert_eq!(form, serde_json::from_str(&json).unwrap());
+//! assert_eq!(
+//!     form,
+//!     serde_json::from_str(r#"{"procedure":"A","dropdown":"transit_declaration_only"}"#).unwrap()
+//! );
+//! ```
+
+use crate::{lookup_procedure, procedure_name, Procedure};
+use alloc::string::String;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+impl Serialize for Procedure {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(procedure_name(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Procedure {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        lookup_procedure(&code).map_err(de::Error::custom)
+    }
+}
+
+/// Procedures as their characters, for `#[serde(with = "mrn_generator::procedure_code::character")]`
+pub mod character {
+    use super::*;
+    use crate::procecure_category_to_char;
+
+    pub fn serialize<S: Serializer>(
+        procedure: &Procedure,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(procecure_category_to_char(*procedure))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Procedure, D::Error> {
+        Procedure::deserialize(deserializer)
+    }
+
+    /// Optional procedures as their characters, for
+    /// `#[serde(with = "mrn_generator::procedure_code::character::option")]`
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            procedure: &Option<Procedure>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match procedure {
+                Some(procedure) => {
+                    serializer.serialize_some(&procecure_category_to_char(*procedure))
+                }
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Procedure>, D::Error> {
+            Option::<Procedure>::deserialize(deserializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use alloc::string::ToString;
+    use serde::de::value::{Error as ValueError, StrDeserializer};
+    use serde::de::IntoDeserializer;
+
+    #[test]
+    fn procedure_code_test() {
+        let deserialize = |code: &str| {
+            let code: StrDeserializer<ValueError> = code.into_deserializer();
+            Procedure::deserialize(code)
+        };
+        assert_eq!(Ok(Procedure::ImportDeclarationOnly), deserialize("R"));
+        assert_eq!(
+            Ok(Procedure::ImportDeclarationOnly),
+            deserialize("import_declaration_only")
+        );
+        assert!(deserialize("import")
+            .unwrap_err()
+            .to_string()
+            .contains("not a known procedure"));
+    }
+}
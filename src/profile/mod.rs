@@ -0,0 +1,97 @@
+//! Traffic profiles, realistic mixes of countries and procedures for load testing
+
+use crate::parser::Profile;
+use mrn_generator::Procedure;
+
+/// Country and procedure weights of a traffic profile
+pub struct Traffic {
+    pub countries: &'static [(&'static str, u32)],
+    pub procedures: &'static [(Procedure, u32)],
+}
+
+impl Traffic {
+    /// Country codes of the profile and their weights
+    pub fn countries(&self) -> (Vec<String>, Vec<u32>) {
+        self.countries
+            .iter()
+            .map(|(code, weight)| (code.to_string(), *weight))
+            .unzip()
+    }
+
+    /// Procedures of the profile and their weights
+    pub fn procedures(&self) -> (Vec<Procedure>, Vec<u32>) {
+        self.procedures.iter().copied().unzip()
+    }
+}
+
+/// Import declarations through the main EU ports of entry, with some entry summary declarations,
+/// temporary storage and exports on the side
+const EU_IMPORT_HEAVY: Traffic = Traffic {
+    countries: &[
+        ("NL", 30),
+        ("DE", 25),
+        ("BE", 15),
+        ("FR", 10),
+        ("IT", 8),
+        ("ES", 7),
+        ("PL", 5),
+    ],
+    procedures: &[
+        (Procedure::ImportDeclarationOnly, 60),
+        (Procedure::ImportDeclarationAndEntrySummaryDeclaration, 10),
+        (Procedure::EntrySummaryDeclarationOnly, 10),
+        (Procedure::TemporaryStorageDeclaration, 10),
+        (Procedure::ExportOnly, 10),
+    ],
+};
+
+/// Transit declarations along the main road corridors, with some proofs of union status
+const TRANSIT_MIX: Traffic = Traffic {
+    countries: &[
+        ("DE", 20),
+        ("NL", 15),
+        ("PL", 15),
+        ("BE", 10),
+        ("AT", 10),
+        ("CZ", 10),
+        ("HU", 10),
+        ("IT", 10),
+    ],
+    procedures: &[
+        (Procedure::TransitDeclarationOnly, 70),
+        (Procedure::TransitDeclarationAndExitSummaryDeclaration, 10),
+        (Procedure::TransitDeclarationAndEntrySummaryDeclaration, 10),
+        (Procedure::ProofOfTheCustomsStatusOfUnionGoods, 10),
+    ],
+};
+
+/// Weights of a traffic profile
+pub fn traffic(profile: Profile) -> &'static Traffic {
+    match profile {
+        Profile::EuImportHeavy => &EU_IMPORT_HEAVY,
+        Profile::TransitMix => &TRANSIT_MIX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use clap::ValueEnum;
+    use mrn_generator::country::country_name;
+
+    #[test]
+    fn traffic_test() {
+        for profile in Profile::value_variants() {
+            let traffic = traffic(*profile);
+            assert!(traffic
+                .countries
+                .iter()
+                .all(|(code, _)| country_name(code).is_some()));
+            let (procedures, weights) = traffic.procedures();
+            assert_eq!(procedures.len(), weights.len());
+            assert_eq!(100, weights.iter().sum::<u32>());
+            assert_eq!(100, traffic.countries().1.iter().sum::<u32>());
+        }
+    }
+}
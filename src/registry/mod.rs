@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Append-only store of every MRN issued by runs sharing the same file
+///
+/// The registry file holds an exclusive advisory lock from `open` until the
+/// registry is dropped, so concurrent invocations using the same registry are
+/// serialized instead of issuing overlapping MRNs or interleaving writes.
+pub struct Registry {
+    issued: HashSet<String>,
+    writer: BufWriter<File>,
+}
+
+impl Registry {
+    /// Opens (creating it if needed) and locks the registry, blocking while another process holds it
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        file.lock()?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let issued = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        Ok(Registry {
+            issued,
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Returns true if the MRN was already issued
+    pub fn contains(&self, mrn: &str) -> bool {
+        self.issued.contains(mrn)
+    }
+
+    /// Records the MRN as issued, it is persisted on `commit`
+    pub fn record(&mut self, mrn: &str) -> io::Result<()> {
+        self.issued.insert(mrn.to_string());
+        writeln!(self.writer, "{mrn}")
+    }
+
+    /// Flushes the MRNs recorded so far to disk, keeping the lock
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()
+    }
+
+    /// Flushes all recorded MRNs to disk and releases the lock
+    pub fn commit(self) -> io::Result<()> {
+        let file = self.writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_data()?;
+        file.unlock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn record_and_reopen_test() {
+        let path = std::env::temp_dir().join(format!("mrn-registry-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut registry = Registry::open(&path).unwrap();
+        assert!(!registry.contains("22ITZXBZYUTJFLJXK6"));
+        registry.record("22ITZXBZYUTJFLJXK6").unwrap();
+        assert!(registry.contains("22ITZXBZYUTJFLJXK6"));
+        registry.commit().unwrap();
+
+        let registry = Registry::open(&path).unwrap();
+        assert!(registry.contains("22ITZXBZYUTJFLJXK6"));
+        drop(registry);
+
+        assert_eq!("22ITZXBZYUTJFLJXK6\n", fs::read_to_string(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+//! Text representations of generated MRNs
+//!
+//! The command line utility, its HTTP server and embedders all render records through these
+//! functions, so that the same record is written as the same bytes by each of them. The
+//! functions return a line without its line break.
+
+use crate::sink::{MrnRecord, COLUMNS, ISSUED_AT_COLUMN, LRN_COLUMN};
+use crate::{layout, procecure_category_to_char};
+use std::fmt::Write;
+
+/// Keys of the JSON records, named like the columns of the parquet output of the command line
+const JSON_KEYS: [&str; 8] = [
+    "mrn",
+    "year",
+    "country_code",
+    "declaration_office",
+    "serial_number",
+    "procedure_character",
+    "procedure",
+    "check_digit",
+];
+
+/// Optional fields of a record, with their column names and JSON keys
+fn optional<'a>(record: &MrnRecord<'a>) -> [(&'static str, &'static str, Option<&'a str>); 2] {
+    [
+        (ISSUED_AT_COLUMN, "issued_at", record.issued_at),
+        (LRN_COLUMN, "lrn", record.lrn),
+    ]
+}
+
+/// The MRN, preceded by its LRN if it has one
+///
+/// ## Example
+/// ```
+/// use mrn_generator::render::plain;
+/// use mrn_generator::sink::MrnRecord;
+///
+/// let record = MrnRecord {
+///     mrn: "22ITZXBZYUTJFLJXK6",
+///     procedure: None,
+///     declaration_office: None,
+///     issued_at: None,
+///     lrn: Some("DECL-0000001"),
+/// };
+/// assert_eq!("DECL-0000001 22ITZXBZYUTJFLJXK6", plain(&record));
+/// ```
+pub fn plain(record: &MrnRecord) -> String {
+    match record.lrn {
+        Some(lrn) => format!("{lrn} {}", record.mrn),
+        None => record.mrn.to_string(),
+    }
+}
+
+/// The [plain] line preceded by the procedure character of the MRN, if it has one
+pub fn labeled(record: &MrnRecord) -> String {
+    match record.procedure {
+        Some(procedure) => format!(
+            "{} {}",
+            procecure_category_to_char(procedure),
+            plain(record)
+        ),
+        None => plain(record),
+    }
+}
+
+/// The MRN split into its components as a JSON object, as described by
+/// `schemas/record.schema.json`, with its keys in alphabetical order
+pub fn json(record: &MrnRecord) -> String {
+    let fields = record.fields();
+    let mut entries: Vec<(&str, &str)> = JSON_KEYS
+        .into_iter()
+        .zip(fields.iter().map(String::as_str))
+        .chain(
+            optional(record)
+                .into_iter()
+                .filter_map(|(_, key, value)| Some((key, value?))),
+        )
+        .collect();
+    entries.sort_unstable_by_key(|&(key, _)| key);
+
+    let mut object = String::from("{");
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            object.push(',');
+        }
+        push_json_string(&mut object, key);
+        object.push(':');
+        push_json_string(&mut object, value);
+    }
+    object.push('}');
+    object
+}
+
+/// Appends a JSON string literal, escaped like serde_json escapes it
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' => write!(out, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Column names of the rows of records like this one, [COLUMNS] followed by those of the optional
+/// fields it has
+pub fn header(record: &MrnRecord) -> Vec<&'static str> {
+    COLUMNS
+        .into_iter()
+        .chain(
+            optional(record)
+                .into_iter()
+                .filter_map(|(name, _, value)| value.map(|_| name)),
+        )
+        .collect()
+}
+
+/// The fields of a record in the order of its [header]
+pub fn row(record: &MrnRecord) -> Vec<String> {
+    let mut fields = Vec::from(record.fields());
+    fields.extend(
+        optional(record)
+            .into_iter()
+            .filter_map(|(_, _, value)| value.map(str::to_string)),
+    );
+    fields
+}
+
+/// Fields separated by a delimiter, those containing it, a quote or a line break quoted like CSV
+/// quotes them
+///
+/// ## Example
+/// ```
+/// use mrn_generator::render::delimited;
+///
+/// assert_eq!("a\tb c", delimited(&["a", "b c"], '\t'));
+/// assert_eq!("a,\"b,c\",\"\"\"d\"\"\"", delimited(&["a", "b,c", "\"d\""], ','));
+/// ```
+pub fn delimited<S: AsRef<str>>(fields: &[S], delimiter: char) -> String {
+    let mut line = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field = field.as_ref();
+        if i > 0 {
+            line.push(delimiter);
+        }
+        if field.contains([delimiter, '"', '\n', '\r']) {
+            line.push('"');
+            line.push_str(&field.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(field);
+        }
+    }
+    line
+}
+
+/// Replaces the placeholders of a template with the fields of a record: `{}` with the MRN,
+/// `{country}` with its country code, `{office}` with its declaration office and `{lrn}` with the
+/// LRN paired with it
+///
+/// ## Example
+/// ```
+/// use mrn_generator::render::template;
+/// use mrn_generator::sink::MrnRecord;
+///
+/// let record = MrnRecord {
+///     mrn: "22ITZXBZYUTJFLJXK6",
+///     procedure: None,
+///     declaration_office: None,
+///     issued_at: None,
+///     lrn: None,
+/// };
+/// assert_eq!("IT: 22ITZXBZYUTJFLJXK6", template("{country}: {}", &record));
+/// ```
+pub fn template(template: &str, record: &MrnRecord) -> String {
+    template
+        .replace("{country}", &record.mrn[layout::COUNTRY_CODE])
+        .replace(
+            "{office}",
+            record
+                .declaration_office
+                .map_or("", |office| office.as_str()),
+        )
+        .replace("{lrn}", record.lrn.unwrap_or_default())
+        .replace("{}", record.mrn)
+}
+
+/// Aligned table of rows under a header, e.g. a [header] and the [row]s of records
+#[cfg(feature = "table")]
+pub fn table<S: AsRef<str>>(header: &[&str], rows: &[Vec<S>]) -> String {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_style(comfy_table::presets::UTF8_FULL_CONDENSED)
+        .set_header(header)
+        .add_rows(
+            rows.iter()
+                .map(|row| row.iter().map(AsRef::as_ref).collect::<Vec<&str>>()),
+        );
+    table.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{DeclarationOffice, Procedure};
+
+    fn record<'a>(lrn: Option<&'a str>) -> MrnRecord<'a> {
+        MrnRecord {
+            mrn: "26DK004700CJNMYMA6",
+            procedure: Some(Procedure::ExportOnly),
+            declaration_office: Some(DeclarationOffice::new("004700").unwrap()),
+            issued_at: None,
+            lrn,
+        }
+    }
+
+    #[test]
+    fn json_test() {
+        // The same bytes as serde_json writes for the record as a map
+        for lrn in [None, Some("DECL-\"1\"\\\n\u{1}é")] {
+            let record = record(lrn);
+            let mut expected: serde_json::Map<String, serde_json::Value> = JSON_KEYS
+                .into_iter()
+                .zip(record.fields())
+                .map(|(key, field)| (key.to_string(), field.into()))
+                .collect();
+            if let Some(lrn) = lrn {
+                expected.insert("lrn".to_string(), lrn.into());
+            }
+            assert_eq!(
+                serde_json::Value::Object(expected).to_string(),
+                json(&record)
+            );
+        }
+    }
+
+    #[test]
+    fn row_test() {
+        let record = record(Some("DECL-0000001"));
+        assert_eq!(COLUMNS.len() + 1, header(&record).len());
+        assert_eq!(Some(&LRN_COLUMN), header(&record).last());
+        assert_eq!("DECL-0000001", row(&record)[COLUMNS.len()]);
+        assert_eq!(
+            "26DK004700CJNMYMA6;2026;DK;004700;CJNMYM;A;Export only;6;DECL-0000001",
+            delimited(&row(&record), ';')
+        );
+        assert_eq!(COLUMNS.len(), header(&self::record(None)).len());
+        assert_eq!("\"a;b\";\"\r\"", delimited(&["a;b", "\r"], ';'));
+    }
+
+    #[test]
+    fn labeled_test() {
+        assert_eq!(
+            "A DECL-1 26DK004700CJNMYMA6",
+            labeled(&record(Some("DECL-1")))
+        );
+        let record = MrnRecord {
+            procedure: None,
+            ..record(None)
+        };
+        assert_eq!(plain(&record), labeled(&record));
+    }
+
+    #[test]
+    fn template_test() {
+        assert_eq!(
+            "curl -d 26DK004700CJNMYMA6 https://example.com/DK/004700/DECL-0000001",
+            template(
+                "curl -d {} https://example.com/{country}/{office}/{lrn}",
+                &record(Some("DECL-0000001"))
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "table")]
+    fn table_test() {
+        let rows = [row(&record(Some("LRN1"))), row(&record(Some("LRN2")))];
+        let table = table(&header(&record(Some("LRN1"))), &rows);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(6, lines.len());
+        assert!(lines[1].contains("Country code") && lines[1].contains("LRN"));
+        assert!(lines[3].contains("26DK004700CJNMYMA6") && lines[3].contains("LRN1"));
+        assert!(lines[4].contains("LRN2"));
+        // Every line is as wide as the others
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().count() == lines[0].chars().count()));
+    }
+}
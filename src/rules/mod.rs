@@ -0,0 +1,135 @@
+//! Procedure mapping rules that extend or override the built-in procedure category matrix
+//!
+//! Rules files list the mappings to add, in TOML:
+//! ```toml
+//! [[rule]]
+//! category = "B1"
+//! combined = "Z"
+//! procedure = "export_and_exit_summary_declaration"
+//! ```
+//! or the equivalent JSON, `{"rule": [{"category": "B1", "combined": "Z", ...}]}`.
+
+use crate::{match_procedure, MrnGeneratorError, Procedure};
+use serde::Deserialize;
+
+/// Maps a procedure category, optionally combined with another one, to a procedure
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+pub struct ProcedureRule {
+    pub category: String,
+    #[serde(default)]
+    pub combined: Option<String>,
+    pub procedure: Procedure,
+}
+
+/// Procedure mapping rules, consulted before the built-in matrix
+#[derive(Debug, PartialEq, Clone, Default, Deserialize)]
+pub struct ProcedureRules {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<ProcedureRule>,
+}
+
+impl ProcedureRules {
+    /// Parses rules from a TOML document
+    pub fn from_toml(s: &str) -> Result<Self, MrnGeneratorError> {
+        toml::from_str(s).map_err(|e| MrnGeneratorError::InvalidRules(e.message().to_string()))
+    }
+
+    /// Parses rules from a JSON document
+    pub fn from_json(s: &str) -> Result<Self, MrnGeneratorError> {
+        serde_json::from_str(s).map_err(|e| MrnGeneratorError::InvalidRules(e.to_string()))
+    }
+
+    /// Matches a procedure category like [match_procedure], the last matching rule takes
+    /// precedence over earlier rules and the built-in matrix
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::rules::ProcedureRules;
+    /// use mrn_generator::Procedure;
+    ///
+    /// let rules = ProcedureRules::from_toml(
+    ///     r#"
+    ///     [[rule]]
+    ///     category = "B1"
+    ///     combined = "Z"
+    ///     procedure = "export_and_exit_summary_declaration"
+    ///     "#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Ok(Procedure::ExportAndExitSummaryDeclaration),
+    ///     rules.match_procedure("B1", Some("Z"))
+    /// );
+    /// assert_eq!(Ok(Procedure::ExportOnly), rules.match_procedure("B1", None));
+    /// ```
+    pub fn match_procedure(
+        &self,
+        proctgr: &str,
+        combined: Option<&str>,
+    ) -> Result<Procedure, MrnGeneratorError> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.category == proctgr && rule.combined.as_deref() == combined)
+            .map(|rule| Ok(rule.procedure))
+            .unwrap_or_else(|| match_procedure(proctgr, combined))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn rules_override_test() {
+        let rules = ProcedureRules::from_toml(
+            r#"
+            [[rule]]
+            category = "A3"
+            procedure = "export_only"
+
+            [[rule]]
+            category = "A3"
+            procedure = "exit_summary_declaration_only"
+
+            [[rule]]
+            category = "X1"
+            combined = "F"
+            procedure = "import_declaration_and_entry_summary_declaration"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok(Procedure::ExitSummaryDeclarationOnly),
+            rules.match_procedure("A3", None)
+        );
+        assert_eq!(
+            Ok(Procedure::ImportDeclarationAndEntrySummaryDeclaration),
+            rules.match_procedure("X1", Some("F"))
+        );
+        assert!(matches!(
+            rules.match_procedure("X1", None),
+            Err(MrnGeneratorError::InvalidProcedureCategory { value, .. }) if value == "X1"
+        ));
+    }
+
+    #[test]
+    fn rules_json_test() {
+        let rules = ProcedureRules::from_json(
+            r#"{"rule": [{"category": "G5", "procedure": "temporary_storage_declaration"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Ok(Procedure::TemporaryStorageDeclaration),
+            rules.match_procedure("G5", None)
+        );
+
+        assert!(matches!(
+            ProcedureRules::from_json(r#"{"rule": [{"category": "G5", "procedure": "nope"}]}"#),
+            Err(MrnGeneratorError::InvalidRules(_))
+        ));
+    }
+}
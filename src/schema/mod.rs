@@ -0,0 +1,62 @@
+//! JSON Schemas of the machine-readable output, published under `schemas/`
+
+use crate::parser::SchemaKind;
+use anyhow::Result;
+
+/// JSON Schema of a document written by the command line utility
+pub fn schema(kind: SchemaKind) -> &'static str {
+    match kind {
+        SchemaKind::Record => include_str!("../../schemas/record.schema.json"),
+        SchemaKind::Error => include_str!("../../schemas/error.schema.json"),
+        SchemaKind::Manifest => include_str!("../../schemas/manifest.schema.json"),
+    }
+}
+
+/// Prints the JSON Schema of a document
+pub fn print_schema(kind: SchemaKind) -> Result<()> {
+    print!("{}", schema(kind));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use clap::ValueEnum;
+    use mrn_generator::render;
+    use mrn_generator::sink::MrnRecord;
+    use mrn_generator::{DeclarationOffice, Procedure};
+    use serde_json::Value;
+
+    /// Checks the keys of a document against the properties of its schema, the crate has no
+    /// validator of its own
+    fn assert_keys(schema: &Value, document: &Value) {
+        let properties = schema["properties"].as_object().unwrap();
+        let document = document.as_object().unwrap();
+        assert!(document.keys().all(|key| properties.contains_key(key)));
+        assert!(schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|key| document.contains_key(key.as_str().unwrap())));
+    }
+
+    #[test]
+    fn schema_test() {
+        for kind in SchemaKind::value_variants() {
+            let schema: Value = serde_json::from_str(schema(*kind)).unwrap();
+            assert_eq!("object", schema["type"]);
+        }
+
+        let record = render::json(&MrnRecord {
+            mrn: "26DK004700CJNMYMA6",
+            procedure: Some(Procedure::ExportOnly),
+            declaration_office: Some(DeclarationOffice::new("004700").unwrap()),
+            issued_at: None,
+            lrn: Some("DECL-0000001"),
+        });
+        let record: Value = serde_json::from_str(&record).unwrap();
+        let schema: Value = serde_json::from_str(schema(SchemaKind::Record)).unwrap();
+        assert_keys(&schema, &record);
+    }
+}
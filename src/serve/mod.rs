@@ -0,0 +1,1360 @@
+//! HTTP server generating and validating MRNs in bulk and handing out leases of MRNs to test
+//! orchestrators
+//!
+//! Requests are JSON over HTTP/1.1, one request per connection. A lease reserves its MRNs until
+//! it expires or they are committed or released. Committed MRNs are never handed out again,
+//! released or expired ones may be. On SIGTERM or SIGINT the server stops accepting connections
+//! and exits once the requests in flight are answered.
+
+use crate::audit::AuditLog;
+use crate::commands::MAX_ATTEMPTS;
+use crate::parser::ServeArgs;
+use crate::pipeline::CHUNK_SIZE;
+use crate::registry::Registry;
+#[cfg(feature = "online")]
+use crate::webhook::{Batch, Webhook};
+use anyhow::{Context, Result};
+use chrono::{SecondsFormat, Utc};
+use mrn_generator::country::resolve_country;
+use mrn_generator::i18n::Localizer;
+use mrn_generator::render;
+use mrn_generator::sink::MrnRecord;
+use mrn_generator::source::RandomSource;
+use mrn_generator::validation::{
+    validate_semantics, Finding, SemanticOptions, Severity, ValidationReport,
+};
+use mrn_generator::{generate_mrn, layout, MrnConfig, MrnGeneratorError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Maximum number of MRNs in one lease
+const MAX_LEASED: usize = 10_000;
+
+/// Maximum number of seconds until a lease expires, 30 days
+const MAX_LEASE_TTL: u64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of MRNs generated by one request
+const MAX_GENERATED: usize = 1_000_000;
+
+/// Number of MRNs above which generated MRNs are streamed in chunks as they are generated,
+/// instead of being sent in one piece
+const STREAMED_ABOVE: usize = 10_000;
+
+/// Maximum size of a request body
+const MAX_BODY: usize = 1 << 20;
+
+/// Paths of the endpoints, all answering POST requests
+const ENDPOINTS: [&str; 5] = ["/generate", "/validate", "/lease", "/commit", "/release"];
+
+/// How often the server checks for a stop signal while waiting for connections
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Set by SIGTERM and SIGINT, to stop accepting connections
+static STOPPING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+fn stop_on_signals() {
+    extern "C" fn stop(_: libc::c_int) {
+        STOPPING.store(true, Ordering::SeqCst);
+    }
+    let stop = stop as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe
+    unsafe {
+        libc::signal(libc::SIGTERM, stop);
+        libc::signal(libc::SIGINT, stop);
+    }
+}
+
+#[cfg(not(unix))]
+fn stop_on_signals() {}
+
+/// Serves requests on the listen address until SIGTERM or SIGINT, then waits for the requests
+/// in flight
+pub fn serve(args: ServeArgs, localizer: &Localizer) -> Result<()> {
+    let listener = TcpListener::bind(&args.listen)
+        .with_context(|| format!("could not listen on {}", args.listen))?;
+    // Accepting without blocking, so that a stop signal is noticed between connections
+    listener.set_nonblocking(true)?;
+    let registry = args
+        .registry
+        .as_deref()
+        .map(|path| {
+            Registry::open(path)
+                .with_context(|| format!("could not open registry {}", path.display()))
+        })
+        .transpose()?;
+    let audit_log = args
+        .audit_log
+        .as_deref()
+        .map(|path| {
+            AuditLog::open(path)
+                .with_context(|| format!("could not open audit log {}", path.display()))
+        })
+        .transpose()?;
+    let server = Server::new(Duration::from_secs(args.lease_ttl), registry, localizer)
+        .with_cors(Cors {
+            origins: args.cors_origins,
+            methods: args.cors_methods,
+        })
+        .with_audit_log(audit_log);
+    #[cfg(feature = "online")]
+    let server = server.with_webhook(args.webhook.as_deref().map(Webhook::new));
+    let slots = Slots::new(args.max_concurrency as usize);
+    let timeout = Duration::from_secs(args.request_timeout);
+    stop_on_signals();
+    eprintln!("listening on {}", listener.local_addr()?);
+
+    thread::scope(|scope| {
+        while !STOPPING.load(Ordering::SeqCst) {
+            if !slots.acquire(POLL_INTERVAL) {
+                continue;
+            }
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    slots.release();
+                    if e.kind() == io::ErrorKind::WouldBlock {
+                        thread::sleep(POLL_INTERVAL);
+                    }
+                    continue;
+                }
+            };
+            let (server, slots) = (&server, &slots);
+            scope.spawn(move || {
+                let result = stream
+                    .set_nonblocking(false)
+                    .and_then(|()| stream.set_read_timeout(Some(timeout)))
+                    .and_then(|()| stream.set_write_timeout(Some(timeout)))
+                    .and_then(|()| server.connection(stream));
+                if let Err(e) = result {
+                    eprintln!("{e:#}");
+                }
+                slots.release();
+            });
+        }
+        // Refuse new connections while the requests in flight are answered
+        drop(listener);
+        eprintln!("stopping, waiting for the requests in flight");
+    });
+
+    if let Some(registry) = server.state.into_inner().unwrap().registry {
+        registry.commit().context("could not write registry")?;
+    }
+    if let Some(log) = server.audit_log {
+        log.into_inner()
+            .unwrap()
+            .commit()
+            .context("could not write audit log")?;
+    }
+    Ok(())
+}
+
+/// Counts the requests being handled, to keep them under the maximum concurrency
+struct Slots {
+    free: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Slots {
+    fn new(count: usize) -> Self {
+        Slots {
+            free: Mutex::new(count),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Takes a slot, waiting at most `timeout` for one to be released. Returns false if none was.
+    fn acquire(&self, timeout: Duration) -> bool {
+        let free = self.free.lock().unwrap();
+        let (mut free, _) = self
+            .released
+            .wait_timeout_while(free, timeout, |free| *free == 0)
+            .unwrap();
+        if *free == 0 {
+            return false;
+        }
+        *free -= 1;
+        true
+    }
+
+    fn release(&self) {
+        *self.free.lock().unwrap() += 1;
+        self.released.notify_one();
+    }
+}
+
+/// An HTTP request, reduced to what the endpoints use
+struct Request {
+    method: String,
+    path: String,
+    /// Origin of the page sending a request from a browser
+    origin: Option<String>,
+    /// Address of the client, recorded in the audit log
+    requester: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Writes the lines of a response body
+type WriteLines<'a> = Box<dyn FnOnce(&mut dyn Write) -> io::Result<()> + 'a>;
+
+/// Body of a response
+enum Body<'a> {
+    Empty,
+    Json(Value),
+    /// Lines written as they are generated, `count` of them
+    Lines {
+        content_type: &'static str,
+        count: usize,
+        write: WriteLines<'a>,
+    },
+}
+
+/// An HTTP response
+struct Response<'a> {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Body<'a>,
+}
+
+impl<'a> Response<'a> {
+    fn new(status: u16, body: Body<'a>) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    fn ok(body: Value) -> Self {
+        Response::new(200, Body::Json(body))
+    }
+}
+
+/// Reads a request, None if the client closed the connection without sending one
+fn read_request(reader: &mut impl BufRead) -> Result<Option<Request>, ApiError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(ApiError::BadRequest(format!(
+            "malformed request line {:?}",
+            line.trim_end()
+        )));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    let mut origin = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let (name, value) = (name.trim(), value.trim());
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .parse()
+                    .map_err(|_| ApiError::BadRequest(format!("invalid content length {value}")))?;
+            } else if name.eq_ignore_ascii_case("origin") {
+                origin = Some(value.to_string());
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(ApiError::TooLarge);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(Request {
+        method,
+        path,
+        origin,
+        requester: None,
+        body,
+    }))
+}
+
+fn write_response(writer: &mut impl Write, response: Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    };
+    let status = response.status;
+    let headers: String = response
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}\r\n"))
+        .collect();
+    let (content_type, body) = match response.body {
+        Body::Empty => {
+            write!(
+                writer,
+                "HTTP/1.1 {status} {reason}\r\n{headers}Connection: close\r\n\r\n"
+            )?;
+            return writer.flush();
+        }
+        Body::Json(body) => ("application/json", body.to_string().into_bytes()),
+        Body::Lines {
+            content_type,
+            count,
+            write,
+        } if count > STREAMED_ABOVE => {
+            write!(
+                writer,
+                "HTTP/1.1 {status} {reason}\r\n{headers}Content-Type: {content_type}\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+            )?;
+            let mut chunked = BufWriter::with_capacity(1 << 16, Chunked(&mut *writer));
+            write(&mut chunked)?;
+            chunked.flush()?;
+            drop(chunked);
+            writer.write_all(b"0\r\n\r\n")?;
+            return writer.flush();
+        }
+        Body::Lines {
+            content_type,
+            write,
+            ..
+        } => {
+            let mut body = Vec::new();
+            write(&mut body)?;
+            (content_type, body)
+        }
+    };
+    write!(
+        writer,
+        "HTTP/1.1 {status} {reason}\r\n{headers}Content-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+/// Writer sending each write as a chunk of a chunked response body
+struct Chunked<W: Write>(W);
+
+impl<W: Write> Write for Chunked<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !buf.is_empty() {
+            write!(self.0, "{:x}\r\n", buf.len())?;
+            self.0.write_all(buf)?;
+            self.0.write_all(b"\r\n")?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Error answered to a request
+#[derive(Debug)]
+enum ApiError {
+    Mrn(Vec<MrnGeneratorError>),
+    BadRequest(String),
+    NotFound(String),
+    MethodNotAllowed,
+    TooLarge,
+    Io(io::Error),
+}
+
+impl From<MrnGeneratorError> for ApiError {
+    fn from(e: MrnGeneratorError) -> Self {
+        ApiError::Mrn(vec![e])
+    }
+}
+
+impl From<io::Error> for ApiError {
+    fn from(e: io::Error) -> Self {
+        ApiError::Io(e)
+    }
+}
+
+impl ApiError {
+    /// Response to the request, with an error object as described by
+    /// `schemas/error.schema.json`
+    fn response(&self, localizer: &Localizer) -> Response<'static> {
+        let (status, message) = match self {
+            ApiError::Mrn(errors) => {
+                let messages: Vec<String> = errors.iter().map(|e| localizer.error(e)).collect();
+                (400, messages.join("; "))
+            }
+            ApiError::BadRequest(message) => (400, message.clone()),
+            ApiError::NotFound(message) => (404, message.clone()),
+            ApiError::MethodNotAllowed => (405, "only POST is supported".to_string()),
+            ApiError::TooLarge => (413, format!("bodies are limited to {MAX_BODY} bytes")),
+            ApiError::Io(e) => (500, e.to_string()),
+        };
+        Response::new(
+            status,
+            Body::Json(json!({"error": {"message": message, "causes": []}})),
+        )
+    }
+}
+
+/// MRNs reserved by a lease
+struct Lease {
+    mrns: HashSet<String>,
+    expires: Instant,
+}
+
+/// Leases and the MRNs that can't be handed out
+struct State {
+    leases: HashMap<String, Lease>,
+    reserved: HashSet<String>,
+    /// MRNs committed while serving, when there is no registry to record them in
+    committed: HashSet<String>,
+    registry: Option<Registry>,
+}
+
+impl State {
+    fn is_taken(&self, mrn: &str) -> bool {
+        self.reserved.contains(mrn)
+            || self.committed.contains(mrn)
+            || self.registry.as_ref().is_some_and(|r| r.contains(mrn))
+    }
+
+    /// Releases the MRNs of the expired leases
+    fn expire(&mut self, now: Instant) {
+        let reserved = &mut self.reserved;
+        self.leases.retain(|_, lease| {
+            if lease.expires <= now {
+                for mrn in &lease.mrns {
+                    reserved.remove(mrn);
+                }
+            }
+            lease.expires > now
+        });
+    }
+
+    /// Takes MRNs out of a lease, all of them if none are given, ending the lease once it is
+    /// empty. Returns the MRNs taken and the number left in the lease.
+    fn take(
+        &mut self,
+        id: &str,
+        mrns: Option<Vec<String>>,
+    ) -> Result<(Vec<String>, usize), ApiError> {
+        let lease = self
+            .leases
+            .get_mut(id)
+            .ok_or_else(|| ApiError::NotFound(format!("no lease {id}, it may have expired")))?;
+        let taken = match mrns {
+            Some(mrns) => {
+                if let Some(mrn) = mrns.iter().find(|mrn| !lease.mrns.contains(*mrn)) {
+                    return Err(ApiError::BadRequest(format!("{mrn} is not in lease {id}")));
+                }
+                mrns
+            }
+            None => lease.mrns.iter().cloned().collect(),
+        };
+        for mrn in &taken {
+            lease.mrns.remove(mrn);
+            self.reserved.remove(mrn);
+        }
+        let remaining = lease.mrns.len();
+        if remaining == 0 {
+            self.leases.remove(id);
+        }
+        Ok((taken, remaining))
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct LeaseRequest {
+    count: usize,
+    #[serde(alias = "country")]
+    country_code: String,
+    #[serde(alias = "procedure")]
+    procedure_category: Option<String>,
+    combined: Option<String>,
+    #[serde(alias = "office")]
+    declaration_office: Option<String>,
+    /// Seconds until the lease expires, the --lease-ttl of the server by default, at most
+    /// [MAX_LEASE_TTL]
+    ttl: Option<u64>,
+}
+
+/// Formats of the generated MRNs, as the --format of the command line
+#[derive(Deserialize, Serialize, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    /// One MRN per line
+    #[default]
+    Plain,
+    /// One JSON record per line, as described by `schemas/record.schema.json`
+    Json,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct GenerateRequest {
+    count: usize,
+    #[serde(alias = "country")]
+    country_code: String,
+    #[serde(alias = "procedure")]
+    procedure_category: Option<String>,
+    combined: Option<String>,
+    #[serde(alias = "office")]
+    declaration_office: Option<String>,
+    #[serde(default)]
+    format: Format,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ValidateRequest {
+    mrns: Vec<String>,
+    /// Number of years after the current one accepted without a future year warning
+    #[serde(default)]
+    future_tolerance: u8,
+    /// Check that the customs office reference following the country code starts with the same
+    /// country code
+    #[serde(default)]
+    check_office_country: bool,
+    /// Suggest the smallest single character correction of invalid MRNs
+    #[serde(default)]
+    suggest_repair: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LeasedRequest {
+    lease: String,
+    /// MRNs of the lease to commit or release, all of them by default
+    mrns: Option<Vec<String>>,
+}
+
+/// State shared by the connections
+struct Server<'a> {
+    ttl: Duration,
+    state: Mutex<State>,
+    localizer: &'a Localizer,
+    cors: Cors,
+    audit_log: Option<Mutex<AuditLog>>,
+    #[cfg(feature = "online")]
+    webhook: Option<Webhook>,
+}
+
+/// Origins allowed to call the server from a browser, and the methods they may use
+#[derive(Default)]
+pub struct Cors {
+    /// Allowed origins, `*` for any
+    pub origins: Vec<String>,
+    pub methods: Vec<String>,
+}
+
+impl Cors {
+    /// Headers letting a browser page of the origin read the response, none if the origin isn't
+    /// allowed
+    fn headers(&self, origin: Option<&str>) -> Vec<(&'static str, String)> {
+        let Some(origin) = origin else {
+            return Vec::new();
+        };
+        let allowed = match self.origins.iter().any(|allowed| allowed == "*") {
+            true => "*",
+            false if self.origins.iter().any(|allowed| allowed == origin) => origin,
+            false => return Vec::new(),
+        };
+        vec![
+            ("Access-Control-Allow-Origin", allowed.to_string()),
+            ("Vary", "Origin".to_string()),
+        ]
+    }
+
+    /// Answer to a preflight request of a browser, asking whether the origin may send a request
+    fn preflight(&self, origin: Option<&str>) -> Response<'static> {
+        let mut response = Response::new(204, Body::Empty);
+        response.headers = self.headers(origin);
+        if !response.headers.is_empty() {
+            response.headers.extend([
+                ("Access-Control-Allow-Methods", self.methods.join(", ")),
+                ("Access-Control-Allow-Headers", "Content-Type".to_string()),
+                ("Access-Control-Max-Age", "86400".to_string()),
+            ]);
+        }
+        response
+    }
+}
+
+impl<'a> Server<'a> {
+    fn new(ttl: Duration, registry: Option<Registry>, localizer: &'a Localizer) -> Self {
+        Server {
+            ttl,
+            state: Mutex::new(State {
+                leases: HashMap::new(),
+                reserved: HashSet::new(),
+                committed: HashSet::new(),
+                registry,
+            }),
+            localizer,
+            cors: Cors::default(),
+            audit_log: None,
+            #[cfg(feature = "online")]
+            webhook: None,
+        }
+    }
+
+    /// Answers browser requests from the origins of `cors`
+    fn with_cors(mut self, cors: Cors) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    /// Records the generated and leased MRNs in the audit log
+    fn with_audit_log(mut self, audit_log: Option<AuditLog>) -> Self {
+        self.audit_log = audit_log.map(Mutex::new);
+        self
+    }
+
+    /// Appends the MRNs generated for a request to the audit log, if there is one, with the
+    /// request as their parameters
+    fn audit<'m>(
+        &self,
+        mrns: impl IntoIterator<Item = &'m String>,
+        parameters: &Value,
+        seed: u64,
+        requester: Option<&str>,
+    ) -> io::Result<()> {
+        let Some(log) = self.audit_log.as_ref() else {
+            return Ok(());
+        };
+        let mut log = log.lock().unwrap();
+        for mrn in mrns {
+            log.record(mrn, parameters, Some(seed), requester)?;
+        }
+        log.sync()
+    }
+
+    /// Notifies the webhook of each batch of generated or leased MRNs
+    #[cfg(feature = "online")]
+    fn with_webhook(mut self, webhook: Option<Webhook>) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Summary of a batch for the webhook, None without a webhook
+    #[cfg(feature = "online")]
+    fn batch(&self) -> Option<Batch> {
+        self.webhook.as_ref().map(|_| Batch::default())
+    }
+
+    /// POSTs the summary of a batch with the fields describing its request to the webhook
+    #[cfg(feature = "online")]
+    fn notify(&self, batch: Option<Batch>, fields: Value) {
+        let (Some(webhook), Some(batch)) = (self.webhook.as_ref(), batch) else {
+            return;
+        };
+        let mut summary = batch.summary();
+        if let (Value::Object(summary), Value::Object(fields)) = (&mut summary, fields) {
+            summary.extend(fields);
+        }
+        // The MRNs are handed out already, a failed notification can only be reported
+        if let Err(e) = webhook.notify(&summary) {
+            eprintln!("{e:#}");
+        }
+    }
+
+    /// Answers the request of a connection
+    fn connection(&self, stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(&stream);
+        let response = match read_request(&mut reader) {
+            Ok(Some(mut request)) => {
+                request.requester = stream.peer_addr().ok().map(|addr| addr.ip().to_string());
+                self.handle(&request)
+            }
+            Ok(None) => return Ok(()),
+            Err(e) => e.response(self.localizer),
+        };
+        write_response(&mut &stream, response)
+    }
+
+    fn handle(&self, request: &Request) -> Response<'_> {
+        let (origin, requester) = (request.origin.as_deref(), request.requester.as_deref());
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/generate") => {
+                parse(&request.body).and_then(|body| self.generate(body, requester))
+            }
+            ("POST", "/validate") => parse(&request.body).map(|body| self.validate(body)),
+            ("POST", "/lease") => parse(&request.body).and_then(|body| self.lease(body, requester)),
+            ("POST", "/commit") => parse(&request.body).and_then(|body| self.commit(body)),
+            ("POST", "/release") => parse(&request.body).and_then(|body| self.release(body)),
+            ("OPTIONS", path) if ENDPOINTS.contains(&path) => return self.cors.preflight(origin),
+            (_, path) if ENDPOINTS.contains(&path) => Err(ApiError::MethodNotAllowed),
+            (_, path) => Err(ApiError::NotFound(format!("no endpoint {path}"))),
+        };
+        let mut response = result.unwrap_or_else(|e| e.response(self.localizer));
+        response.headers.extend(self.cors.headers(origin));
+        response
+    }
+
+    /// Validates the MRNs, with the errors and findings of each and the characters they are about
+    fn validate(&self, request: ValidateRequest) -> Response<'static> {
+        let options = SemanticOptions {
+            future_year_tolerance: request.future_tolerance,
+            check_office_country: request.check_office_country,
+            suggest_repair: request.suggest_repair,
+            ..SemanticOptions::now()
+        };
+        let reports: Vec<ValidationReport> = request
+            .mrns
+            .iter()
+            .map(|mrn| validate_semantics(mrn.trim(), &options))
+            .collect();
+        let invalid = reports.iter().filter(|report| !report.is_valid()).count();
+        let results: Vec<Value> = reports
+            .iter()
+            .map(|report| {
+                let errors: Vec<Value> = report
+                    .errors
+                    .iter()
+                    .map(|e| {
+                        let (code, span) = error_code(e);
+                        finding(code, self.localizer.error(e), span)
+                    })
+                    .collect();
+                let findings: Vec<Value> = report
+                    .findings
+                    .iter()
+                    .map(|semantic| {
+                        let (code, span) = finding_code(semantic);
+                        let mut finding =
+                            finding(code, self.localizer.finding(semantic), Some(span));
+                        finding["severity"] = json!(severity_name(semantic.severity()));
+                        finding
+                    })
+                    .collect();
+                let mut result = json!({
+                    "mrn": report.mrn,
+                    "valid": report.is_valid(),
+                    "errors": errors,
+                    "findings": findings,
+                });
+                if let Some(repair) = &report.repair {
+                    result["repair"] = json!({
+                        "mrn": repair.mrn,
+                        "span": [repair.position, repair.position + 1],
+                        "found": repair.found,
+                        "replacement": repair.replacement,
+                    });
+                }
+                result
+            })
+            .collect();
+        Response::ok(json!({
+            "valid": reports.len() - invalid,
+            "invalid": invalid,
+            "results": results,
+        }))
+    }
+
+    fn lease(
+        &self,
+        request: LeaseRequest,
+        requester: Option<&str>,
+    ) -> Result<Response<'static>, ApiError> {
+        if !(1..=MAX_LEASED).contains(&request.count) {
+            return Err(ApiError::BadRequest(format!(
+                "count must be between 1 and {MAX_LEASED}"
+            )));
+        }
+        if request.ttl.is_some_and(|ttl| ttl > MAX_LEASE_TTL) {
+            return Err(ApiError::BadRequest(format!(
+                "ttl must be at most {MAX_LEASE_TTL} seconds"
+            )));
+        }
+        let country_code = resolve_country(&request.country_code)?;
+        let config = MrnConfig::from_input(
+            &country_code,
+            request.procedure_category.as_deref(),
+            request.combined.as_deref(),
+            request.declaration_office.as_deref(),
+        )
+        .map_err(ApiError::Mrn)?;
+        let ttl = request.ttl.map_or(self.ttl, Duration::from_secs);
+        let expires_at = chrono::Duration::from_std(ttl)
+            .ok()
+            .and_then(|ttl| Utc::now().checked_add_signed(ttl))
+            .ok_or_else(|| ApiError::BadRequest(format!("ttl of {ttl:?} is too long")))?;
+
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        state.expire(now);
+        let expires = now
+            .checked_add(ttl)
+            .ok_or_else(|| ApiError::BadRequest(format!("ttl of {ttl:?} is too long")))?;
+        let seed = rand::random();
+        let mut source = RandomSource::seeded(seed);
+        let mut mrns = HashSet::with_capacity(request.count);
+        while mrns.len() < request.count {
+            let mut attempts = 0;
+            let mrn = loop {
+                let mrn = generate_mrn(&config, &mut source)?;
+                if !state.is_taken(&mrn) && !mrns.contains(&mrn) {
+                    break mrn;
+                }
+                attempts += 1;
+                if attempts == MAX_ATTEMPTS {
+                    return Err(ApiError::Mrn(vec![MrnGeneratorError::SourceExhausted]));
+                }
+            };
+            mrns.insert(mrn);
+        }
+        self.audit(&mrns, &json!(request), seed, requester)?;
+        state.reserved.extend(mrns.iter().cloned());
+
+        let id = format!("{:016x}", rand::random::<u64>());
+        let body = json!({
+            "lease": id,
+            "mrns": mrns,
+            "expires_at": expires_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        });
+        #[cfg(feature = "online")]
+        let batch = self.batch().map(|mut batch| {
+            batch.extend(&mrns);
+            batch
+        });
+        state.leases.insert(id, Lease { mrns, expires });
+        drop(state);
+        #[cfg(feature = "online")]
+        self.notify(
+            batch,
+            json!({"endpoint": "/lease", "lease": body["lease"], "requester": requester}),
+        );
+        Ok(Response::ok(body))
+    }
+
+    /// Generates MRNs as they are written, without reserving them
+    fn generate(
+        &self,
+        request: GenerateRequest,
+        requester: Option<&str>,
+    ) -> Result<Response<'_>, ApiError> {
+        if !(1..=MAX_GENERATED).contains(&request.count) {
+            return Err(ApiError::BadRequest(format!(
+                "count must be between 1 and {MAX_GENERATED}"
+            )));
+        }
+        let country_code = resolve_country(&request.country_code)?;
+        // Checked before answering, the status can't change once the MRNs are being written
+        MrnConfig::from_input(
+            &country_code,
+            request.procedure_category.as_deref(),
+            request.combined.as_deref(),
+            request.declaration_office.as_deref(),
+        )
+        .map_err(ApiError::Mrn)?;
+
+        let content_type = match request.format {
+            Format::Plain => "text/plain",
+            Format::Json => "application/x-ndjson",
+        };
+        let (count, parameters) = (request.count, json!(request));
+        let requester = requester.map(str::to_string);
+        let write: WriteLines = Box::new(move |out| {
+            let config = MrnConfig::from_input(
+                &country_code,
+                request.procedure_category.as_deref(),
+                request.combined.as_deref(),
+                request.declaration_office.as_deref(),
+            )
+            .expect("the config was checked before answering");
+            let seed = rand::random();
+            let mut source = RandomSource::seeded(seed);
+            #[cfg(feature = "online")]
+            let mut batch = self.batch();
+            let mut mrns = Vec::with_capacity(CHUNK_SIZE.min(count));
+            for start in (0..count).step_by(CHUNK_SIZE) {
+                mrns.clear();
+                for _ in start..count.min(start + CHUNK_SIZE) {
+                    mrns.push(generate_mrn(&config, &mut source).map_err(io::Error::other)?);
+                }
+                // Recorded before they are sent, so an MRN is never handed out unrecorded
+                self.audit(&mrns, &parameters, seed, requester.as_deref())?;
+                #[cfg(feature = "online")]
+                if let Some(batch) = batch.as_mut() {
+                    batch.extend(&mrns);
+                }
+                for mrn in &mrns {
+                    let record = MrnRecord {
+                        mrn,
+                        procedure: config.procedure,
+                        declaration_office: config.declaration_office,
+                        issued_at: None,
+                        lrn: None,
+                    };
+                    let line = match request.format {
+                        Format::Plain => render::plain(&record),
+                        Format::Json => render::json(&record),
+                    };
+                    writeln!(out, "{line}")?;
+                }
+            }
+            #[cfg(feature = "online")]
+            self.notify(
+                batch,
+                json!({"endpoint": "/generate", "requester": requester}),
+            );
+            Ok(())
+        });
+        Ok(Response::new(
+            200,
+            Body::Lines {
+                content_type,
+                count,
+                write,
+            },
+        ))
+    }
+
+    fn commit(&self, request: LeasedRequest) -> Result<Response<'static>, ApiError> {
+        let mut state = self.state.lock().unwrap();
+        state.expire(Instant::now());
+        let (committed, remaining) = state.take(&request.lease, request.mrns)?;
+        match state.registry.as_mut() {
+            Some(registry) => {
+                for mrn in &committed {
+                    registry.record(mrn)?;
+                }
+                registry.sync()?;
+            }
+            None => state.committed.extend(committed.iter().cloned()),
+        }
+        Ok(Response::ok(json!({
+            "lease": request.lease,
+            "committed": committed.len(),
+            "remaining": remaining,
+        })))
+    }
+
+    fn release(&self, request: LeasedRequest) -> Result<Response<'static>, ApiError> {
+        let mut state = self.state.lock().unwrap();
+        state.expire(Instant::now());
+        let (released, remaining) = state.take(&request.lease, request.mrns)?;
+        Ok(Response::ok(json!({
+            "lease": request.lease,
+            "released": released.len(),
+            "remaining": remaining,
+        })))
+    }
+}
+
+/// An error or semantic finding of a validation, with the byte range of the MRN it is about if it is
+/// about some of its characters
+fn finding(code: &str, message: String, span: Option<Range<usize>>) -> Value {
+    let mut finding = json!({"code": code, "message": message});
+    if let Some(span) = span {
+        finding["span"] = json!([span.start, span.end]);
+    }
+    finding
+}
+
+/// Code of a validation error and the characters it is about
+fn error_code(error: &MrnGeneratorError) -> (&'static str, Option<Range<usize>>) {
+    match error {
+        MrnGeneratorError::InvalidCharacter { position, found } => (
+            "invalid-character",
+            Some(*position..position + found.len_utf8()),
+        ),
+        MrnGeneratorError::MrnLength(_) => ("length", None),
+        MrnGeneratorError::InvalidYear(_) => ("invalid-year", Some(layout::YEAR)),
+        MrnGeneratorError::InvalidCountryCode(_) => {
+            ("invalid-country-code", Some(layout::COUNTRY_CODE))
+        }
+        MrnGeneratorError::InvalidCheckDigit { .. } => {
+            ("invalid-check-digit", Some(layout::CHECK_DIGIT))
+        }
+        _ => ("invalid", None),
+    }
+}
+
+/// Name of a severity in the JSON of the validation results
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+/// Code of a semantic finding of a validation and the characters it is about
+fn finding_code(finding: &Finding) -> (&'static str, Range<usize>) {
+    match finding {
+        Finding::FutureYear { .. } => ("future-year", layout::YEAR),
+        Finding::OfficeCountryMismatch { .. } => (
+            "office-country-mismatch",
+            layout::OFFICE_REFERENCE.start..layout::OFFICE_REFERENCE.start + 2,
+        ),
+        Finding::UnknownCountry { .. } => ("unknown-country", layout::COUNTRY_CODE),
+        Finding::OfficeNotNumeric { .. } => (
+            "office-not-numeric",
+            layout::OFFICE_REFERENCE.start + 2..layout::OFFICE_REFERENCE.end,
+        ),
+        Finding::DeclarationYearMismatch { .. } => ("declaration-year-mismatch", layout::YEAR),
+    }
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body)
+        .map_err(|e| ApiError::BadRequest(format!("invalid request body: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use mrn_generator::i18n::Lang;
+    use mrn_generator::validate_mrn;
+
+    struct JsonResponse {
+        status: u16,
+        body: Value,
+    }
+
+    fn post(server: &Server, path: &str, body: Value) -> JsonResponse {
+        let response = server.handle(&Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            origin: None,
+            requester: None,
+            body: body.to_string().into_bytes(),
+        });
+        match response.body {
+            Body::Json(body) => JsonResponse {
+                status: response.status,
+                body,
+            },
+            Body::Empty | Body::Lines { .. } => panic!("expected a JSON response"),
+        }
+    }
+
+    /// Response to a request as sent over the connection
+    fn post_raw(server: &Server, path: &str, body: Value) -> String {
+        let response = server.handle(&Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            origin: None,
+            requester: None,
+            body: body.to_string().into_bytes(),
+        });
+        let mut raw = Vec::new();
+        write_response(&mut raw, response).unwrap();
+        String::from_utf8(raw).unwrap()
+    }
+
+    fn mrns(response: &JsonResponse) -> Vec<String> {
+        serde_json::from_value(response.body["mrns"].clone()).unwrap()
+    }
+
+    #[test]
+    fn lease_test() {
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer);
+
+        let leased = post(
+            &server,
+            "/lease",
+            json!({"count": 3, "country_code": "DK", "procedure_category": "B1"}),
+        );
+        assert_eq!(200, leased.status);
+        let lease = leased.body["lease"].as_str().unwrap();
+        let leased = mrns(&leased);
+        assert_eq!(3, leased.len());
+        assert!(leased
+            .iter()
+            .all(|mrn| mrn.starts_with(&Utc::now().format("%y").to_string())));
+
+        let committed = post(
+            &server,
+            "/commit",
+            json!({"lease": lease, "mrns": [leased[0]]}),
+        );
+        assert_eq!(json!(1), committed.body["committed"]);
+        assert_eq!(json!(2), committed.body["remaining"]);
+        assert!(server.state.lock().unwrap().committed.contains(&leased[0]));
+
+        let released = post(&server, "/release", json!({"lease": lease}));
+        assert_eq!(json!(2), released.body["released"]);
+        assert_eq!(json!(0), released.body["remaining"]);
+        let state = server.state.lock().unwrap();
+        assert!(state.leases.is_empty() && state.reserved.is_empty());
+        drop(state);
+
+        // The lease ended with its last MRN
+        assert_eq!(
+            404,
+            post(&server, "/release", json!({"lease": lease})).status
+        );
+    }
+
+    #[test]
+    fn lease_expiry_test() {
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer);
+
+        let leased = post(
+            &server,
+            "/lease",
+            json!({"count": 2, "country_code": "DK", "ttl": 0}),
+        );
+        let lease = leased.body["lease"].as_str().unwrap();
+        assert_eq!(
+            404,
+            post(&server, "/commit", json!({"lease": lease})).status
+        );
+        assert!(server.state.lock().unwrap().reserved.is_empty());
+    }
+
+    #[test]
+    fn lease_errors_test() {
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer);
+
+        let response = post(
+            &server,
+            "/lease",
+            json!({"count": 1, "country_code": "DK", "procedure_category": "X1"}),
+        );
+        assert_eq!(400, response.status);
+        assert!(response.body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("X1"));
+        assert_eq!(
+            400,
+            post(&server, "/lease", json!({"count": 0, "country_code": "DK"})).status
+        );
+        let response = post(
+            &server,
+            "/lease",
+            json!({"count": 1, "country_code": "DK", "ttl": u64::MAX}),
+        );
+        assert_eq!(400, response.status);
+        assert!(response.body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("ttl"));
+        assert_eq!(404, post(&server, "/leases", json!({})).status);
+
+        let leased = post(&server, "/lease", json!({"count": 1, "country_code": "DK"}));
+        let lease = leased.body["lease"].as_str().unwrap();
+        let response = post(
+            &server,
+            "/commit",
+            json!({"lease": lease, "mrns": ["22ITZXBZYUTJFLJXK6"]}),
+        );
+        assert_eq!(400, response.status);
+    }
+
+    #[test]
+    fn generate_test() {
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer);
+
+        let raw = post_raw(
+            &server,
+            "/generate",
+            json!({"count": 3, "country": "DK", "procedure": "B1", "office": "004700"}),
+        );
+        let (head, body) = raw.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains("Content-Type: text/plain"));
+        assert!(head.contains(&format!("Content-Length: {}", 3 * 19)));
+        assert_eq!(3, body.lines().count());
+        assert!(body.lines().all(|mrn| validate_mrn(mrn).is_ok()));
+
+        let raw = post_raw(
+            &server,
+            "/generate",
+            json!({"count": 2, "country_code": "DK", "format": "json"}),
+        );
+        let (_, body) = raw.split_once("\r\n\r\n").unwrap();
+        let record: Value = serde_json::from_str(body.lines().next().unwrap()).unwrap();
+        assert_eq!(json!("DK"), record["country_code"]);
+
+        // Large counts are streamed in chunks
+        let count = STREAMED_ABOVE + 1;
+        let raw = post_raw(
+            &server,
+            "/generate",
+            json!({"count": count, "country": "DK"}),
+        );
+        let (head, mut chunks) = raw.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains("Transfer-Encoding: chunked"));
+        let mut body = String::new();
+        loop {
+            let (size, rest) = chunks.split_once("\r\n").unwrap();
+            let size = usize::from_str_radix(size, 16).unwrap();
+            if size == 0 {
+                break;
+            }
+            body.push_str(&rest[..size]);
+            chunks = &rest[size + 2..];
+        }
+        assert_eq!(count, body.lines().count());
+
+        assert_eq!(
+            400,
+            post(
+                &server,
+                "/generate",
+                json!({"count": 1, "country": "DK", "procedure": "X1"})
+            )
+            .status
+        );
+    }
+
+    #[test]
+    fn validate_test() {
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer);
+
+        let response = post(
+            &server,
+            "/validate",
+            json!({"mrns": ["22ITZXBZYUTJFLJXK6", "22ITZXBZYUTJFLJXK7", "22IT-XBZYUTJFLJXK6", "99ITZXBZYUTJFLJXK5"]}),
+        );
+        assert_eq!(json!(2), response.body["valid"]);
+        assert_eq!(json!(2), response.body["invalid"]);
+        let results = &response.body["results"];
+        assert_eq!(json!([]), results[0]["errors"]);
+        let error = &results[1]["errors"][0];
+        assert_eq!(json!("invalid-check-digit"), error["code"]);
+        assert_eq!(json!([17, 18]), error["span"]);
+        assert!(error["message"].as_str().unwrap().contains("should be 6"));
+        assert_eq!(json!([4, 5]), results[2]["errors"][0]["span"]);
+        assert_eq!(json!(true), results[3]["valid"]);
+        assert_eq!(json!("future-year"), results[3]["findings"][0]["code"]);
+        assert_eq!(json!([0, 2]), results[3]["findings"][0]["span"]);
+        assert_eq!(json!("warning"), results[3]["findings"][0]["severity"]);
+    }
+
+    #[test]
+    fn slots_test() {
+        let slots = Slots::new(1);
+        assert!(slots.acquire(Duration::ZERO));
+        assert!(!slots.acquire(Duration::from_millis(10)));
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                slots.release();
+            });
+            assert!(slots.acquire(Duration::from_secs(10)));
+        });
+    }
+
+    #[test]
+    fn audit_log_test() {
+        let path =
+            std::env::temp_dir().join(format!("mrn-serve-audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer)
+            .with_audit_log(Some(AuditLog::open(&path).unwrap()));
+
+        let leased = post(&server, "/lease", json!({"count": 3, "country": "DK"}));
+        assert_eq!(200, leased.status);
+        post_raw(&server, "/generate", json!({"count": 2, "country": "IT"}));
+        server
+            .audit_log
+            .unwrap()
+            .into_inner()
+            .unwrap()
+            .commit()
+            .unwrap();
+
+        let entries: Vec<Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(5, entries.len());
+        let mut logged: Vec<String> = entries[..3]
+            .iter()
+            .map(|entry| entry["mrn"].as_str().unwrap().to_string())
+            .collect();
+        let mut leased = mrns(&leased);
+        logged.sort();
+        leased.sort();
+        assert_eq!(leased, logged);
+        assert_eq!("DK", entries[0]["parameters"]["country_code"]);
+        assert_eq!(2, entries[4]["parameters"]["count"]);
+        assert_eq!("IT", &entries[4]["mrn"].as_str().unwrap()[2..4]);
+        assert!(entries.iter().all(|entry| entry["seed"].is_u64()));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn cors_test() {
+        let localizer = Localizer::new(Lang::En);
+        let server = Server::new(Duration::from_secs(60), None, &localizer).with_cors(Cors {
+            origins: vec!["https://ui.example.com".to_string()],
+            methods: vec!["POST".to_string()],
+        });
+        let request = |method: &str, origin: &str| {
+            server.handle(&Request {
+                method: method.to_string(),
+                path: "/validate".to_string(),
+                origin: Some(origin.to_string()),
+                requester: None,
+                body: json!({"mrns": []}).to_string().into_bytes(),
+            })
+        };
+        let header = |response: &Response, name: &str| {
+            response
+                .headers
+                .iter()
+                .find(|(header, _)| *header == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let preflight = request("OPTIONS", "https://ui.example.com");
+        assert_eq!(204, preflight.status);
+        assert_eq!(
+            Some("https://ui.example.com".to_string()),
+            header(&preflight, "Access-Control-Allow-Origin")
+        );
+        assert_eq!(
+            Some("POST".to_string()),
+            header(&preflight, "Access-Control-Allow-Methods")
+        );
+        let response = request("POST", "https://ui.example.com");
+        assert_eq!(200, response.status);
+        assert!(header(&response, "Access-Control-Allow-Origin").is_some());
+
+        let response = request("POST", "https://other.example.com");
+        assert_eq!(None, header(&response, "Access-Control-Allow-Origin"));
+        assert!(request("OPTIONS", "https://other.example.com")
+            .headers
+            .is_empty());
+    }
+
+    #[test]
+    fn read_request_test() {
+        let mut raw =
+            "POST /lease HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\n\r\n{}".as_bytes();
+        let request = read_request(&mut raw).unwrap().unwrap();
+        assert_eq!(("POST", "/lease"), (&*request.method, &*request.path));
+        assert_eq!(b"{}", &request.body[..]);
+
+        assert!(read_request(&mut "".as_bytes()).unwrap().is_none());
+        let mut raw = "POST /lease HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n".as_bytes();
+        assert!(matches!(read_request(&mut raw), Err(ApiError::TooLarge)));
+    }
+}
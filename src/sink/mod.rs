@@ -0,0 +1,217 @@
+//! Destinations of generated MRNs
+//!
+//! The command line utility writes all its generated MRNs through [OutputSink], implement it to
+//! send MRNs somewhere else, e.g. an HTTP endpoint or a message queue.
+
+use crate::{layout, procedure_description, DeclarationOffice, Procedure};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// A generated MRN with the parameters it was generated from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MrnRecord<'a> {
+    pub mrn: &'a str,
+    pub procedure: Option<Procedure>,
+    pub declaration_office: Option<DeclarationOffice<'a>>,
+    /// Issue date of the MRN as `YYYY-MM-DD`, if the generator picked one
+    pub issued_at: Option<&'a str>,
+    /// Local reference number paired with the MRN, if the generator made one
+    pub lrn: Option<&'a str>,
+}
+
+/// Column name of [MrnRecord::issued_at], written after [COLUMNS] by the sinks that support it
+pub const ISSUED_AT_COLUMN: &str = "Issued at";
+
+/// Column name of [MrnRecord::lrn], written after [ISSUED_AT_COLUMN] by the sinks that support it
+pub const LRN_COLUMN: &str = "LRN";
+
+/// Column names of [MrnRecord::fields]
+pub const COLUMNS: [&str; 8] = [
+    "MRN",
+    "Year",
+    "Country code",
+    "Declaration office",
+    "Serial number",
+    "Procedure character",
+    "Procedure",
+    "Check digit",
+];
+
+impl MrnRecord<'_> {
+    /// The MRN split into its components, in the order of [COLUMNS]
+    pub fn fields(&self) -> [String; 8] {
+        let mrn = self.mrn;
+        let office_end = layout::SERIAL.start
+            + self
+                .declaration_office
+                .map_or(0, |office| office.as_str().len());
+        let serial_end = match self.procedure {
+            Some(_) => layout::PROCEDURE.start,
+            None => layout::CHECK_DIGIT.start,
+        };
+        [
+            mrn.to_string(),
+            format!("20{}", &mrn[layout::YEAR]),
+            mrn[layout::COUNTRY_CODE].to_string(),
+            mrn[layout::SERIAL.start..office_end].to_string(),
+            mrn[office_end..serial_end].to_string(),
+            mrn[serial_end..layout::CHECK_DIGIT.start].to_string(),
+            self.procedure
+                .map(procedure_description)
+                .unwrap_or_default()
+                .to_string(),
+            mrn[layout::CHECK_DIGIT].to_string(),
+        ]
+    }
+}
+
+/// A file written by a sink
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrittenFile {
+    pub path: PathBuf,
+    /// Number of MRNs written to the file
+    pub rows: u64,
+}
+
+/// Destination of generated MRNs
+///
+/// ## Example
+/// ```
+/// use mrn_generator::sink::{MrnRecord, OutputSink, WrittenFile};
+///
+/// /// Collects the MRNs, e.g. to post them in one request
+/// struct Batch(Vec<String>);
+///
+/// impl OutputSink for Batch {
+///     type Error = std::convert::Infallible;
+///
+///     fn write(&mut self, record: &MrnRecord) -> Result<(), Self::Error> {
+///         self.0.push(record.mrn.to_string());
+///         Ok(())
+///     }
+///
+///     fn finish(self: Box<Self>) -> Result<Vec<WrittenFile>, Self::Error> {
+///         Ok(vec![])
+///     }
+/// }
+/// ```
+pub trait OutputSink {
+    type Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<(), Self::Error>;
+
+    /// Flushes or saves the output once all MRNs are written, returning the files written
+    fn finish(self: Box<Self>) -> Result<Vec<WrittenFile>, Self::Error>;
+}
+
+/// One MRN per line to a writer, e.g. standard output or a file
+pub struct WriterSink<W: Write> {
+    writer: W,
+    path: Option<PathBuf>,
+    rows: u64,
+}
+
+impl<W: Write> WriterSink<W> {
+    pub fn new(writer: W) -> Self {
+        WriterSink {
+            writer,
+            path: None,
+            rows: 0,
+        }
+    }
+
+    /// Reports the writer as the file at the path once finished
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Unwraps the writer, without flushing it
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> OutputSink for WriterSink<W> {
+    type Error = io::Error;
+
+    fn write(&mut self, record: &MrnRecord) -> Result<(), Self::Error> {
+        writeln!(self.writer, "{}", record.mrn)?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> Result<Vec<WrittenFile>, Self::Error> {
+        self.writer.flush()?;
+        Ok(match self.path {
+            Some(path) => vec![WrittenFile {
+                path,
+                rows: self.rows,
+            }],
+            None => vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn record_fields_test() {
+        let record = MrnRecord {
+            mrn: "26DK004700CJNMYMA6",
+            procedure: Some(Procedure::ExportOnly),
+            declaration_office: Some(DeclarationOffice::new("004700").unwrap()),
+            issued_at: None,
+            lrn: None,
+        };
+        assert_eq!(
+            [
+                "26DK004700CJNMYMA6",
+                "2026",
+                "DK",
+                "004700",
+                "CJNMYM",
+                "A",
+                "Export only",
+                "6"
+            ],
+            record.fields()
+        );
+
+        let record = MrnRecord {
+            mrn: "22ITZXBZYUTJFLJXK6",
+            procedure: None,
+            declaration_office: None,
+            issued_at: None,
+            lrn: None,
+        };
+        let fields = record.fields();
+        assert_eq!(("ZXBZYUTJFLJXK", ""), (&*fields[4], &*fields[5]));
+    }
+
+    #[test]
+    fn writer_sink_test() {
+        let mut sink = WriterSink::new(Vec::new());
+        sink.write(&MrnRecord {
+            mrn: "22ITZXBZYUTJFLJXK6",
+            procedure: None,
+            declaration_office: None,
+            issued_at: None,
+            lrn: None,
+        })
+        .unwrap();
+        assert_eq!(b"22ITZXBZYUTJFLJXK6\n", &*sink.into_inner());
+
+        let sink = Box::new(WriterSink::new(io::sink()).with_path("mrns.txt"));
+        assert_eq!(
+            vec![WrittenFile {
+                path: "mrns.txt".into(),
+                rows: 0
+            }],
+            sink.finish().unwrap()
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Sources for the free segment of an MRN, the part that isn't fixed by the year, country code,
+//! declaration office or procedure
+
+use crate::MrnGeneratorError;
+use alloc::boxed::Box;
+use alloc::string::String;
+#[cfg(feature = "generate")]
+use chrono::Utc;
+#[cfg(feature = "generate")]
+use rand::{rngs::StdRng, rngs::ThreadRng, Rng, SeedableRng};
+
+/// Characters that may appear in the free segment of an MRN
+pub const ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Allocation strategy for the free segment of generated MRNs
+///
+/// Implement this to plug in a custom strategy, e.g. drawing from a range issued by a national
+/// administration.
+pub trait IdSource {
+    /// Returns the next segment, exactly `len` uppercase ASCII alphanumeric characters long
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError>;
+}
+
+impl<S: IdSource + ?Sized> IdSource for &mut S {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        (**self).next_segment(len)
+    }
+}
+
+impl<S: IdSource + ?Sized> IdSource for Box<S> {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        (**self).next_segment(len)
+    }
+}
+
+/// Uniformly random segments
+#[cfg(feature = "generate")]
+#[derive(Debug, Clone)]
+pub struct RandomSource<R = ThreadRng> {
+    rng: R,
+}
+
+#[cfg(feature = "generate")]
+impl Default for RandomSource<ThreadRng> {
+    fn default() -> Self {
+        RandomSource {
+            rng: rand::thread_rng(),
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+impl RandomSource<StdRng> {
+    /// Reproducible random segments, the same seed always yields the same sequence
+    pub fn seeded(seed: u64) -> Self {
+        RandomSource {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+impl<R: Rng> RandomSource<R> {
+    /// Random segments drawn from the given generator
+    pub fn from_rng(rng: R) -> Self {
+        RandomSource { rng }
+    }
+}
+
+#[cfg(feature = "generate")]
+impl<R: Rng> IdSource for RandomSource<R> {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        Ok((0..len)
+            .map(|_| ALPHABET[self.rng.gen_range(0..ALPHABET.len())] as char)
+            .collect())
+    }
+}
+
+/// Zero-padded decimal counter, e.g. `000041`, `000042`, ...
+#[derive(Debug, Clone, Default)]
+pub struct SequentialSource {
+    next: u64,
+}
+
+impl SequentialSource {
+    /// Counter starting at the given value
+    pub fn starting_at(start: u64) -> Self {
+        SequentialSource { next: start }
+    }
+}
+
+impl IdSource for SequentialSource {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        let segment = encode(self.next, len, 10).ok_or(MrnGeneratorError::SourceExhausted)?;
+        self.next += 1;
+        Ok(segment)
+    }
+}
+
+/// Base-36 encoded milliseconds since the Unix epoch
+///
+/// Calls within the same millisecond advance the timestamp by one, so segments are unique
+/// within a source as long as it's called less than once per millisecond on average.
+#[cfg(feature = "generate")]
+#[derive(Debug, Clone, Default)]
+pub struct TimestampSource {
+    last: u64,
+}
+
+#[cfg(feature = "generate")]
+impl IdSource for TimestampSource {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        let now = Utc::now().timestamp_millis().max(0) as u64;
+        self.last = now.max(self.last + 1);
+
+        // Keep the fastest changing digits when the segment can't hold the whole timestamp
+        let modulus = 36u64.checked_pow(len as u32).unwrap_or(u64::MAX);
+        Ok(encode(self.last % modulus, len, 36).unwrap())
+    }
+}
+
+/// Random-looking segments that never repeat, a keyed permutation of a counter
+///
+/// The n-th segment is the n-th counter value shuffled by a small Feistel network over the
+/// `36^len` possible segments, so uniqueness needs no memory of the issued segments however many
+/// are drawn. Segments of the same length are unique for a given key until they are exhausted.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::source::{IdSource, PermutedSource};
+///
+/// let mut source = PermutedSource::new(7);
+/// let first = source.next_segment(8).unwrap();
+/// assert_ne!(first, source.next_segment(8).unwrap());
+/// assert_eq!(first, PermutedSource::new(7).next_segment(8).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermutedSource {
+    key: u64,
+    next: u64,
+}
+
+impl PermutedSource {
+    /// Permutation selected by the key, starting with the first counter value
+    pub fn new(key: u64) -> Self {
+        Self::starting_at(key, 0)
+    }
+
+    /// Permutation selected by the key, starting at the given counter value, so that sources
+    /// starting at different values draw disjoint segments
+    pub fn starting_at(key: u64, start: u64) -> Self {
+        PermutedSource { key, next: start }
+    }
+}
+
+impl IdSource for PermutedSource {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        let size = 36u64.checked_pow(len as u32).unwrap_or(u64::MAX);
+        if self.next >= size {
+            return Err(MrnGeneratorError::SourceExhausted);
+        }
+        let value = permute(self.next, size, self.key);
+        self.next += 1;
+        Ok(encode(value, len, 36).unwrap())
+    }
+}
+
+/// Segments of another source behind a namespace from 0 to 15, written as the hexadecimal digit
+/// of their first character, so that sources in different namespaces never draw the same segment
+///
+/// ## Example
+/// ```
+/// use mrn_generator::source::{IdSource, NamespacedSource, SequentialSource};
+///
+/// let mut source = NamespacedSource::new(12, SequentialSource::starting_at(42)).unwrap();
+/// assert_eq!("C00042", source.next_segment(6).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct NamespacedSource<S> {
+    namespace: u8,
+    source: S,
+}
+
+impl<S: IdSource> NamespacedSource<S> {
+    /// Number of namespaces
+    pub const NAMESPACES: u8 = 16;
+
+    /// Draws the rest of the segments of the namespace from the given source
+    pub fn new(namespace: u8, source: S) -> Result<Self, MrnGeneratorError> {
+        if namespace >= Self::NAMESPACES {
+            return Err(MrnGeneratorError::InvalidNamespace(namespace));
+        }
+        Ok(NamespacedSource { namespace, source })
+    }
+}
+
+impl<S: IdSource> IdSource for NamespacedSource<S> {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        let rest = len
+            .checked_sub(1)
+            .ok_or(MrnGeneratorError::SourceExhausted)?;
+        let mut segment = String::with_capacity(len);
+        segment.push(ALPHABET[self.namespace as usize] as char);
+        segment.push_str(&self.source.next_segment(rest)?);
+        Ok(segment)
+    }
+}
+
+/// Bijection of `0..size`, a Feistel network over the smallest even number of bits holding
+/// `size`, applied again to the values that fall outside of it
+fn permute(value: u64, size: u64, key: u64) -> u64 {
+    let bits = (u64::BITS - (size - 1).leading_zeros()).max(2);
+    let half = bits.div_ceil(2);
+    let mask = (1 << half) - 1;
+
+    let mut value = value;
+    loop {
+        let (mut left, mut right) = (value >> half, value & mask);
+        for round in 0..4 {
+            let f = mix(right ^ key.rotate_left(round * 16) ^ u64::from(round)) & mask;
+            (left, right) = (right, left ^ f);
+        }
+        value = (left << half) | right;
+        if value < size {
+            return value;
+        }
+    }
+}
+
+/// SplitMix64 finalizer, scrambling the bits of a value
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Encodes `value` in the given base, left-padded with zeros to `len` digits
+fn encode(mut value: u64, len: usize, base: u64) -> Option<String> {
+    let mut digits = alloc::vec![b'0'; len];
+    for digit in digits.iter_mut().rev() {
+        *digit = ALPHABET[(value % base) as usize];
+        value /= base;
+    }
+
+    if value == 0 {
+        Some(String::from_utf8(digits).unwrap())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sequential_source_test() {
+        let mut source = SequentialSource::starting_at(41);
+        assert_eq!("00041", source.next_segment(5).unwrap());
+        assert_eq!("00042", source.next_segment(5).unwrap());
+
+        let mut source = SequentialSource::starting_at(100);
+        assert_eq!(
+            Err(MrnGeneratorError::SourceExhausted),
+            source.next_segment(2)
+        );
+    }
+
+    #[test]
+    fn permuted_source_test() {
+        // Every one of the 36^3 segments exactly once, then exhausted
+        let mut source = PermutedSource::new(42);
+        let mut seen = alloc::vec![false; 36 * 36 * 36];
+        for _ in 0..seen.len() {
+            let segment = source.next_segment(3).unwrap();
+            let value = usize::from_str_radix(&segment, 36).unwrap();
+            assert!(!seen[value]);
+            seen[value] = true;
+        }
+        assert_eq!(
+            Err(MrnGeneratorError::SourceExhausted),
+            source.next_segment(3)
+        );
+
+        assert_ne!(
+            PermutedSource::new(1).next_segment(12),
+            PermutedSource::new(2).next_segment(12)
+        );
+        assert_eq!(PermutedSource::starting_at(3, 5).next_segment(12), {
+            let mut source = PermutedSource::new(3);
+            (0..5).for_each(|_| drop(source.next_segment(12)));
+            source.next_segment(12)
+        });
+    }
+
+    #[test]
+    fn namespaced_source_test() {
+        let mut source = NamespacedSource::new(0, SequentialSource::starting_at(7)).unwrap();
+        assert_eq!("0007", source.next_segment(4).unwrap());
+        let mut source = NamespacedSource::new(15, PermutedSource::new(7)).unwrap();
+        assert!(source.next_segment(12).unwrap().starts_with('F'));
+        assert_eq!(
+            Err(MrnGeneratorError::SourceExhausted),
+            source.next_segment(0)
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidNamespace(16)),
+            NamespacedSource::new(16, PermutedSource::new(7)).map(|_| ())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn random_source_test() {
+        let segment = RandomSource::default().next_segment(10).unwrap();
+        assert_eq!(10, segment.len());
+        assert!(segment.bytes().all(|b| ALPHABET.contains(&b)));
+
+        assert_eq!(
+            RandomSource::seeded(7).next_segment(10),
+            RandomSource::seeded(7).next_segment(10)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn timestamp_source_test() {
+        let mut source = TimestampSource::default();
+        let first = source.next_segment(8).unwrap();
+        let second = source.next_segment(8).unwrap();
+        assert_eq!(8, first.len());
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,210 @@
+//! [Proptest](https://docs.rs/proptest) strategies of valid MRNs, for property tests of code taking
+//! MRNs
+//!
+//! [mrn_strategy] generates any valid MRN and is narrowed down by its builder methods. Each method
+//! can be called more than once to allow several values, e.g. two countries. The free segment
+//! shrinks towards zeros and the year towards 00, so failing cases are reported with the simplest
+//! MRN that still fails.
+//!
+//! ## Example
+//! ```
+//! use mrn_generator::strategy::mrn_strategy;
+//! use mrn_generator::Procedure;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let strategy = mrn_strategy()
+//!     .country("DK")
+//!     .procedure(Procedure::TransitDeclarationOnly);
+//! TestRunner::default()
+//!     .run(&strategy, |mrn| {
+//!         assert_eq!("DK", mrn.components().country_code);
+//!         assert_eq!('J', mrn.components().procedure_char);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::country::{all_countries, resolve_country};
+use crate::source::{IdSource, ALPHABET};
+use crate::{
+    char_to_procedure_category, generate_mrn, layout, DeclarationOffice, Mrn, MrnConfig,
+    MrnGeneratorError, Procedure, PROCEDURE_CHARACTERS,
+};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use proptest::collection;
+use proptest::sample::select;
+use proptest::strategy::{BoxedStrategy, NewTree, Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+
+/// Strategy of valid MRNs, built with [mrn_strategy]
+#[derive(Debug, Clone, Default)]
+pub struct MrnStrategy {
+    countries: Vec<String>,
+    procedures: Vec<Option<Procedure>>,
+    offices: Vec<String>,
+    years: Vec<u8>,
+}
+
+/// Returns a strategy of valid MRNs of any known country, with or without a procedure, without a
+/// declaration office and of any year
+pub fn mrn_strategy() -> MrnStrategy {
+    MrnStrategy::default()
+}
+
+impl MrnStrategy {
+    /// Allows MRNs of a country, given by its code or name
+    ///
+    /// Panics if the country is unknown.
+    pub fn country(mut self, country: &str) -> Self {
+        let code = resolve_country(country).unwrap_or_else(|e| panic!("{e}"));
+        self.countries.push(code);
+        self
+    }
+
+    /// Allows MRNs of a procedure
+    pub fn procedure(mut self, procedure: Procedure) -> Self {
+        self.procedures.push(Some(procedure));
+        self
+    }
+
+    /// Allows MRNs without a procedure character, as issued before the procedure was encoded
+    pub fn without_procedure(mut self) -> Self {
+        self.procedures.push(None);
+        self
+    }
+
+    /// Allows MRNs starting with a declaration office, an office number or the office reference
+    /// with its country code
+    ///
+    /// Panics if the office is not a valid declaration office.
+    pub fn office(mut self, office: &str) -> Self {
+        let office = DeclarationOffice::new(office).unwrap_or_else(|e| panic!("{e}"));
+        self.offices.push(office.as_str().to_string());
+        self
+    }
+
+    /// Allows MRNs issued in a year, given by its last two digits
+    ///
+    /// Panics if the year is over 99.
+    pub fn year(mut self, year: u8) -> Self {
+        assert!(year <= 99, "{}", MrnGeneratorError::IssueYear(year));
+        self.years.push(year);
+        self
+    }
+
+    fn boxed_strategy(&self) -> BoxedStrategy<Mrn> {
+        let countries = match self.countries.is_empty() {
+            true => all_countries()
+                .iter()
+                .map(|country| country.code.to_string())
+                .collect(),
+            false => self.countries.clone(),
+        };
+        let procedures = match self.procedures.is_empty() {
+            true => PROCEDURE_CHARACTERS
+                .iter()
+                .map(|&c| char_to_procedure_category(c as char))
+                .chain([None])
+                .collect(),
+            false => self.procedures.clone(),
+        };
+        let offices = match self.offices.is_empty() {
+            true => vec![None],
+            false => self.offices.iter().cloned().map(Some).collect(),
+        };
+        let years = match self.years.is_empty() {
+            true => (0u8..100).boxed(),
+            false => select(self.years.clone()).boxed(),
+        };
+        let segment = collection::vec(
+            select(ALPHABET.to_vec()),
+            layout::CHECK_DIGIT.start - layout::SERIAL.start,
+        );
+
+        (
+            select(countries),
+            select(procedures),
+            select(offices),
+            years,
+            segment,
+        )
+            .prop_map(|(country_code, procedure, office, year, segment)| {
+                let config = MrnConfig {
+                    country_code: &country_code,
+                    procedure,
+                    declaration_office: office
+                        .as_deref()
+                        .map(DeclarationOffice::new)
+                        .transpose()
+                        .expect("the offices are checked when added"),
+                    year: Some(year),
+                };
+                let mrn = generate_mrn(&config, Segment(segment))
+                    .expect("the constraints are checked when added");
+                Mrn::try_from(mrn).expect("generated MRNs are valid")
+            })
+            .boxed()
+    }
+}
+
+impl Strategy for MrnStrategy {
+    type Tree = Box<dyn ValueTree<Value = Mrn>>;
+    type Value = Mrn;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.boxed_strategy().new_tree(runner)
+    }
+}
+
+/// Free segment drawn by the strategy, cut to the length the rest of the MRN leaves for it
+struct Segment(Vec<u8>);
+
+impl IdSource for Segment {
+    fn next_segment(&mut self, len: usize) -> Result<String, MrnGeneratorError> {
+        Ok(self.0[..len].iter().map(|&c| c as char).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::test_runner::{TestCaseError, TestError};
+
+    #[test]
+    fn mrn_strategy_test() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = mrn_strategy()
+            .country("Denmark")
+            .country("IT")
+            .procedure(Procedure::ExportOnly)
+            .procedure(Procedure::ImportDeclarationOnly)
+            .office("DK004700")
+            .year(24);
+        runner
+            .run(&strategy, |mrn| {
+                let components = mrn.components();
+                assert!(["DK", "IT"].contains(&components.country_code));
+                assert!(['A', 'R'].contains(&components.procedure_char));
+                assert!(components.serial.starts_with("DK004700"));
+                assert_eq!("24", components.year);
+                Ok(())
+            })
+            .unwrap();
+
+        // A property failing for every MRN shrinks to the simplest one
+        let mut runner = TestRunner::deterministic();
+        let strategy = mrn_strategy()
+            .country("DK")
+            .procedure(Procedure::ExportOnly);
+        match runner.run(&strategy, |_| Err(TestCaseError::fail(""))) {
+            Err(TestError::Fail(_, mrn)) => {
+                assert_eq!("00DK000000000000A", &mrn.as_str()[..17])
+            }
+            result => panic!("unexpected {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Helpers for the tests of code taking MRNs: [assert_valid_mrn] and [assert_invalid_mrn], which
+//! point at the wrong characters of an MRN when they fail, and [mrn_fixture], a builder of
+//! deterministic MRNs with given parts
+//!
+//! ## Example
+//! ```
+//! use mrn_generator::testing::{assert_invalid_mrn, assert_valid_mrn, mrn_fixture};
+//! use mrn_generator::Procedure;
+//!
+//! let mrn = mrn_fixture()
+//!     .country("IT")
+//!     .procedure(Procedure::ExportOnly)
+//!     .year(25)
+//!     .build();
+//! assert_valid_mrn!(mrn);
+//! assert_valid_mrn!("22ITZXBZYUTJFLJXK6", "MRN of declaration {}", 7);
+//! assert_invalid_mrn!("22ITZXBZYUTJFLJXK7");
+//! ```
+//!
+//! A failing assertion shows the error and the characters it is about:
+//! ```text
+//! assertion failed: `"22ITZXBZYUTJFLJXK7"` is a valid MRN
+//!   22ITZXBZYUTJFLJXK7
+//!                    ^ its check digit should be 6
+//! ```
+
+use crate::country::resolve_country;
+use crate::{
+    generate_nth, layout, validate_mrn, DeclarationOffice, Mrn, MrnConfig, MrnGeneratorError,
+    Procedure,
+};
+
+#[doc(inline)]
+pub use crate::{assert_invalid_mrn, assert_valid_mrn};
+
+/// Asserts that an MRN, a `&str`, `String` or [Mrn], is valid, with an optional message like
+/// [assert!]. On failure the MRN is shown with the characters that are wrong marked.
+#[macro_export]
+macro_rules! assert_valid_mrn {
+    ($mrn:expr $(,)?) => {
+        if let Some(failure) =
+            $crate::testing::invalid_mrn_failure(::core::convert::AsRef::<str>::as_ref(&$mrn))
+        {
+            panic!(
+                "assertion failed: `{}` is a valid MRN\n{}",
+                stringify!($mrn),
+                failure
+            );
+        }
+    };
+    ($mrn:expr, $($arg:tt)+) => {
+        if let Some(failure) =
+            $crate::testing::invalid_mrn_failure(::core::convert::AsRef::<str>::as_ref(&$mrn))
+        {
+            panic!(
+                "assertion failed: `{}` is a valid MRN: {}\n{}",
+                stringify!($mrn),
+                format_args!($($arg)+),
+                failure
+            );
+        }
+    };
+}
+
+/// Asserts that an MRN, a `&str` or `String`, is invalid, with an optional message like
+/// [assert!]
+#[macro_export]
+macro_rules! assert_invalid_mrn {
+    ($mrn:expr $(,)?) => {
+        if $crate::testing::invalid_mrn_failure(::core::convert::AsRef::<str>::as_ref(&$mrn))
+            .is_none()
+        {
+            panic!("assertion failed: `{}` is an invalid MRN", stringify!($mrn));
+        }
+    };
+    ($mrn:expr, $($arg:tt)+) => {
+        if $crate::testing::invalid_mrn_failure(::core::convert::AsRef::<str>::as_ref(&$mrn))
+            .is_none()
+        {
+            panic!(
+                "assertion failed: `{}` is an invalid MRN: {}",
+                stringify!($mrn),
+                format_args!($($arg)+)
+            );
+        }
+    };
+}
+
+/// Describes why an MRN is invalid, marking the characters that are wrong, None if it is valid
+#[doc(hidden)]
+pub fn invalid_mrn_failure(mrn: &str) -> Option<String> {
+    let error = validate_mrn(mrn).err()?;
+    let (span, reason) = match &error {
+        MrnGeneratorError::InvalidCharacter { position, found } => (
+            Some(*position..position + found.len_utf8()),
+            "is not a letter or digit".to_string(),
+        ),
+        MrnGeneratorError::InvalidYear(_) => (
+            Some(layout::YEAR),
+            "should be the last two digits of the year".to_string(),
+        ),
+        MrnGeneratorError::InvalidCountryCode(_) => (
+            Some(layout::COUNTRY_CODE),
+            "should be two capital letters".to_string(),
+        ),
+        MrnGeneratorError::InvalidCheckDigit { expected, .. } => (
+            Some(layout::CHECK_DIGIT),
+            format!("its check digit should be {expected}"),
+        ),
+        _ => (None, error.to_string()),
+    };
+    Some(match span {
+        // Marks are placed by characters, not bytes, for MRNs with non-ASCII characters
+        Some(span) => format!(
+            "  {mrn}\n  {}{} {reason}",
+            " ".repeat(mrn[..span.start].chars().count()),
+            "^".repeat(mrn[span].chars().count())
+        ),
+        None => format!("  {mrn}\n  {reason}"),
+    })
+}
+
+/// Builder of deterministic valid MRNs, built with [mrn_fixture]
+#[derive(Debug, Clone)]
+pub struct MrnFixture {
+    country: String,
+    procedure: Option<Procedure>,
+    office: Option<String>,
+    year: u8,
+    seed: u64,
+}
+
+/// Returns a builder of MRNs of Denmark, without a procedure character or declaration office,
+/// issued in 2024. The same builder always builds the same MRNs, whatever the current year.
+pub fn mrn_fixture() -> MrnFixture {
+    MrnFixture {
+        country: "DK".to_string(),
+        procedure: None,
+        office: None,
+        year: 24,
+        seed: 0,
+    }
+}
+
+impl MrnFixture {
+    /// Sets the country, given by its code or name
+    ///
+    /// Panics if the country is unknown.
+    pub fn country(mut self, country: &str) -> Self {
+        self.country = resolve_country(country).unwrap_or_else(|e| panic!("{e}"));
+        self
+    }
+
+    pub fn procedure(mut self, procedure: Procedure) -> Self {
+        self.procedure = Some(procedure);
+        self
+    }
+
+    /// Sets the declaration office the MRNs start with, an office number or the office reference
+    /// with its country code
+    ///
+    /// Panics if the office is not a valid declaration office.
+    pub fn office(mut self, office: &str) -> Self {
+        let office = DeclarationOffice::new(office).unwrap_or_else(|e| panic!("{e}"));
+        self.office = Some(office.as_str().to_string());
+        self
+    }
+
+    /// Sets the issue year, given by its last two digits
+    ///
+    /// Panics if the year is over 99.
+    pub fn year(mut self, year: u8) -> Self {
+        assert!(year <= 99, "{}", MrnGeneratorError::IssueYear(year));
+        self.year = year;
+        self
+    }
+
+    /// Sets the seed, another seed giving other MRNs with the same parts
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Builds the MRN at an index of the seeded MRNs of the builder
+    pub fn build_nth(&self, index: u64) -> Mrn {
+        let config = MrnConfig {
+            country_code: &self.country,
+            procedure: self.procedure,
+            declaration_office: self
+                .office
+                .as_deref()
+                .map(DeclarationOffice::new)
+                .transpose()
+                .expect("the office is checked when set"),
+            year: Some(self.year),
+        };
+        generate_nth(self.seed, index, &config).expect("the parts are checked when set")
+    }
+
+    /// Builds the first MRN of the builder
+    pub fn build(&self) -> Mrn {
+        self.build_nth(0)
+    }
+
+    /// Builds the first MRNs of the builder, which all differ
+    pub fn build_many(&self, count: usize) -> Vec<Mrn> {
+        (0..count as u64)
+            .map(|index| self.build_nth(index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::panic;
+
+    fn failure(f: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(f).unwrap_err();
+        match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => payload.downcast_ref::<String>().unwrap().clone(),
+        }
+    }
+
+    #[test]
+    fn assert_valid_mrn_test() {
+        assert_valid_mrn!("22ITZXBZYUTJFLJXK6");
+        assert_valid_mrn!(String::from("22ITZXBZYUTJFLJXK6"), "with a message");
+        assert_invalid_mrn!("22ITZXBZYUTJFLJXK7");
+
+        assert_eq!(
+            "assertion failed: `\"22ITZXBZYUTJFLJXK7\"` is a valid MRN\n  \
+             22ITZXBZYUTJFLJXK7\n                   ^ its check digit should be 6",
+            failure(|| assert_valid_mrn!("22ITZXBZYUTJFLJXK7"))
+        );
+        assert_eq!(
+            "assertion failed: `mrn` is a valid MRN: row 3\n  \
+             22ÏTZXBZYUTJFLJXK6\n    ^ is not a letter or digit",
+            failure(|| {
+                let mrn = "22ÏTZXBZYUTJFLJXK6";
+                assert_valid_mrn!(mrn, "row {}", 3)
+            })
+        );
+        assert!(failure(|| assert_valid_mrn!("26DK")).contains("exactly 18 characters"));
+        assert_eq!(
+            "assertion failed: `\"22ITZXBZYUTJFLJXK6\"` is an invalid MRN",
+            failure(|| assert_invalid_mrn!("22ITZXBZYUTJFLJXK6"))
+        );
+    }
+
+    #[test]
+    fn mrn_fixture_test() {
+        let fixture = mrn_fixture()
+            .country("Greece")
+            .procedure(Procedure::TransitDeclarationOnly)
+            .office("004700")
+            .year(26);
+        let mrn = fixture.build();
+        assert_eq!(mrn, fixture.build());
+        assert!(mrn.as_str().starts_with("26GR004700"));
+        assert_eq!('J', mrn.components().procedure_char);
+
+        let mrns = fixture.build_many(3);
+        assert_eq!(mrn, mrns[0]);
+        assert!(mrns[0] != mrns[1] && mrns[1] != mrns[2]);
+        assert_ne!(mrn, fixture.seed(1).build());
+    }
+}
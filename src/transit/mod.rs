@@ -0,0 +1,127 @@
+//! Common Transit Convention (CTC) conventions
+//!
+//! Transit movements under the convention are declared in NCTS with the D declaration categories,
+//! standalone or combined with an exit (A) or entry (F) summary declaration, by the customs
+//! offices of its contracting parties.
+
+use crate::{char_to_procedure_category, layout, match_optional_procedure, validate_mrn};
+use crate::{MrnGeneratorError, Procedure};
+use alloc::string::ToString;
+
+/// Country codes of the contracting parties of the convention, the EU member states, Northern
+/// Ireland and the common transit countries
+pub const CTC_COUNTRIES: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK", "XI", "CH", "GB", "GE", "IS",
+    "MK", "NO", "RS", "TR", "UA",
+];
+
+/// Returns true if the country code is one of a contracting party of the convention
+pub fn is_ctc_country(country_code: &str) -> bool {
+    CTC_COUNTRIES.contains(&country_code)
+}
+
+/// Returns true if the procedure includes a transit declaration
+pub fn is_transit_procedure(procedure: Procedure) -> bool {
+    matches!(
+        procedure,
+        Procedure::TransitDeclarationOnly
+            | Procedure::TransitDeclarationAndExitSummaryDeclaration
+            | Procedure::TransitDeclarationAndEntrySummaryDeclaration
+    )
+}
+
+/// Matches the procedure of a transit declaration, defaulting to a transit declaration only
+///
+/// ## Example
+/// ```
+/// use mrn_generator::transit::match_transit_procedure;
+/// use mrn_generator::Procedure;
+///
+/// assert_eq!(Ok(Procedure::TransitDeclarationOnly), match_transit_procedure(None, None));
+/// assert_eq!(
+///     Ok(Procedure::TransitDeclarationAndEntrySummaryDeclaration),
+///     match_transit_procedure(Some("D2"), Some("F"))
+/// );
+/// assert!(match_transit_procedure(Some("H1"), None).is_err());
+/// ```
+pub fn match_transit_procedure(
+    proctgr: Option<&str>,
+    combined: Option<&str>,
+) -> Result<Procedure, MrnGeneratorError> {
+    check_transit_procedure(match_optional_procedure(proctgr, combined)?)
+}
+
+/// Checks that an already matched procedure is a transit declaration procedure, defaulting to
+/// [Procedure::TransitDeclarationOnly] if there is none
+pub fn check_transit_procedure(
+    procedure: Option<Procedure>,
+) -> Result<Procedure, MrnGeneratorError> {
+    let procedure = procedure.unwrap_or(Procedure::TransitDeclarationOnly);
+    if is_transit_procedure(procedure) {
+        Ok(procedure)
+    } else {
+        Err(MrnGeneratorError::NotATransitProcedure(procedure))
+    }
+}
+
+/// Checks that the MRN is valid and plausible as the MRN of a transit movement under the
+/// convention, i.e. its country is a contracting party and its procedure character is one of a
+/// transit declaration
+pub fn validate_ctc_mrn(mrn: &str) -> Result<(), MrnGeneratorError> {
+    validate_mrn(mrn)?;
+
+    let country_code = &mrn[layout::COUNTRY_CODE];
+    if !is_ctc_country(country_code) {
+        return Err(MrnGeneratorError::NotACtcCountry(country_code.to_string()));
+    }
+    let procedure_char = mrn[layout::PROCEDURE].chars().next().unwrap();
+    match char_to_procedure_category(procedure_char) {
+        Some(procedure) if is_transit_procedure(procedure) => Ok(()),
+        _ => Err(MrnGeneratorError::NotATransitMrn {
+            mrn: mrn.to_string(),
+            procedure_char,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::country::country_name;
+
+    #[test]
+    fn match_transit_procedure_test() {
+        assert_eq!(
+            Ok(Procedure::TransitDeclarationAndExitSummaryDeclaration),
+            match_transit_procedure(Some("D3"), Some("A"))
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::NotATransitProcedure(
+                Procedure::ExportOnly
+            )),
+            match_transit_procedure(Some("B1"), None)
+        );
+        assert!(CTC_COUNTRIES
+            .iter()
+            .all(|code| country_name(code).is_some()));
+    }
+
+    #[test]
+    fn validate_ctc_mrn_test() {
+        assert_eq!(Ok(()), validate_ctc_mrn("21BEQ22D4Q2F66DEJ1"));
+        assert_eq!(Ok(()), validate_ctc_mrn("22ITZXBZYUTJFLJXK6"));
+        assert_eq!(
+            Err(MrnGeneratorError::NotATransitMrn {
+                mrn: "24DKU1B0GKUUN40NA3".to_string(),
+                procedure_char: 'A'
+            }),
+            validate_ctc_mrn("24DKU1B0GKUUN40NA3")
+        );
+        assert_eq!(
+            Err(MrnGeneratorError::NotACtcCountry("SM".to_string())),
+            validate_ctc_mrn("26SMJLMZBD2WCZ5MJ8")
+        );
+    }
+}
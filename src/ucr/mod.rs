@@ -0,0 +1,209 @@
+//! UK Declaration and Master Unique Consignment References (DUCR/MUCR) used by CDS
+
+use crate::MrnGeneratorError;
+#[cfg(feature = "generate")]
+use crate::{source::IdSource, source::RandomSource};
+use alloc::string::ToString;
+#[cfg(feature = "generate")]
+use alloc::{format, string::String};
+
+/// Maximum length of a DUCR or MUCR
+pub const UCR_MAX_LENGTH: usize = 35;
+
+/// Returns a DUCR such as `4GB123456789000-3F7K2QZA` for the given GB EORI number
+///
+/// The DUCR is the last digit of the year, the trader's EORI number and a random reference.
+/// A random EORI is used if none is given.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::ucr::{generate_random_ducr, validate_ducr};
+///
+/// let ducr = generate_random_ducr(Some("GB123456789000")).unwrap();
+/// assert_eq!(Ok(()), validate_ducr(&ducr));
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_random_ducr(eori: Option<&str>) -> Result<String, MrnGeneratorError> {
+    let year = crate::current_year().chars().last().unwrap();
+    let ducr = format!("{year}{}-{}", trader_eori(eori)?, random_reference()?);
+    validate_ducr(&ducr)?;
+    Ok(ducr)
+}
+
+/// Returns a MUCR such as `GB/123456789000-3F7K2QZA` for the given GB EORI number
+///
+/// A random EORI is used if none is given.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::ucr::{generate_random_mucr, validate_mucr};
+///
+/// let mucr = generate_random_mucr(Some("GB123456789000")).unwrap();
+/// assert!(mucr.starts_with("GB/123456789000-"));
+/// assert_eq!(Ok(()), validate_mucr(&mucr));
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_random_mucr(eori: Option<&str>) -> Result<String, MrnGeneratorError> {
+    let eori = trader_eori(eori)?;
+    let mucr = format!("GB/{}-{}", &eori[2..], random_reference()?);
+    validate_mucr(&mucr)?;
+    Ok(mucr)
+}
+
+/// Checks the DUCR format: a year digit, a country code and 6 to 32 characters of
+/// `0-9`, `A-Z`, `(`, `)`, `-` or `/`
+pub fn validate_ducr(ducr: &str) -> Result<(), MrnGeneratorError> {
+    if is_ducr(ducr) {
+        Ok(())
+    } else {
+        Err(MrnGeneratorError::InvalidDucr(ducr.to_string()))
+    }
+}
+
+/// Checks the MUCR format, which accepts a DUCR, a `GB/` reference, or an air (`A:`) or
+/// CSP (`C:`) consolidation reference
+pub fn validate_mucr(mucr: &str) -> Result<(), MrnGeneratorError> {
+    let valid = mucr.len() <= UCR_MAX_LENGTH
+        && (is_ducr(mucr) || is_gb_mucr(mucr) || is_air_mucr(mucr) || is_csp_mucr(mucr));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(MrnGeneratorError::InvalidMucr(mucr.to_string()))
+    }
+}
+
+/// `[0-9][A-Z]{2}[0-9A-Z()\-/]{6,32}`
+fn is_ducr(ucr: &str) -> bool {
+    let bytes = ucr.as_bytes();
+    bytes.len() >= 9
+        && bytes.len() <= UCR_MAX_LENGTH
+        && bytes[0].is_ascii_digit()
+        && bytes[1..3].iter().all(u8::is_ascii_uppercase)
+        && bytes[3..]
+            .iter()
+            .all(|b| is_upper_alphanumeric(b) || b"()-/".contains(b))
+}
+
+/// `GB/[0-9A-Z]{3,4}-[0-9A-Z]{5,28}` or `GB/[0-9A-Z]{9,12}-[0-9A-Z]{1,23}`
+fn is_gb_mucr(ucr: &str) -> bool {
+    let Some((owner, reference)) = ucr.strip_prefix("GB/").and_then(|r| r.split_once('-')) else {
+        return false;
+    };
+
+    let alphanumeric = |s: &str| s.bytes().all(|b| is_upper_alphanumeric(&b));
+    if !alphanumeric(owner) || !alphanumeric(reference) {
+        return false;
+    }
+
+    match owner.len() {
+        3..=4 => (5..=28).contains(&reference.len()),
+        9..=12 => (1..=23).contains(&reference.len()),
+        _ => false,
+    }
+}
+
+/// `A:[0-9A-Z]{3}[0-9]{8}`
+fn is_air_mucr(ucr: &str) -> bool {
+    ucr.strip_prefix("A:").is_some_and(|r| {
+        let bytes = r.as_bytes();
+        bytes.len() == 11
+            && bytes[..3].iter().all(is_upper_alphanumeric)
+            && bytes[3..].iter().all(u8::is_ascii_digit)
+    })
+}
+
+/// `C:[A-Z]{3}[0-9A-Z]{3,30}`
+fn is_csp_mucr(ucr: &str) -> bool {
+    ucr.strip_prefix("C:").is_some_and(|r| {
+        let bytes = r.as_bytes();
+        (6..=33).contains(&bytes.len())
+            && bytes[..3].iter().all(u8::is_ascii_uppercase)
+            && bytes[3..].iter().all(is_upper_alphanumeric)
+    })
+}
+
+fn is_upper_alphanumeric(b: &u8) -> bool {
+    b.is_ascii_uppercase() || b.is_ascii_digit()
+}
+
+/// The supplied GB EORI, or a random one
+#[cfg(feature = "generate")]
+fn trader_eori(eori: Option<&str>) -> Result<String, MrnGeneratorError> {
+    match eori {
+        Some(eori) => {
+            crate::eori::validate_eori(eori)?;
+            Ok(eori.to_string())
+        }
+        None => crate::eori::generate_random_eori("GB"),
+    }
+}
+
+#[cfg(feature = "generate")]
+fn random_reference() -> Result<String, MrnGeneratorError> {
+    RandomSource::default().next_segment(8)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "generate")]
+    fn generate_random_ucr_test() {
+        let ducr = generate_random_ducr(None).unwrap();
+        assert_eq!(Ok(()), validate_ducr(&ducr));
+        assert_eq!(&ducr[1..3], "GB");
+
+        let mucr = generate_random_mucr(None).unwrap();
+        assert_eq!(Ok(()), validate_mucr(&mucr));
+
+        assert_eq!(
+            Err(MrnGeneratorError::InvalidEori("GB 123".to_string())),
+            generate_random_ducr(Some("GB 123"))
+        );
+    }
+
+    #[test]
+    fn validate_ducr_test() {
+        assert_eq!(Ok(()), validate_ducr("4GB123456789000-INV12345"));
+        assert_eq!(Ok(()), validate_ducr("9GB123456789000-(A)/1"));
+        for invalid in [
+            "GB123456789000-INV1",
+            "4GB12345",
+            "4gb123456789000-INV1",
+            "4GB123456789000-INV 1",
+        ] {
+            assert_eq!(
+                Err(MrnGeneratorError::InvalidDucr(invalid.to_string())),
+                validate_ducr(invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_mucr_test() {
+        for valid in [
+            "GB/ABCD-12345",
+            "GB/123456789000-1",
+            "A:ABC12345678",
+            "C:ABC123",
+            "4GB123456789000-INV12345",
+        ] {
+            assert_eq!(Ok(()), validate_mucr(valid));
+        }
+        for invalid in [
+            "GB/AB-12345",
+            "GB/ABCD-1234",
+            "A:ABC1234567",
+            "C:AB1123",
+            "GB/ABCD12345",
+        ] {
+            assert_eq!(
+                Err(MrnGeneratorError::InvalidMucr(invalid.to_string())),
+                validate_mucr(invalid)
+            );
+        }
+    }
+}
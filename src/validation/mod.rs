@@ -0,0 +1,607 @@
+//! Semantic validation, checks that go beyond the structure and check digit of an MRN
+
+use crate::country::{country_name, suggest_country_code};
+use crate::{layout, parse_components, validate_mrn, DidYouMean, MrnGeneratorError};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use thiserror::Error;
+
+/// Findings of the semantic checks of a structurally valid MRN, from worth knowing to making it
+/// invalid, see [Finding::severity]
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum Finding {
+    #[error("year {year:02} is in the future, the current year is {current_year:02}")]
+    FutureYear { year: u8, current_year: u8 },
+    #[error("declaration office segment starts with {office_prefix} instead of country code {country_code}")]
+    OfficeCountryMismatch {
+        country_code: String,
+        office_prefix: String,
+    },
+    #[error(
+        "country code {country_code} is not a known issuing country{}",
+        DidYouMean(suggestion)
+    )]
+    UnknownCountry {
+        country_code: String,
+        suggestion: Option<String>,
+    },
+    #[error("declaration office number {office_number} has letters, while the office numbers of {country_code} are digits")]
+    OfficeNotNumeric {
+        country_code: String,
+        office_number: String,
+    },
+    #[error("year {year:02} does not match the declaration date {declaration_date}")]
+    DeclarationYearMismatch {
+        year: u8,
+        declaration_date: DeclarationDate,
+    },
+}
+
+/// How much a finding of the validation of an MRN matters, from the least to the most
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    /// Worth knowing, the MRN is most likely fine
+    Info,
+    /// The MRN is implausible, though valid
+    Warning,
+    /// The MRN is invalid
+    Error,
+}
+
+impl Finding {
+    /// Severity of the finding, only [Severity::Error] making the MRN invalid
+    ///
+    /// A declaration year mismatch is an error, as the declaration date is only given to check
+    /// the MRN year against it.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::FutureYear { .. }
+            | Finding::OfficeCountryMismatch { .. }
+            | Finding::UnknownCountry { .. } => Severity::Warning,
+            Finding::OfficeNotNumeric { .. } => Severity::Info,
+            Finding::DeclarationYearMismatch { .. } => Severity::Error,
+        }
+    }
+}
+
+/// Countries whose customs office numbers are all digits, e.g. `DK004700`
+pub const NUMERIC_OFFICE_COUNTRIES: &[&str] = &[
+    "AT", "BE", "DE", "DK", "ES", "FR", "GR", "IE", "IT", "NL", "PL", "PT", "SE",
+];
+
+/// First year of the hundred years the two year digits of MRNs are taken from by default, the
+/// years 00 to 99 being 2000 to 2099
+pub const DEFAULT_CENTURY_PIVOT: u16 = 2000;
+
+/// Days around New Year within which an MRN may have the year before or after that of its
+/// declaration date, for declarations dated when they were drafted or in another time zone
+pub const YEAR_BOUNDARY_TOLERANCE_DAYS: u16 = 7;
+
+/// Calendar date of a declaration, to check the year of its MRN against
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DeclarationDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl DeclarationDate {
+    /// Returns the date, None if it doesn't exist
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        let date = DeclarationDate { year, month, day };
+        ((1..=12).contains(&month) && (1..=date.days_in_month(month)).contains(&day))
+            .then_some(date)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn is_leap_year(&self) -> bool {
+        self.year.is_multiple_of(4)
+            && (!self.year.is_multiple_of(100) || self.year.is_multiple_of(400))
+    }
+
+    fn days_in_month(&self, month: u8) -> u8 {
+        match month {
+            2 if self.is_leap_year() => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Day of the year, from 1
+    fn ordinal(&self) -> u16 {
+        let days_before: u16 = (1..self.month)
+            .map(|month| u16::from(self.days_in_month(month)))
+            .sum();
+        days_before + u16::from(self.day)
+    }
+
+    /// Returns true if an MRN with the last two digits of a year can be of a declaration of this
+    /// date, i.e. the year is that of the date, or the year before or after within
+    /// [YEAR_BOUNDARY_TOLERANCE_DAYS] of New Year
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::validation::DeclarationDate;
+    ///
+    /// let date = DeclarationDate::new(2024, 1, 3).unwrap();
+    /// assert!(date.matches_year(24));
+    /// assert!(date.matches_year(23));
+    /// assert!(!date.matches_year(25));
+    /// assert!(!DeclarationDate::new(2024, 3, 1).unwrap().matches_year(23));
+    /// ```
+    pub fn matches_year(&self, year: u8) -> bool {
+        let days_in_year = if self.is_leap_year() { 366 } else { 365 };
+        let ordinal = self.ordinal();
+        let two_digits = |year: u16| (year % 100) as u8;
+        year == two_digits(self.year)
+            || (ordinal <= YEAR_BOUNDARY_TOLERANCE_DAYS && year == two_digits(self.year + 99))
+            || (days_in_year - ordinal < YEAR_BOUNDARY_TOLERANCE_DAYS
+                && year == two_digits(self.year + 1))
+    }
+}
+
+impl fmt::Display for DeclarationDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Options of the semantic checks
+#[derive(Debug, PartialEq, Clone)]
+pub struct SemanticOptions {
+    /// Year the MRNs are validated in, e.g. 2024
+    pub current_year: u16,
+    /// Number of years after the current one that are still accepted, to allow for MRNs issued
+    /// around the year rollover in other time zones
+    pub future_year_tolerance: u8,
+    /// Whether the MRNs embed a customs office reference (e.g. `DK004700`) right after their
+    /// country code, which should then start with the same country code
+    pub check_office_country: bool,
+    /// Whether to look for a single character correction of invalid MRNs, see [suggest_repair]
+    pub suggest_repair: bool,
+    /// First year of the hundred years the two year digits of MRNs are taken from, e.g. 1990 for
+    /// archived MRNs of the 1990s, see [crate::MrnComponents::issue_year_from]
+    pub century_pivot: u16,
+    /// Date of the declarations of the MRNs, whose year the MRN years should match, see
+    /// [DeclarationDate::matches_year]
+    pub declaration_date: Option<DeclarationDate>,
+}
+
+impl SemanticOptions {
+    /// Options for validating in the given year, without any tolerance or optional checks
+    pub fn for_year(current_year: u16) -> Self {
+        SemanticOptions {
+            current_year,
+            future_year_tolerance: 0,
+            check_office_country: false,
+            suggest_repair: false,
+            century_pivot: DEFAULT_CENTURY_PIVOT,
+            declaration_date: None,
+        }
+    }
+
+    /// Options for validating in the current year, without any tolerance or optional checks
+    #[cfg(feature = "generate")]
+    pub fn now() -> Self {
+        use chrono::{Datelike, Utc};
+
+        Self::for_year(Utc::now().year() as u16)
+    }
+}
+
+/// Result of validating an MRN, with structural errors and semantic findings
+#[derive(Debug, PartialEq, Clone)]
+pub struct ValidationReport {
+    pub mrn: String,
+    pub errors: Vec<MrnGeneratorError>,
+    pub findings: Vec<Finding>,
+    /// Correction of an invalid MRN, if it was asked for and one was found
+    pub repair: Option<Repair>,
+}
+
+impl ValidationReport {
+    /// Returns true if there are no errors or semantic findings of [Severity::Error], other
+    /// findings are allowed
+    pub fn is_valid(&self) -> bool {
+        self.passes(Severity::Error)
+    }
+
+    /// Highest severity of the errors and semantic findings, None if there are none
+    pub fn severity(&self) -> Option<Severity> {
+        match self.errors.is_empty() {
+            true => self.findings.iter().map(Finding::severity).max(),
+            false => Some(Severity::Error),
+        }
+    }
+
+    /// Returns true if all errors and findings are below a severity, e.g. with
+    /// [Severity::Warning] for strict pipelines that treat warnings as errors
+    ///
+    /// ## Example
+    /// ```
+    /// use mrn_generator::validation::{validate_semantics, SemanticOptions, Severity};
+    ///
+    /// let report = validate_semantics("22ITZXBZYUTJFLJXK6", &SemanticOptions::for_year(2021));
+    /// assert_eq!(Some(Severity::Warning), report.severity());
+    /// assert!(report.passes(Severity::Error));
+    /// assert!(!report.passes(Severity::Warning));
+    /// ```
+    pub fn passes(&self, severity: Severity) -> bool {
+        self.severity().is_none_or(|found| found < severity)
+    }
+}
+
+/// Validates the MRN and runs the semantic checks on it
+///
+/// Semantic checks only run on structurally valid MRNs.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::validation::{validate_semantics, SemanticOptions, Finding};
+///
+/// let report = validate_semantics("22ITZXBZYUTJFLJXK6", &SemanticOptions::for_year(2021));
+/// assert!(report.is_valid());
+/// assert_eq!(
+///     vec![Finding::FutureYear { year: 22, current_year: 21 }],
+///     report.findings
+/// );
+/// ```
+pub fn validate_semantics(mrn: &str, options: &SemanticOptions) -> ValidationReport {
+    let mut report = ValidationReport {
+        mrn: mrn.to_string(),
+        errors: Vec::new(),
+        findings: Vec::new(),
+        repair: None,
+    };
+
+    if let Err(e) = validate_mrn(mrn) {
+        report.errors.push(e);
+        if options.suggest_repair {
+            report.repair = suggest_repair(mrn);
+        }
+        return report;
+    }
+
+    let year: u8 = mrn[layout::YEAR].parse().unwrap();
+    let issue_year = parse_components(mrn)
+        .ok()
+        .and_then(|components| components.issue_year_from(options.century_pivot.into()))
+        .expect("the MRN is valid");
+    let current_year = (options.current_year % 100) as u8;
+    let latest_year = i32::from(options.current_year) + i32::from(options.future_year_tolerance);
+    if issue_year > latest_year {
+        report
+            .findings
+            .push(Finding::FutureYear { year, current_year });
+    }
+    if let Some(declaration_date) = options.declaration_date {
+        if !declaration_date.matches_year(year) {
+            report.findings.push(Finding::DeclarationYearMismatch {
+                year,
+                declaration_date,
+            });
+        }
+    }
+
+    let (country_code, office_prefix) = (
+        &mrn[layout::COUNTRY_CODE],
+        &mrn[layout::OFFICE_REFERENCE][..2],
+    );
+    if country_name(country_code).is_none() {
+        report.findings.push(Finding::UnknownCountry {
+            country_code: country_code.to_string(),
+            suggestion: suggest_country_code(country_code).map(str::to_string),
+        });
+    }
+    if options.check_office_country && country_code != office_prefix {
+        report.findings.push(Finding::OfficeCountryMismatch {
+            country_code: country_code.to_string(),
+            office_prefix: office_prefix.to_string(),
+        });
+    }
+
+    // Only an office reference starting with the country code tells the office apart from the
+    // rest of the serial
+    let office_number = &mrn[layout::OFFICE_REFERENCE][2..];
+    if country_code == office_prefix
+        && NUMERIC_OFFICE_COUNTRIES.contains(&country_code)
+        && !office_number.bytes().all(|b| b.is_ascii_digit())
+    {
+        report.findings.push(Finding::OfficeNotNumeric {
+            country_code: country_code.to_string(),
+            office_number: office_number.to_string(),
+        });
+    }
+
+    report
+}
+
+/// A single character correction making an invalid MRN valid
+#[derive(Debug, PartialEq, Clone)]
+pub struct Repair {
+    /// Position of the corrected character, from 0
+    pub position: usize,
+    pub found: char,
+    pub replacement: char,
+    /// The corrected MRN
+    pub mrn: String,
+}
+
+/// Characters OCR commonly reads in place of each other
+const LOOK_ALIKES: &[(char, char)] = &[
+    ('0', 'O'),
+    ('0', 'D'),
+    ('0', 'Q'),
+    ('1', 'I'),
+    ('1', 'L'),
+    ('2', 'Z'),
+    ('5', 'S'),
+    ('6', 'G'),
+    ('8', 'B'),
+];
+
+/// Looks for the smallest single character correction making an invalid MRN valid, as for an MRN
+/// scanned with OCR. A lowercase letter is the smallest correction, then the check digit, which
+/// makes up for any other wrong character of a well-formed MRN, and last a character swapped for
+/// its look-alike where the MRN isn't well-formed, e.g. `O` for `0` in the year. None if the MRN
+/// is valid or no single correction makes it valid.
+///
+/// ## Example
+/// ```
+/// use mrn_generator::validation::suggest_repair;
+///
+/// let repair = suggest_repair("22ITZXBZYUTJFLJXK7").unwrap();
+/// assert_eq!("22ITZXBZYUTJFLJXK6", repair.mrn);
+/// let repair = suggest_repair("22ITZXBZYUTJFLJXKG").unwrap();
+/// assert_eq!((17, 'G', '6'), (repair.position, repair.found, repair.replacement));
+/// assert_eq!(None, suggest_repair("22ITZXBZYUTJFLJXK6"));
+/// ```
+pub fn suggest_repair(mrn: &str) -> Option<Repair> {
+    if validate_mrn(mrn).is_ok() || !mrn.is_ascii() || mrn.len() != layout::LENGTH {
+        return None;
+    }
+    let chars: Vec<char> = mrn.chars().collect();
+    let look_alikes = |c: char| {
+        LOOK_ALIKES.iter().filter_map(move |&(a, b)| match c {
+            c if c == a => Some(b),
+            c if c == b => Some(a),
+            _ => None,
+        })
+    };
+    let lowercase = chars
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_ascii_lowercase())
+        .map(|(position, c)| (position, c.to_ascii_uppercase()));
+    let swapped = chars
+        .iter()
+        .enumerate()
+        .flat_map(|(position, &c)| look_alikes(c).map(move |replacement| (position, replacement)));
+    let check_digit = (0..10).map(|digit| {
+        let digit = char::from_digit(digit, 10).expect("the digit is below 10");
+        (layout::CHECK_DIGIT.start, digit)
+    });
+
+    lowercase
+        .chain(check_digit)
+        .chain(swapped)
+        .find_map(|(position, replacement)| {
+            let mut repaired = chars.clone();
+            repaired[position] = replacement;
+            let repaired: String = repaired.into_iter().collect();
+            validate_mrn(&repaired).is_ok().then(|| Repair {
+                position,
+                found: chars[position],
+                replacement,
+                mrn: repaired,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn future_year_test() {
+        let options = SemanticOptions::for_year(2022);
+        assert_eq!(
+            Vec::<Finding>::new(),
+            validate_semantics("22ITZXBZYUTJFLJXK6", &options).findings
+        );
+
+        let options = SemanticOptions::for_year(2021);
+        assert_eq!(
+            vec![Finding::FutureYear {
+                year: 22,
+                current_year: 21
+            }],
+            validate_semantics("22ITZXBZYUTJFLJXK6", &options).findings
+        );
+
+        let options = SemanticOptions {
+            future_year_tolerance: 1,
+            ..options
+        };
+        assert!(validate_semantics("22ITZXBZYUTJFLJXK6", &options)
+            .findings
+            .is_empty());
+
+        // Archived MRNs of the 1990s are only in the past with a pivot before them
+        let options = SemanticOptions::for_year(2026);
+        assert!(!validate_semantics("95DK0047002M8N41B0", &options)
+            .findings
+            .is_empty());
+        let options = SemanticOptions {
+            century_pivot: 1990,
+            ..options
+        };
+        assert!(validate_semantics("95DK0047002M8N41B0", &options)
+            .findings
+            .is_empty());
+    }
+
+    #[test]
+    fn declaration_date_test() {
+        assert_eq!(None, DeclarationDate::new(2023, 2, 29));
+        assert_eq!(None, DeclarationDate::new(2024, 13, 1));
+        let leap_day = DeclarationDate::new(2024, 2, 29).unwrap();
+        assert_eq!("2024-02-29", leap_day.to_string());
+        assert_eq!(60, leap_day.ordinal());
+
+        let new_years_eve = DeclarationDate::new(2099, 12, 31).unwrap();
+        assert!(new_years_eve.matches_year(99));
+        assert!(new_years_eve.matches_year(0));
+        assert!(!new_years_eve.matches_year(98));
+        assert!(DeclarationDate::new(2000, 1, 7).unwrap().matches_year(99));
+        assert!(!DeclarationDate::new(2000, 1, 8).unwrap().matches_year(99));
+
+        let options = SemanticOptions {
+            declaration_date: DeclarationDate::new(2024, 3, 1),
+            ..SemanticOptions::for_year(2024)
+        };
+        assert_eq!(
+            vec![Finding::DeclarationYearMismatch {
+                year: 22,
+                declaration_date: DeclarationDate::new(2024, 3, 1).unwrap()
+            }],
+            validate_semantics("22ITZXBZYUTJFLJXK6", &options).findings
+        );
+    }
+
+    #[test]
+    fn severity_test() {
+        let options = SemanticOptions::for_year(2024);
+        let report = validate_semantics("24DKDKAB4700ABCDE8", &options);
+        assert_eq!(Some(Severity::Info), report.severity());
+        assert!(report.passes(Severity::Warning));
+
+        let report = validate_semantics("24DKDKAB4700ABCDE4", &options);
+        assert_eq!(Some(Severity::Error), report.severity());
+        assert!(!report.is_valid());
+        assert_eq!(
+            None,
+            validate_semantics("22ITZXBZYUTJFLJXK6", &options).severity()
+        );
+
+        let options = SemanticOptions {
+            declaration_date: DeclarationDate::new(2022, 6, 1),
+            ..options
+        };
+        let report = validate_semantics("24DKDKAB4700ABCDE8", &options);
+        assert_eq!(Some(Severity::Error), report.severity());
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn office_country_test() {
+        let options = SemanticOptions {
+            check_office_country: true,
+            ..SemanticOptions::for_year(2024)
+        };
+        assert!(validate_semantics("24DKDK004700ABCDE0", &options)
+            .findings
+            .is_empty());
+        assert_eq!(
+            vec![Finding::OfficeCountryMismatch {
+                country_code: "DK".to_string(),
+                office_prefix: "00".to_string()
+            }],
+            validate_semantics("24DK004700ABCDEFG3", &options).findings
+        );
+        assert!(
+            validate_semantics("24DK004700ABCDEFG3", &SemanticOptions::for_year(2024))
+                .findings
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn office_not_numeric_test() {
+        let options = SemanticOptions::for_year(2024);
+        let report = validate_semantics("24DKDK00A700ABCDE6", &options);
+        assert!(report.is_valid());
+        assert_eq!(
+            vec![Finding::OfficeNotNumeric {
+                country_code: "DK".to_string(),
+                office_number: "00A700".to_string()
+            }],
+            report.findings
+        );
+        assert!(validate_semantics("24DKDK004700ABCDE0", &options)
+            .findings
+            .is_empty());
+        // Without the country code the office can't be told apart from the rest of the serial
+        assert!(validate_semantics("24DK00A700ABCDEFG2", &options)
+            .findings
+            .is_empty());
+    }
+
+    #[test]
+    fn unknown_country_test() {
+        let options = SemanticOptions::for_year(2024);
+        let report = validate_semantics("24ELZXBZYUTJFLJXK7", &options);
+        assert!(report.is_valid());
+        assert_eq!(
+            vec![Finding::UnknownCountry {
+                country_code: "EL".to_string(),
+                suggestion: Some("GR".to_string())
+            }],
+            report.findings
+        );
+        assert_eq!(
+            "country code EL is not a known issuing country, did you mean GR?",
+            report.findings[0].to_string()
+        );
+    }
+
+    #[test]
+    fn structural_errors_test() {
+        let report = validate_semantics("22ITZXBZYUTJFLJXK7", &SemanticOptions::for_year(2021));
+        assert!(!report.is_valid());
+        assert!(report.findings.is_empty());
+        assert_eq!(None, report.repair);
+    }
+
+    #[test]
+    fn suggest_repair_test() {
+        // A letter read as its look-alike digit where the MRN should have a digit
+        assert_eq!(
+            Some(Repair {
+                position: 1,
+                found: 'Z',
+                replacement: '2',
+                mrn: "22ITZXBZYUTJFLJXK6".to_string(),
+            }),
+            suggest_repair("2ZITZXBZYUTJFLJXK6")
+        );
+        assert_eq!(
+            Some("22ITZXBZYUTJFLJXK6".to_string()),
+            suggest_repair("22ITZXBZYUTJFLJXK0").map(|repair| repair.mrn)
+        );
+        assert_eq!(
+            Some("22ITZXBZYUTJFLJXK6".to_string()),
+            suggest_repair("22ITzXBZYUTJFLJXK6").map(|repair| repair.mrn)
+        );
+        assert_eq!(None, suggest_repair("22ITZXBZYUTJFLJXK"));
+        assert_eq!(None, suggest_repair("22itzxbzyutjfljxk6"));
+
+        let options = SemanticOptions {
+            suggest_repair: true,
+            ..SemanticOptions::for_year(2024)
+        };
+        let report = validate_semantics("22ITZXBZYUTJFLJXK7", &options);
+        assert!(!report.is_valid());
+        assert_eq!(
+            Some("22ITZXBZYUTJFLJXK6"),
+            report.repair.as_ref().map(|repair| repair.mrn.as_str())
+        );
+    }
+}
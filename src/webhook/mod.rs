@@ -0,0 +1,141 @@
+//! Summaries of completed batches, POSTed to a webhook for pipeline orchestration
+//!
+//! A summary is a JSON object with the `count` of MRNs in the batch, a `sample` of its first MRNs
+//! and the `sha256` checksum of its MRNs one per line, the checksum of plain output of the batch.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Number of MRNs in the sample of a summary
+pub const SAMPLE_SIZE: usize = 10;
+
+/// How long a webhook may take to answer
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// MRNs of a batch, summarized as they are added
+#[derive(Default)]
+pub struct Batch {
+    count: usize,
+    sample: Vec<String>,
+    hasher: Sha256,
+}
+
+impl<'a> Extend<&'a String> for Batch {
+    fn extend<I: IntoIterator<Item = &'a String>>(&mut self, mrns: I) {
+        for mrn in mrns {
+            self.add(mrn);
+        }
+    }
+}
+
+impl Batch {
+    pub fn add(&mut self, mrn: &str) {
+        self.count += 1;
+        if self.sample.len() < SAMPLE_SIZE {
+            self.sample.push(mrn.to_string());
+        }
+        self.hasher.update(mrn);
+        self.hasher.update("\n");
+    }
+
+    pub fn summary(self) -> Value {
+        json!({
+            "count": self.count,
+            "sample": self.sample,
+            "sha256": format!("{:x}", self.hasher.finalize()),
+        })
+    }
+}
+
+/// Endpoint notified of completed batches
+pub struct Webhook {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl Webhook {
+    pub fn new(url: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .build()
+            .into();
+        Webhook {
+            url: url.to_string(),
+            agent,
+        }
+    }
+
+    /// POSTs the summary of a batch, failing unless the webhook answers with a success status
+    pub fn notify(&self, summary: &Value) -> Result<()> {
+        self.agent
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .send(summary.to_string())
+            .with_context(|| format!("could not notify webhook {}", self.url))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn summary_test() {
+        let mut batch = Batch::default();
+        for _ in 0..SAMPLE_SIZE + 2 {
+            batch.add("22ITZXBZYUTJFLJXK6");
+        }
+        let summary = batch.summary();
+        assert_eq!(12, summary["count"]);
+        assert_eq!(SAMPLE_SIZE, summary["sample"].as_array().unwrap().len());
+
+        let mut batch = Batch::default();
+        batch.add("abc");
+        // sha256sum of a file holding "abc\n"
+        assert_eq!(
+            "edeaaff3f1774ad2888673770c6d64097e391bc362d7d6fb34982ddf0efd18cb",
+            batch.summary()["sha256"]
+        );
+    }
+
+    #[test]
+    fn notify_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/batches", listener.local_addr().unwrap());
+        let receiver = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                match line.trim_end().split_once(':') {
+                    Some((name, value)) if name.eq_ignore_ascii_case("content-length") => {
+                        content_length = value.trim().parse().unwrap()
+                    }
+                    None if line.trim_end().is_empty() => break,
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            (&stream)
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            serde_json::from_slice::<Value>(&body).unwrap()
+        });
+
+        let mut batch = Batch::default();
+        batch.add("22ITZXBZYUTJFLJXK6");
+        Webhook::new(&url).notify(&batch.summary()).unwrap();
+        let summary = receiver.join().unwrap();
+        assert_eq!(json!(["22ITZXBZYUTJFLJXK6"]), summary["sample"]);
+    }
+}
@@ -0,0 +1,233 @@
+//! Weights file of a generation run, the proportions of its countries, procedures and offices and
+//! of deliberately invalid MRNs, e.g.
+//!
+//! ```toml
+//! invalid-rate = 0.05
+//!
+//! [countries]
+//! DK = 3
+//! Germany = 1
+//!
+//! [procedures]
+//! import_declaration_only = 5
+//! transit_declaration_only = 2
+//!
+//! [offices]
+//! DK004700 = 2
+//! DK002000 = 1
+//! ```
+//!
+//! Countries without offices in the file get the office of the command line, if any. Files with
+//! a `.json` extension are read as JSON with the same keys, e.g. `{"countries": {"DK": 3}}`.
+
+use anyhow::{bail, Context, Result};
+use mrn_generator::country::resolve_country;
+use mrn_generator::{DeclarationOffice, Procedure};
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct WeightsFile {
+    #[serde(default)]
+    invalid_rate: f64,
+    #[serde(default)]
+    countries: BTreeMap<String, u32>,
+    #[serde(default)]
+    procedures: BTreeMap<String, u32>,
+    #[serde(default)]
+    offices: BTreeMap<String, u32>,
+}
+
+/// Weights of a generation run, checked when loaded
+#[derive(Debug, Default, PartialEq)]
+pub struct Weights {
+    pub countries: Vec<(String, u32)>,
+    pub procedures: Vec<(Procedure, u32)>,
+    /// Office numbers of each country and their weights, by country code
+    pub offices: BTreeMap<String, Vec<(String, u32)>>,
+    /// Share of the MRNs generated with a wrong check digit, from 0 to 1
+    pub invalid_rate: f64,
+}
+
+impl Weights {
+    /// Loads weights, as JSON if the file has a `.json` extension and TOML otherwise
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("could not read weights file {}", path.display()))?;
+        let weights = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Weights::from_json(&contents),
+            _ => Weights::from_toml(&contents),
+        };
+        weights.with_context(|| format!("could not load weights file {}", path.display()))
+    }
+
+    /// Parses weights from a TOML document
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        Weights::check(toml::from_str(contents)?)
+    }
+
+    /// Parses weights from a JSON document
+    pub fn from_json(contents: &str) -> Result<Self> {
+        Weights::check(serde_json::from_str(contents)?)
+    }
+
+    fn check(file: WeightsFile) -> Result<Self> {
+        if !(0.0..=1.0).contains(&file.invalid_rate) {
+            bail!(
+                "invalid-rate is {}, it should be between 0 and 1",
+                file.invalid_rate
+            );
+        }
+
+        let countries = file
+            .countries
+            .into_iter()
+            .map(|(country, weight)| {
+                let code =
+                    resolve_country(&country).with_context(|| format!("countries.{country}"))?;
+                Ok((code, weight))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        check_weights("countries", &countries)?;
+
+        let procedures = file
+            .procedures
+            .into_iter()
+            .map(|(name, weight)| {
+                let name: StrDeserializer<ValueError> = name.as_str().into_deserializer();
+                let procedure =
+                    Procedure::deserialize(name).with_context(|| "procedures".to_string())?;
+                Ok((procedure, weight))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        check_weights("procedures", &procedures)?;
+
+        let mut offices: BTreeMap<String, Vec<(String, u32)>> = BTreeMap::new();
+        for (reference, weight) in file.offices {
+            DeclarationOffice::new(&reference).with_context(|| format!("offices.{reference}"))?;
+            let Some((country, office)) = reference
+                .split_at_checked(2)
+                .filter(|(country, _)| country.chars().all(|c| c.is_ascii_uppercase()))
+            else {
+                bail!("offices.{reference}: expected an office reference starting with its country code, e.g. DK004700");
+            };
+            offices
+                .entry(country.to_string())
+                .or_default()
+                .push((office.to_string(), weight));
+        }
+        for (country, offices) in &offices {
+            check_weights(&format!("offices of {country}"), offices)?;
+        }
+
+        let weights = Weights {
+            countries,
+            procedures,
+            offices,
+            invalid_rate: file.invalid_rate,
+        };
+        if !weights.countries.is_empty() {
+            let codes: Vec<String> = weights
+                .countries
+                .iter()
+                .map(|(code, _)| code.clone())
+                .collect();
+            weights.check_offices(&codes)?;
+        }
+        Ok(weights)
+    }
+
+    /// Checks that every office is in one of the countries of the run
+    pub fn check_offices(&self, country_codes: &[String]) -> Result<()> {
+        if let Some(country) = self
+            .offices
+            .keys()
+            .find(|country| !country_codes.contains(country))
+        {
+            bail!("the offices of {country} are not in one of the countries of the run");
+        }
+        Ok(())
+    }
+
+    /// Procedures and their weights, if the file has any
+    pub fn procedures(&self) -> Option<(Vec<Procedure>, Vec<u32>)> {
+        (!self.procedures.is_empty()).then(|| self.procedures.iter().copied().unzip())
+    }
+}
+
+/// Checks that a table of weights, if not empty, has a weight above 0
+fn check_weights<T>(table: &str, weights: &[(T, u32)]) -> Result<()> {
+    if !weights.is_empty() && weights.iter().all(|(_, weight)| *weight == 0) {
+        bail!("the {table} all have a weight of 0");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn parse_weights_test() {
+        let weights = Weights::from_toml(
+            r#"
+            invalid-rate = 0.25
+
+            [countries]
+            DK = 3
+            Germany = 1
+
+            [procedures]
+            import_declaration_only = 5
+
+            [offices]
+            DK004700 = 2
+            DK002000 = 1
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            Weights {
+                countries: vec![("DK".to_string(), 3), ("DE".to_string(), 1)],
+                procedures: vec![(Procedure::ImportDeclarationOnly, 5)],
+                offices: BTreeMap::from([(
+                    "DK".to_string(),
+                    vec![("002000".to_string(), 1), ("004700".to_string(), 2)]
+                )]),
+                invalid_rate: 0.25,
+            },
+            weights
+        );
+
+        let error = |contents: &str| format!("{:#}", Weights::from_toml(contents).unwrap_err());
+        assert!(error("invalid-rate = 2.0").contains("between 0 and 1"));
+        assert!(error("[countries]\nDenmrak = 1").starts_with("countries.Denmrak"));
+        assert!(error("[countries]\nDK = 0").contains("all have a weight of 0"));
+        assert!(error("[procedures]\nimport = 1").contains("not a known procedure"));
+        assert!(error("[countries]\nDK = 1\n[offices]\nIT012345 = 1")
+            .contains("not in one of the countries"));
+        assert!(error("[offices]\n004700 = 1").contains("starting with its country code"));
+        assert!(error("[country]\nDK = 1").contains("unknown field"));
+
+        let json = Weights::from_json(
+            r#"{
+                "invalid-rate": 0.25,
+                "countries": {"DK": 3, "Germany": 1},
+                "procedures": {"import_declaration_only": 5},
+                "offices": {"DK004700": 2, "DK002000": 1}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(weights, json);
+        assert!(
+            format!("{:#}", Weights::from_json("{\"country\": {}}").unwrap_err())
+                .contains("unknown field")
+        );
+    }
+}